@@ -0,0 +1,72 @@
+//! Measure the time needed to construct chords and to find the best voice
+//! leading for a chord sequence.
+//!
+//! Run with `cargo bench --bench voice_lead`. Add `--features rayon` to
+//! compare the construction of the voicing graph for a long chord sequence
+//! with the chords searched in parallel.
+
+use std::{str::FromStr, time::Instant};
+
+use ukebox::{Chord, ChordSequence, ChordType, DistanceWeights, Note, VoicingConfig, VoicingGraph};
+
+const CHORD_SEQUENCE: &str = "C Am F G7 Em A7 Dm G7";
+
+const LONG_CHORD_SEQUENCE: &str = "Cmaj7 Am7 Dm7 G7 Em7 A7 Dm9 G13 Fmaj7 Bb7 Ebmaj7 Abmaj7 \
+                                   Dbmaj7 Gb7 Bm7b5 E7b9 Am7 D7 Gm7 C7 F6 Fm6 Em7 A7 \
+                                   Dm7 G7sus4 G7 C6";
+
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    let root = Note::from_str("C").unwrap();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS * 1000 {
+        for chord_type in ChordType::values() {
+            std::hint::black_box(Chord::new(root, chord_type));
+        }
+    }
+
+    println!(
+        "construction of {} chords: {:?}",
+        ChordType::values().count(),
+        start.elapsed() / (ITERATIONS * 1000)
+    );
+
+    let chord_seq = ChordSequence::from_str(CHORD_SEQUENCE).unwrap();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+        std::hint::black_box(voicing_graph.paths(1).count());
+    }
+
+    println!(
+        "voice leading for {} chords: {:?}",
+        chord_seq.chords().count(),
+        start.elapsed() / ITERATIONS
+    );
+
+    // Finding all paths is not feasible for long sequences, so only
+    // the construction of the graph is measured.
+    let chord_seq = ChordSequence::from_str(LONG_CHORD_SEQUENCE).unwrap();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+        std::hint::black_box(voicing_graph);
+    }
+
+    println!(
+        "voicing graph for {} chords: {:?}",
+        chord_seq.chords().count(),
+        start.elapsed() / ITERATIONS
+    );
+}
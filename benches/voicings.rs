@@ -0,0 +1,48 @@
+//! Compare the computation of all the voicings of a chord with the computation
+//! of the first few ones only.
+//!
+//! Run with `cargo bench --bench voicings`.
+
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use ukebox::{Chord, ChordType, Note, VoicingConfig};
+
+const ROOTS: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+const ITERATIONS: u32 = 20;
+
+/// Run `f` for all twelve major 13th chords and return the average time
+/// per iteration.
+fn measure(f: impl Fn(&Chord) -> usize) -> Duration {
+    let chords: Vec<Chord> = ROOTS
+        .iter()
+        .map(|root| Chord::new(Note::from_str(root).unwrap(), ChordType::MajorThirteenth))
+        .collect();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        for chord in &chords {
+            std::hint::black_box(f(chord));
+        }
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let config = VoicingConfig::default();
+
+    let all = measure(|chord| chord.voicings(config).count());
+    println!("maj13, all voicings:       {all:?}");
+
+    for max_count in [1, 10] {
+        let capped = measure(|chord| chord.voicings_capped(config, max_count).len());
+        println!("maj13, first {max_count:>2} voicings: {capped:?}");
+    }
+}
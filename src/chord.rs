@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     convert::TryFrom,
     fmt,
     ops::{Add, Sub},
@@ -6,17 +7,72 @@ use std::{
 };
 
 use itertools::Itertools;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    ChordType, NoMatchingChordTypeFoundError, Note, PitchClass, Semitones, UkeString, Voicing,
-    VoicingConfig, STRING_COUNT,
+    ChordType, Fingering, FretID, Interval, NoMatchingChordTypeFoundError, Note, PitchClass, Scale,
+    ScaleType, Semitones, Spelling, StaffSteps, UkeString, Voicing, VoicingConfig,
+    PITCH_CLASS_COUNT,
 };
 
+/// Maximal edit distance between an unknown chord symbol and a valid one
+/// for the latter to be suggested in a `ParseChordError`.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
 /// Custom error for strings that cannot be parsed into chords.
 #[derive(Debug, thiserror::Error)]
-#[error("could not parse chord name '{name}'")]
+#[error("could not parse chord name '{name}'{}", suggestion_hint(.suggestion))]
 pub struct ParseChordError {
     name: String,
+    suggestion: Option<String>,
+}
+
+impl ParseChordError {
+    /// Return the valid chord symbol closest to the one that could not be parsed
+    /// (if there is one), e.g. `CmMaj7` for `CmMaj7b5`.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+fn suggestion_hint(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(" (did you mean {suggestion}?)"),
+        None => String::new(),
+    }
+}
+
+/// Split `s` after its first `n` characters (e.g. after `C♯`, whose sharp sign
+/// takes up several bytes). Return `None` if `s` has fewer than `n` characters.
+fn split_chars(s: &str, n: usize) -> Option<(&str, &str)> {
+    let i = match s.char_indices().nth(n) {
+        Some((i, _c)) => i,
+        None if s.chars().count() == n => s.len(),
+        None => return None,
+    };
+
+    Some(s.split_at(i))
+}
+
+/// Return the number of single-character insertions, deletions and
+/// substitutions needed to turn `a` into `b` (the Levenshtein distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + (ca != *cb) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
 }
 
 /// A chord such as C, Cm and so on.
@@ -25,6 +81,12 @@ pub struct Chord {
     pub root: Note,
     pub chord_type: ChordType,
     pub notes: Vec<Note>,
+    /// The lowest note of the chord if it differs from the root (as in
+    /// inversions and slash chords such as `C/E`).
+    pub bass: Option<Note>,
+    /// The numbers of the intervals that are left out of the chord
+    /// (as in `C(no3)` or `Comit5`).
+    pub omitted: Vec<StaffSteps>,
 }
 
 impl Chord {
@@ -34,36 +96,329 @@ impl Chord {
             root,
             chord_type,
             notes,
+            bass: None,
+            omitted: vec![],
+        }
+    }
+
+    /// Return the same chord played with `bass` as the lowest note.
+    /// If `bass` is the root of the chord, no separate bass note is stored.
+    pub fn with_bass(self, bass: Note) -> Self {
+        let bass = match bass == self.root {
+            true => None,
+            false => Some(bass),
+        };
+
+        Self { bass, ..self }
+    }
+
+    /// Return the same chord without the tones at the interval with the given
+    /// `number` above the root, e.g. without the fifth for `number` 5.
+    /// The root cannot be omitted. If the chord does not contain such an interval,
+    /// it is returned unchanged.
+    pub fn omit(self, number: StaffSteps) -> Self {
+        let omitted_notes: Vec<Note> = self
+            .chord_type
+            .intervals()
+            .filter(|i| i.to_number() == number && number > 1)
+            .map(|i| self.root + i)
+            .collect();
+
+        if omitted_notes.is_empty() || self.omitted.contains(&number) {
+            return self;
+        }
+
+        let notes = self
+            .notes
+            .into_iter()
+            .filter(|n| !omitted_notes.contains(n))
+            .collect();
+        let mut omitted = self.omitted;
+        omitted.push(number);
+
+        Self {
+            notes,
+            omitted,
+            ..self
+        }
+    }
+
+    /// Return the spelling preferred by the chord, i.e. flats if its root
+    /// is a flat note (e.g. Bb) and sharps if its root is a sharp note (e.g. C#).
+    /// Chords with a natural root keep the spelling of their notes.
+    pub fn spelling(&self) -> Spelling {
+        self.root.spelling()
+    }
+
+    /// Return the same chord with its root, notes and bass note spelled
+    /// according to `spelling`.
+    pub fn respell(&self, spelling: Spelling) -> Self {
+        Self {
+            root: self.root.respell(spelling),
+            chord_type: self.chord_type,
+            notes: self.notes.iter().map(|n| n.respell(spelling)).collect(),
+            bass: self.bass.map(|b| b.respell(spelling)),
+            omitted: self.omitted.clone(),
+        }
+    }
+
+    /// Return `chord` with a bass note at the same interval above its root
+    /// as the bass note of the current chord (if there is any) and with
+    /// the same tones omitted.
+    fn transfer_alterations(&self, chord: Self) -> Self {
+        let chord = self.omitted.iter().fold(chord, |chord, &n| chord.omit(n));
+
+        match self.bass {
+            Some(bass) => {
+                let interval = Interval::from(bass.pitch_class - self.root.pitch_class);
+                let bass = chord.root + interval;
+                chord.with_bass(bass)
+            }
+            None => chord,
+        }
+    }
+
+    /// Split off a suffix such as `(no3)`, `no5` or `omit5` from the end of
+    /// a chord symbol. Return the remaining symbol and the number of the
+    /// omitted interval.
+    fn split_omission(s: &str) -> Option<(&str, StaffSteps)> {
+        let (inner, parenthesized) = match s.strip_suffix(')') {
+            Some(inner) => (inner, true),
+            None => (s, false),
+        };
+
+        let rest = inner.trim_end_matches(|c: char| c.is_ascii_digit());
+        let number = inner[rest.len()..].parse().ok()?;
+        let rest = rest
+            .strip_suffix("no")
+            .or_else(|| rest.strip_suffix("omit"))?;
+
+        match parenthesized {
+            true => Some((rest.strip_suffix('(')?, number)),
+            false => Some((rest, number)),
+        }
+    }
+
+    /// Split off all the omission suffixes from the end of a chord symbol.
+    /// Return the remaining symbol and the numbers of the omitted intervals
+    /// (in the order in which they appear in the symbol).
+    fn split_omissions(s: &str) -> (&str, Vec<StaffSteps>) {
+        let mut s = s;
+        let mut omitted = vec![];
+
+        while let Some((rest, number)) = Self::split_omission(s) {
+            s = rest;
+            omitted.insert(0, number);
+        }
+
+        (s, omitted)
+    }
+
+    /// Parse a chord symbol such as `C#m7` or `C(no3)` (without bass note).
+    fn parse_symbol(s: &str) -> Option<Self> {
+        let (s, omitted) = Self::split_omissions(s);
+
+        // All the omitted intervals must be part of the chord.
+        let chord = Self::parse_plain_symbol(s)?;
+        let chord = omitted.iter().try_fold(chord, |chord, &n| {
+            let omitted_count = chord.omitted.len();
+            let chord = chord.omit(n);
+            (chord.omitted.len() > omitted_count).then_some(chord)
+        })?;
+
+        Some(chord)
+    }
+
+    /// Parse a chord symbol such as `C#m7` (without bass note and omitted intervals).
+    fn parse_plain_symbol(s: &str) -> Option<Self> {
+        // 1. Check the two first characters of the input string (for notes such as `C#`).
+        // 2. Check only the first character (for notes such as `C`).
+        for i in (1..3).rev() {
+            if let Some((prefix, suffix)) = split_chars(s, i) {
+                // Try to convert the prefix into a `Note`.
+                if let Ok(root) = Note::from_str(prefix) {
+                    // Try to convert the remaining string into a `ChordType`.
+                    if let Ok(chord_type) = ChordType::from_str(suffix) {
+                        return Some(Self::new(root, chord_type));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Return the valid chord symbol that is closest to `s` (measured by the edit
+    /// distance between the chord type symbols), keeping the root and the bass note.
+    /// Return `None` if `s` does not start with a note or if no symbol is close enough.
+    fn suggest_symbol(s: &str) -> Option<String> {
+        let (s, bass) = match s.rsplit_once('/') {
+            Some((symbol, bass)) => (symbol, Some(Note::from_str(bass).ok()?)),
+            None => (s, None),
+        };
+
+        // Prefer two-character roots such as `C#` as when parsing the symbol.
+        let (root, suffix) = (1..3)
+            .rev()
+            .filter_map(|i| {
+                let (prefix, suffix) = split_chars(s, i)?;
+                Some((Note::from_str(prefix).ok()?, suffix))
+            })
+            .next()?;
+
+        let (distance, symbol) = ChordType::values()
+            .flat_map(|chord_type| chord_type.symbols())
+            .map(|symbol| (edit_distance(suffix, symbol), symbol))
+            .min_by_key(|(distance, _symbol)| *distance)?;
+
+        if distance > MAX_SUGGESTION_DISTANCE {
+            return None;
+        }
+
+        Some(match bass {
+            Some(bass) => format!("{root}{symbol}/{bass}"),
+            None => format!("{root}{symbol}"),
+        })
+    }
+
+    /// Return the chord symbol, e.g. `Cm7` or `C/E`.
+    pub fn name(&self) -> String {
+        let name = format!("{}{}", self.root, self.chord_type.to_symbol());
+        let name = self
+            .omitted
+            .iter()
+            .fold(name, |name, n| format!("{name}(no{n})"));
+
+        match self.bass {
+            Some(bass) => format!("{name}/{bass}"),
+            None => name,
         }
     }
 
     /// Return an iterator over the chord's notes that are played on our instrument.
     ///
-    /// If the chord contains more notes than we have strings on our instrument,
-    /// only required notes are played.
-    pub fn played_notes(&self) -> impl Iterator<Item = Note> + '_ {
-        self.chord_type
-            .required_intervals()
-            .chain(self.chord_type.optional_intervals())
-            .take(STRING_COUNT)
-            .map(move |i| self.root + i)
+    /// If the chord contains more notes than `string_count`, the number of strings
+    /// on our instrument, only required notes are played. A bass note that is not
+    /// part of the chord (such as the B in `C/B`) is always played.
+    pub fn played_notes(&self, string_count: usize) -> impl Iterator<Item = Note> + '_ {
+        let extra_bass = self.bass.filter(|bass| !self.notes.contains(bass));
+
+        extra_bass
+            .into_iter()
+            .chain(
+                self.chord_type
+                    .required_intervals()
+                    .chain(self.chord_type.optional_intervals())
+                    .filter(move |i| !self.omitted.contains(&i.to_number()))
+                    .map(move |i| self.root + i),
+            )
+            .take(string_count)
     }
 
+    /// Return an iterator over all voicings of the chord that fulfill the
+    /// conditions given by `config`.
+    ///
+    /// The voicings are ordered by position on the fretboard. If requested in `config`,
+    /// the easiest voicings come first. For slash chords, the voicings that have the
+    /// bass note as their lowest note come first.
     pub fn voicings(&self, config: VoicingConfig) -> impl Iterator<Item = Voicing> + '_ {
+        self.voicing_candidates(config, |_fret| true)
+            .sorted_by_cached_key(move |voicing| self.voicing_key(config, voicing))
+    }
+
+    /// Return the voicing of the chord that is easiest to play
+    /// (see `Voicing::difficulty`) within the limits of `config`.
+    ///
+    /// Voicings of the same difficulty are ranked by their position on the
+    /// fretboard, the lowest one first (see the ordering of `Voicing`).
+    /// For slash chords, voicings with the bass note as their lowest note
+    /// are preferred even if they are harder to play.
+    ///
+    /// Return `None` if the chord cannot be played within the limits of `config`.
+    pub fn best_voicing(&self, config: VoicingConfig) -> Option<Voicing> {
+        let config = VoicingConfig {
+            sort_by_difficulty: true,
+            ..config
+        };
+
+        self.voicings_capped(config, 1).into_iter().next()
+    }
+
+    /// Return the first `max_count` voicings of the chord in the same order
+    /// as `voicings`.
+    ///
+    /// This is cheaper than collecting all the voicings when only a few of them
+    /// are needed. If the voicings are ordered by position only, they are searched
+    /// position by position until enough of them have been found.
+    pub fn voicings_capped(&self, config: VoicingConfig, max_count: usize) -> Vec<Voicing> {
+        if config.sort_by_difficulty || self.bass.is_some() {
+            // Keep only the best voicings found so far in memory.
+            return self
+                .voicing_candidates(config, |_fret| true)
+                .k_smallest_by_key(max_count, |voicing| self.voicing_key(config, voicing))
+                .collect();
+        }
+
+        let mut voicings = vec![];
+
+        for position in 0..=config.max_fret {
+            if voicings.len() >= max_count {
+                break;
+            }
+
+            // Only consider the frets that can be combined with `position`
+            // as the lowest pressed fret.
+            let in_window =
+                move |fret| fret == 0 || (fret >= position && fret < position + config.max_span);
+
+            voicings.extend(
+                self.voicing_candidates(config, in_window)
+                    .filter(|voicing| voicing.get_min_pressed_fret() == position)
+                    .sorted(),
+            );
+        }
+
+        voicings.truncate(max_count);
+        voicings
+    }
+
+    /// Return the key by which the voicings of the chord are ordered.
+    fn voicing_key(&self, config: VoicingConfig, voicing: &Voicing) -> (bool, u32, Voicing) {
+        (
+            // For slash chords, prefer the voicings that actually have the bass note
+            // as their lowest note.
+            self.bass.is_some_and(|bass| voicing.bass() != bass),
+            // Optionally, put the voicings that are easiest to play first.
+            match config.sort_by_difficulty {
+                true => voicing.difficulty(),
+                false => 0,
+            },
+            voicing.clone(),
+        )
+    }
+
+    /// Return an iterator over all the (unordered) valid voicings of the chord
+    /// that only use frets for which `keep_fret` returns `true`.
+    fn voicing_candidates(
+        &self,
+        config: VoicingConfig,
+        keep_fret: impl Fn(FretID) -> bool + Copy + 'static,
+    ) -> impl Iterator<Item = Voicing> + '_ {
+        let string_count = config.tuning.string_count();
+
         config
             .tuning
             .roots()
+            .enumerate()
             // For each ukulele string, keep track of all the frets that when pressed down
             // while playing the string result in a note of the chord.
-            .map(|root| {
-                self.played_notes()
-                    // Allow each note to be checked twice on the fretboard.
-                    .cartesian_product(vec![0, 12])
-                    // Determine the fret on which `note` is played.
-                    .map(|(note, st)| (root, (note.pitch_class - root.pitch_class) + st, note))
-                    // Keep only frets within the given boundaries.
-                    .filter(|(_r, fret, _n)| fret >= &config.min_fret && fret <= &config.max_fret)
-                    .collect::<Vec<UkeString>>()
+            .map(move |(i, root)| {
+                // A drone string is always played open.
+                if config.drone == Some(i + 1) {
+                    return vec![(root, 0, root)];
+                }
+
+                self.string_candidates(config, root, string_count, keep_fret)
             })
             // At this point, we have collected all possible positions of the notes in the chord
             // on each ukulele string. Now let's check all combinations and determine the ones
@@ -72,8 +427,169 @@ impl Chord {
             // Create voicing from the UkeString vec.
             .map(|us_vec| Voicing::from(&us_vec[..]))
             // Keep only valid voicings.
-            .filter(|voicing| voicing.spells_out(self) && voicing.get_span() <= config.max_span)
-            .sorted()
+            .filter(move |voicing| {
+                self.is_spelled_out_by(voicing, config) && self.fulfills(voicing, config)
+            })
+    }
+
+    /// Return `true` if `voicing` fulfills the conditions given by `config`
+    /// that do not depend on how the chord is spelled out, i.e. the span,
+    /// playability, root position, number of open strings and pitch range.
+    fn fulfills(&self, voicing: &Voicing, config: VoicingConfig) -> bool {
+        voicing.get_span() <= config.max_span
+            && (!config.only_playable || Fingering::is_feasible(voicing))
+            && (!config.root_in_bass || voicing.bass() == self.bass.unwrap_or(self.root))
+            && config
+                .max_open_strings
+                .map_or(true, |max| voicing.count_open_strings() <= max)
+            // Keep only voicings whose notes all sound within the given pitch range.
+            && voicing.notes().all(|note| {
+                config
+                    .lowest_note
+                    .map_or(true, |low| note.midi_number() >= low.midi_number())
+                    && config
+                        .highest_note
+                        .map_or(true, |high| note.midi_number() <= high.midi_number())
+            })
+    }
+
+    /// Return all the frets that when pressed down on the string tuned to `root`
+    /// result in one of the notes played on `string_count` strings, together
+    /// with the notes as they sound on this string.
+    fn string_candidates(
+        &self,
+        config: VoicingConfig,
+        root: Note,
+        string_count: usize,
+        keep_fret: impl Fn(FretID) -> bool,
+    ) -> Vec<UkeString> {
+        self.played_notes(string_count)
+            // Allow each note to be checked twice on the fretboard.
+            .cartesian_product(vec![0, 12])
+            // Determine the fret on which `note` is played and place the note
+            // in the octave in which it sounds on this string.
+            .map(|(note, st)| {
+                let fret = (note.pitch_class - root.pitch_class) + st;
+                (root, fret, note.with_octave((root + fret).octave()))
+            })
+            // Keep only frets within the given boundaries.
+            .filter(|(_r, fret, _n)| {
+                fret >= &config.min_fret && fret <= &config.max_fret && keep_fret(*fret)
+            })
+            .collect()
+    }
+
+    /// Return an iterator over the three-note voicings of the chord played on
+    /// the strings in `string_set` only (counted from 1, starting at the leftmost
+    /// string in the notation of the tuning), all other strings being muted.
+    ///
+    /// Choosing adjacent strings yields close triads, while skipping a string
+    /// yields spread (e.g. drop-2) triads. For chords with more than three notes,
+    /// only the required ones are played (see `played_notes`). A drone string
+    /// given in `config` is played open, whether it belongs to `string_set` or not.
+    /// The voicings are filtered and ordered as in `voicings`.
+    /// The iterator is empty if `string_set` contains
+    /// the same string twice or a string that does not exist in the tuning.
+    pub fn triad_voicings(
+        &self,
+        config: VoicingConfig,
+        string_set: [usize; 3],
+    ) -> impl Iterator<Item = Voicing> + '_ {
+        let string_count = config.tuning.string_count();
+        let is_valid = string_set.iter().all_unique()
+            && string_set.iter().all(|&s| s >= 1 && s <= string_count);
+
+        config
+            .tuning
+            .roots()
+            .enumerate()
+            .map(move |(i, root)| match string_set.contains(&(i + 1)) {
+                _ if !is_valid => vec![],
+                // A drone string is always played open.
+                _ if config.drone == Some(i + 1) => vec![(root, 0, root)],
+                true => self.string_candidates(config, root, 3, |_fret| true),
+                // Placeholder for a string that will be muted.
+                false => vec![(root, 0, root)],
+            })
+            .multi_cartesian_product()
+            .filter(move |us_vec| {
+                let triad: Vec<UkeString> = string_set.iter().map(|&s| us_vec[s - 1]).collect();
+                Voicing::from(&triad[..]).spells_out(self)
+            })
+            .map(move |us_vec| {
+                (1..=string_count)
+                    .filter(|i| !string_set.contains(i) && config.drone != Some(*i))
+                    .fold(Voicing::from(&us_vec[..]), |voicing, i| voicing.mute(i - 1))
+            })
+            .filter(move |voicing| self.fulfills(voicing, config))
+            .sorted_by_cached_key(move |voicing| self.voicing_key(config, voicing))
+    }
+
+    /// Return `true` if `voicing` spells out the chord, taking into account
+    /// the drone string given in `config` (if any): a drone that is not
+    /// among the played notes of the chord is left out, unless it is required
+    /// to be a chord tone and is not.
+    fn is_spelled_out_by(&self, voicing: &Voicing, config: VoicingConfig) -> bool {
+        let string_count = config.tuning.string_count();
+
+        let drone = config
+            .drone
+            .and_then(|drone| Some((drone, config.tuning.roots().nth(drone.checked_sub(1)?)?)));
+        let (drone, drone_note) = match drone {
+            Some((drone, drone_note))
+                if !self.played_notes(string_count).any(|n| n == drone_note) =>
+            {
+                (drone, drone_note)
+            }
+            _ => return voicing.spells_out(self),
+        };
+
+        if config.drone_chord_tone && self.interval_of(drone_note).is_none() {
+            return false;
+        }
+
+        let others: Vec<UkeString> = voicing
+            .uke_strings()
+            .enumerate()
+            .filter(|(i, _s)| i + 1 != drone)
+            .map(|(_i, s)| *s)
+            .collect();
+
+        Voicing::from(&others[..]).spells_out(self)
+    }
+
+    /// Return an iterator over the voicings of the chord that can be played as
+    /// an arpeggio, i.e. the notes rise in pitch from one string to the next.
+    ///
+    /// Note that on a re-entrant tuning (e.g. the standard C tuning with a high G string),
+    /// ascending voicings are rare and can only be found high up the neck.
+    pub fn arpeggio_voicings(&self, config: VoicingConfig) -> impl Iterator<Item = Voicing> + '_ {
+        self.voicings(config).filter(|voicing| {
+            voicing
+                .notes()
+                .tuple_windows()
+                .all(|(n1, n2)| n1.midi_number() < n2.midi_number())
+        })
+    }
+
+    /// Return an iterator over all voicings of the chord, starting at the
+    /// voicing with index `offset` and wrapping around at the end.
+    ///
+    /// This makes it possible to cycle through the voicings of a chord
+    /// (e.g. for practicing) without always starting with the same shape.
+    pub fn voicing_rotated(
+        &self,
+        config: VoicingConfig,
+        offset: usize,
+    ) -> impl Iterator<Item = Voicing> {
+        let mut voicings: Vec<Voicing> = self.voicings(config).collect();
+
+        if !voicings.is_empty() {
+            let mid = offset % voicings.len();
+            voicings.rotate_left(mid);
+        }
+
+        voicings.into_iter()
     }
 
     pub fn transpose(&self, semitones: i8) -> Self {
@@ -82,12 +598,303 @@ impl Chord {
             _ => self.clone() + semitones as Semitones,
         }
     }
+
+    /// Determine all chords that are represented by the given notes.
+    ///
+    /// The lowest note (taking octaves into account) is regarded as the bass note
+    /// and stored in the chords whose root differs from it.
+    /// The most likely interpretation, i.e. the chord whose root is the bass
+    /// note, comes first. All other interpretations follow in the usual chord
+    /// order. Each chord's root is spelled as in `notes`.
+    ///
+    /// If the notes do not form a complete chord, the chords lacking the
+    /// fewest required notes are returned instead (see `ChordType::candidates`).
+    pub fn from_notes(notes: &[Note]) -> Vec<Self> {
+        let bass = match notes.iter().min_by_key(|n| n.midi_number()) {
+            Some(note) => *note,
+            None => return vec![],
+        };
+
+        let mut pitches: Vec<PitchClass> = notes.iter().map(|n| n.pitch_class).collect();
+        pitches.sort();
+        pitches.dedup();
+
+        let mut candidates = vec![];
+
+        // Rotate pitch class list and collect the best matching chord for each root.
+        // For example, try [C, DSharp, GSharp], [DSharp, GSharp, C], [GSharp, C, FSharp].
+        for _ in 0..pitches.len() {
+            if let Some(&chord_type) = ChordType::candidates(&pitches).first() {
+                let missing = chord_type.missing_intervals(&pitches).unwrap();
+                let root = notes.iter().find(|n| n.pitch_class == pitches[0]).unwrap();
+                candidates.push((missing, Self::new(*root, chord_type).with_bass(bass)));
+            }
+
+            pitches.rotate_left(1);
+        }
+
+        // Only fall back to incomplete chords if there is no exact match.
+        let min_missing = candidates.iter().map(|(missing, _)| *missing).min();
+
+        let mut chords: Vec<_> = candidates
+            .into_iter()
+            .filter(|(missing, _)| Some(*missing) == min_missing)
+            .map(|(_, chord)| chord)
+            .collect();
+
+        chords.sort_by_key(|chord| (chord.root != bass, chord.clone()));
+        chords
+    }
+
+    /// Return the inversion of the chord when played with `bass` as the lowest note,
+    /// i.e. 0 for root position, 1 for first inversion (third in the bass) and so on.
+    /// Return `None` if `bass` is not a note of the chord.
+    pub fn inversion(&self, bass: Note) -> Option<usize> {
+        self.notes.iter().position(|&n| n == bass)
+    }
+
+    /// Return the interval at which `note` is contained in the chord (counted
+    /// from the root), e.g. the minor seventh for Bb in C7.
+    /// Return `None` if `note` is not a chord tone.
+    pub fn interval_of(&self, note: Note) -> Option<Interval> {
+        self.chord_type
+            .intervals()
+            .filter(|i| !self.omitted.contains(&i.to_number()))
+            .find(|&i| self.root + i == note)
+    }
+
+    /// Return the Roman numeral of the chord relative to the given `key`,
+    /// e.g. `IV` for F major in the key of C or `vi` for A minor.
+    ///
+    /// Minor and diminished chords are written in lowercase, diminished chords
+    /// are additionally marked by `°`. Roots outside of the major scale of `key`
+    /// get a `b` or `#` prefix (e.g. `bVII` for Bb major in the key of C).
+    pub fn roman_numeral(&self, key: Note) -> String {
+        let numeral = match self.root.pitch_class - key.pitch_class {
+            0 => "I",
+            1 => "bII",
+            2 => "II",
+            3 => "bIII",
+            4 => "III",
+            5 => "IV",
+            6 => "#IV",
+            7 => "V",
+            8 => "bVI",
+            9 => "VI",
+            10 => "bVII",
+            _ => "VII",
+        };
+
+        let has = |interval| self.chord_type.intervals().any(|i| i == interval);
+
+        if has(Interval::MajorThird) || !has(Interval::MinorThird) {
+            return numeral.to_string();
+        }
+
+        match has(Interval::DiminishedFifth) {
+            true => format!("{}°", numeral.to_lowercase()),
+            false => numeral.to_lowercase(),
+        }
+    }
+
+    /// Return the Roman numeral analysis of the chord in the major key of `key`.
+    ///
+    /// In addition to the Roman numeral (see `roman_numeral`), seventh chords
+    /// are marked by `7`, `maj7`, `ø7` (half-diminished) or `°7` (diminished).
+    /// Non-diatonic dominant chords that resolve to a diatonic chord other than
+    /// the tonic are written as secondary dominants, e.g. `V7/V` for D7 in C.
+    pub fn analyze(&self, key: Note) -> String {
+        use Interval::*;
+
+        let has = |interval| self.chord_type.intervals().any(|i| i == interval);
+
+        let scale = Scale::new(key, ScaleType::Major);
+        let is_diatonic = self.notes.iter().all(|n| scale.notes().any(|s| s == *n));
+
+        let seventh = match (has(MinorSeventh), has(MajorSeventh), has(DiminishedSeventh)) {
+            (true, _, _) => "7",
+            (_, true, _) => "maj7",
+            (_, _, true) => "7",
+            _ => "",
+        };
+
+        let is_dominant = has(MajorThird) && has(PerfectFifth) && !has(MajorSeventh);
+
+        if !is_diatonic && is_dominant {
+            // The diatonic chords (except for the tonic) that can be
+            // the target of a secondary dominant.
+            let target = match (self.root.pitch_class + 5) - key.pitch_class {
+                2 => Some("ii"),
+                4 => Some("iii"),
+                5 => Some("IV"),
+                7 => Some("V"),
+                9 => Some("vi"),
+                _ => None,
+            };
+
+            if let Some(target) = target {
+                return format!("V{seventh}/{target}");
+            }
+        }
+
+        let numeral = self.roman_numeral(key);
+
+        match has(MinorSeventh) && has(DiminishedFifth) && has(MinorThird) {
+            true => format!("{}ø{seventh}", numeral.trim_end_matches('°')),
+            false => format!("{numeral}{seventh}"),
+        }
+    }
+
+    /// Return `true` if all the notes of the chord (including the bass note
+    /// of a slash chord) belong to the scale of `key`.
+    ///
+    /// In minor keys, the raised seventh of the harmonic minor scale is accepted
+    /// as well, so that e.g. E7 is diatonic to A minor.
+    pub fn is_diatonic(&self, key: Scale) -> bool {
+        let fits = |scale: Scale| {
+            self.notes
+                .iter()
+                .chain(&self.bass)
+                .all(|&n| scale.contains(n))
+        };
+
+        match key.scale_type {
+            ScaleType::NaturalMinor => {
+                fits(key) || fits(Scale::new(key.root, ScaleType::HarmonicMinor))
+            }
+            _ => fits(key),
+        }
+    }
+
+    /// Return all chords that can be played with at most `max_pressed_strings`
+    /// strings pressed down (and all other strings played open) within the
+    /// bounds given by `config`.
+    ///
+    /// With `max_pressed_strings` set to 0, these are the chords that are
+    /// formed by the open strings of the tuning (e.g. C6 and Am7 in C tuning).
+    pub fn open_chords(config: VoicingConfig, max_pressed_strings: usize) -> Vec<Self> {
+        (0..PITCH_CLASS_COUNT)
+            .map(|pc| Note::from(PitchClass::from(pc)))
+            .cartesian_product(ChordType::values().collect_vec())
+            .map(|(root, chord_type)| Self::new(root, chord_type))
+            // Chord types such as add13 and 6 consist of the same pitches,
+            // so only keep the first of them.
+            .unique_by(|chord| {
+                let pitches: BTreeSet<_> =
+                    chord.notes.iter().map(|n| n.pitch_class as u8).collect();
+                (chord.root.pitch_class as u8, pitches)
+            })
+            .filter(|chord| {
+                chord
+                    .voicings(config)
+                    .any(|v| v.count_pressed_strings() <= max_pressed_strings)
+            })
+            .collect()
+    }
+
+    /// Return all chords that can be played within the bounds given by `config`
+    /// (without muting any string), each together with its easiest voicing.
+    /// The strings whose index is contained in `open_strings` are always played open.
+    ///
+    /// Inversions are named by their root position chord (e.g. C rather than C/E).
+    /// The chords are ordered by the difficulty of their voicing (see
+    /// `Voicing::difficulty`), the easiest ones first.
+    pub fn playable_chords(config: VoicingConfig, open_strings: &[usize]) -> Vec<(Self, Voicing)> {
+        config
+            .tuning
+            .roots()
+            .enumerate()
+            .map(|(i, root)| {
+                let frets = match open_strings.contains(&i) {
+                    true => 0..=0,
+                    false => config.min_fret..=config.max_fret,
+                };
+
+                frets
+                    .map(|fret| (root, fret, root + fret))
+                    .collect::<Vec<UkeString>>()
+            })
+            .multi_cartesian_product()
+            .map(|us_vec| Voicing::from(&us_vec[..]))
+            .filter(|voicing| voicing.get_span() <= config.max_span)
+            .flat_map(|voicing| {
+                voicing
+                    .get_chords()
+                    .into_iter()
+                    // Skip incomplete chords.
+                    .filter(|chord| voicing.spells_out(chord))
+                    .map(|chord| {
+                        (
+                            Self {
+                                bass: None,
+                                ..chord
+                            },
+                            voicing.clone(),
+                        )
+                    })
+                    .collect_vec()
+            })
+            .sorted_by_cached_key(|(chord, voicing)| {
+                (voicing.difficulty(), voicing.clone(), chord.clone())
+            })
+            .unique_by(|(chord, _voicing)| chord.name())
+            .collect()
+    }
+
+    /// Transpose the chord up by `interval`.
+    ///
+    /// In contrast to `transpose`, the root note is spelled according to
+    /// the interval, e.g. transposing C up a minor third yields Eb (not D#).
+    pub fn transpose_by(&self, interval: Interval) -> Self {
+        self.clone() + interval
+    }
+
+    /// Return the chord of the relative minor key, i.e. the minor chord whose root is
+    /// a minor third below the root of this major chord (e.g. Am for C).
+    ///
+    /// Major seventh, ninth, eleventh and thirteenth chords are turned into the
+    /// corresponding minor chords (e.g. Am7 for Cmaj7). Return `None` for all
+    /// other chord types.
+    pub fn relative_minor(&self) -> Option<Self> {
+        use ChordType::*;
+
+        let chord_type = match self.chord_type {
+            Major => Minor,
+            MajorSeventh => MinorSeventh,
+            MajorNinth => MinorNinth,
+            MajorEleventh => MinorEleventh,
+            MajorThirteenth => MinorThirteenth,
+            _ => return None,
+        };
+
+        // A minor third down is the same pitch class as a major sixth up.
+        Some(Self::new(self.root + Interval::MajorSixth, chord_type))
+    }
+
+    /// Return the chord of the relative major key, i.e. the major chord whose root is
+    /// a minor third above the root of this minor chord (e.g. C for Am).
+    ///
+    /// This is the inverse of `relative_minor`. Return `None` for all chord types
+    /// other than minor triads and minor seventh, ninth, eleventh and thirteenth chords.
+    pub fn relative_major(&self) -> Option<Self> {
+        use ChordType::*;
+
+        let chord_type = match self.chord_type {
+            Minor => Major,
+            MinorSeventh => MajorSeventh,
+            MinorNinth => MajorNinth,
+            MinorEleventh => MajorEleventh,
+            MinorThirteenth => MajorThirteenth,
+            _ => return None,
+        };
+
+        Some(Self::new(self.root + Interval::MinorThird, chord_type))
+    }
 }
 
 impl fmt::Display for Chord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let name = format!("{}{}", self.root, self.chord_type.to_symbol());
-        write!(f, "{name} - {} {}", self.root, self.chord_type)
+        write!(f, "{} - {} {}", self.name(), self.root, self.chord_type)
     }
 }
 
@@ -95,24 +902,37 @@ impl FromStr for Chord {
     type Err = ParseChordError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // 1. Check the two first characters of the input string (for notes such as `C#`).
-        // 2. Check only the first character (for notes such as `C`).
-        for i in (1..3).rev() {
-            if let Some(prefix) = s.get(0..i) {
-                // Try to convert the prefix into a `Note`.
-                if let Ok(root) = Note::from_str(prefix) {
-                    // Try to convert the remaining string into a `ChordType`.
-                    if let Some(suffix) = s.get(i..) {
-                        if let Ok(chord_type) = ChordType::from_str(suffix) {
-                            return Ok(Self::new(root, chord_type));
-                        }
-                    }
-                }
+        if let Some(chord) = Self::parse_symbol(s) {
+            return Ok(chord);
+        }
+
+        // Try to interpret the input as a slash chord such as `C/G`.
+        if let Some((symbol, bass)) = s.rsplit_once('/') {
+            if let (Some(chord), Ok(bass)) = (Self::parse_symbol(symbol), Note::from_str(bass)) {
+                return Ok(chord.with_bass(bass));
             }
         }
 
         let name = s.to_string();
-        Err(ParseChordError { name })
+        let suggestion = Self::suggest_symbol(s);
+        Err(ParseChordError { name, suggestion })
+    }
+}
+
+/// Chords are serialized by their symbols, e.g. `"Cmaj7"` or `"C/G"`.
+#[cfg(feature = "serde")]
+impl Serialize for Chord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+/// Chords are deserialized from their symbols (see `from_str`).
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Chord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(de::Error::custom)
     }
 }
 
@@ -128,11 +948,21 @@ impl TryFrom<&[PitchClass]> for Chord {
     }
 }
 
+impl Add<Interval> for Chord {
+    type Output = Self;
+
+    fn add(self, interval: Interval) -> Self {
+        let chord = Self::new(self.root + interval, self.chord_type);
+        self.transfer_alterations(chord)
+    }
+}
+
 impl Add<Semitones> for Chord {
     type Output = Self;
 
     fn add(self, n: Semitones) -> Self {
-        Self::new(self.root + n, self.chord_type)
+        let chord = Self::new(self.root + n, self.chord_type);
+        self.transfer_alterations(chord)
     }
 }
 
@@ -140,16 +970,21 @@ impl Sub<Semitones> for Chord {
     type Output = Self;
 
     fn sub(self, n: Semitones) -> Self {
-        Self::new(self.root - n, self.chord_type)
+        let chord = Self::new(self.root - n, self.chord_type);
+        self.transfer_alterations(chord)
     }
 }
 
 #[cfg(test)]
+// The tests of the extended chords take one argument per chord tone, which rstest
+// passes on to the test functions it generates (so the lint cannot be allowed on them).
+#[allow(clippy::too_many_arguments)]
 mod tests {
     use rstest::rstest;
     use PitchClass::*;
 
     use super::*;
+    use crate::{CustomTuning, FretPattern, Octave, Tuning, STRING_COUNT};
 
     #[rstest(
         chord,
@@ -159,46 +994,202 @@ mod tests {
         case("C#mb5"),
         case("C#mbla"),
         case("CmMaj"),
-        case("CmMaj7b5")
+        case("CmMaj7b5"),
+        case("C/H"),
+        case("C/"),
+        case("/C"),
+        case("H/C"),
+        case("C/G/E"),
+        case("C(no9)"),
+        case("C(no1)"),
+        case("C(no5)(no5)"),
+        case("C(no5"),
+        case("Cno"),
+        case("Comit")
     )]
     fn test_from_str_fail(chord: &str) {
         assert!(Chord::from_str(chord).is_err());
     }
 
     #[rstest(
-        chord_base,
-        root,
-        third,
-        fifth,
-        case("C", "C", "E", "G"),
-        case("C#", "C#", "F", "G#"),
-        case("Db", "Db", "F", "Ab"),
-        case("D", "D", "F#", "A"),
-        case("D#", "D#", "G", "A#"),
-        case("Eb", "Eb", "G", "Bb"),
-        case("E", "E", "G#", "B"),
-        case("F", "F", "A", "C"),
-        case("F#", "F#", "A#", "C#"),
-        case("Gb", "Gb", "Bb", "Db"),
-        case("G", "G", "B", "D"),
-        case("G#", "G#", "C", "D#"),
-        case("Ab", "Ab", "C", "Eb"),
-        case("A", "A", "C#", "E"),
-        case("A#", "A#", "D", "F"),
-        case("Bb", "Bb", "D", "F"),
-        case("B", "B", "D#", "F#")
+        s,
+        chord,
+        case("C♯m7", "C#m7"),
+        case("E♭maj7", "Ebmaj7"),
+        case("B♭/D", "Bb/D"),
+        case("CMAJ7", "Cmaj7"),
+        case("CMaj7", "Cmaj7"),
+        case("Gsus4", "Gsus4"),
+        case("GSUS4", "Gsus4"),
+        // Exact matches take precedence: CM7 is major and Cm7 is minor.
+        case("CM7", "Cmaj7"),
+        case("Cm7", "Cm7")
     )]
-    fn test_from_str_major(
-        #[values("", "maj", "M")] chord_suffix: &str,
-        chord_base: &str,
-        root: Note,
-        third: Note,
-        fifth: Note,
-    ) {
-        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
-
-        assert_eq!(chord.notes, vec![root, third, fifth]);
-        assert_eq!(chord.chord_type, ChordType::Major);
+    fn test_from_str_lenient(s: &str, chord: Chord) {
+        assert_eq!(Chord::from_str(s).unwrap(), chord);
+    }
+
+    #[rstest(
+        s,
+        n,
+        split,
+        case("C#m", 2, Some(("C#", "m"))),
+        case("C♯m", 2, Some(("C♯", "m"))),
+        case("C♯", 1, Some(("C", "♯"))),
+        case("C", 1, Some(("C", ""))),
+        case("C", 2, None)
+    )]
+    fn test_split_chars(s: &str, n: usize, split: Option<(&str, &str)>) {
+        assert_eq!(split_chars(s, n), split);
+    }
+
+    #[rstest(
+        chord,
+        suggestion,
+        case("CmMaj7b5", Some("CmMaj7")),
+        case("Cmajj7", Some("Cmaj7")),
+        case("F#mi", Some("F#m")),
+        case("Bbmaj9x", Some("Bbmaj9")),
+        case("Cmaj7b5/E", Some("Cmaj7/E")),
+        case("blafoo", None),
+        case("Cxyzxyz", None),
+        case("Cmaj7b5/H", None),
+        case("H7", None)
+    )]
+    fn test_from_str_suggestion(chord: &str, suggestion: Option<&str>) {
+        let error = Chord::from_str(chord).unwrap_err();
+        assert_eq!(error.suggestion(), suggestion);
+    }
+
+    #[rstest(
+        chord,
+        message,
+        case(
+            "CmMaj7b5",
+            "could not parse chord name 'CmMaj7b5' (did you mean CmMaj7?)"
+        ),
+        case("blafoo", "could not parse chord name 'blafoo'")
+    )]
+    fn test_from_str_error_message(chord: &str, message: &str) {
+        assert_eq!(Chord::from_str(chord).unwrap_err().to_string(), message);
+    }
+
+    #[rstest(
+        a,
+        b,
+        distance,
+        case("", "", 0),
+        case("maj7", "maj7", 0),
+        case("", "maj7", 4),
+        case("maj7b5", "m7b5", 2),
+        case("mi", "m", 1),
+        case("ø7", "o7", 1)
+    )]
+    fn test_edit_distance(a: &str, b: &str, distance: usize) {
+        assert_eq!(edit_distance(a, b), distance);
+        assert_eq!(edit_distance(b, a), distance);
+    }
+
+    #[rstest(
+        chord,
+        notes,
+        name,
+        case("C(no5)", vec!["C", "E"], "C(no5)"),
+        case("Cno5", vec!["C", "E"], "C(no5)"),
+        case("Comit5", vec!["C", "E"], "C(no5)"),
+        case("C(omit3)", vec!["C", "G"], "C(no3)"),
+        case("Cm7(no5)", vec!["C", "Eb", "Bb"], "Cm7(no5)"),
+        case("C7(no3)(no5)", vec!["C", "Bb"], "C7(no3)(no5)"),
+        case("C9no5/E", vec!["C", "E", "Bb", "D"], "C9(no5)/E"),
+        case("Bbmaj7no3", vec!["Bb", "F", "A"], "Bbmaj7(no3)")
+    )]
+    fn test_from_str_omitted(chord: Chord, notes: Vec<&str>, name: &str) {
+        let notes: Vec<Note> = notes.iter().map(|s| Note::from_str(s).unwrap()).collect();
+        assert_eq!(chord.notes, notes);
+        assert_eq!(chord.name(), name);
+        assert_eq!(Chord::from_str(name).unwrap(), chord);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest(
+        chord,
+        json,
+        case("Cmaj7", r#""Cmaj7""#),
+        case("F#m7b5", r#""F#m7b5""#),
+        case("Bb/D", r#""Bb/D""#),
+        case("C9no5/E", r#""C9(no5)/E""#),
+        case("Ebsus4", r#""Ebsus4""#)
+    )]
+    fn test_serde_round_trip(chord: Chord, json: &str) {
+        assert_eq!(serde_json::to_string(&chord).unwrap(), json);
+
+        let deserialized: Chord = serde_json::from_str(json).unwrap();
+        assert_eq!(deserialized, chord);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest(
+        json,
+        case(r#""H""#),
+        case(r#""Cmajestic""#),
+        case(r#"{"root":"C","chord_type":"Major"}"#),
+        case(r#"["C"]"#)
+    )]
+    fn test_deserialize_fail(json: &str) {
+        assert!(serde_json::from_str::<Chord>(json).is_err());
+    }
+
+    #[rstest(
+        chord,
+        case("C(no5)"),
+        case("C(no3)"),
+        case("G7(no3)"),
+        case("Am(no5)")
+    )]
+    fn test_voicings_omitted(chord: Chord) {
+        let config = VoicingConfig::default();
+        assert!(chord.voicings(config).next().is_some());
+
+        for voicing in chord.voicings(config) {
+            assert!(voicing.spells_out(&chord));
+            assert!(voicing.notes().all(|n| chord.notes.contains(&n)));
+        }
+    }
+
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        fifth,
+        case("C", "C", "E", "G"),
+        case("C#", "C#", "F", "G#"),
+        case("Db", "Db", "F", "Ab"),
+        case("D", "D", "F#", "A"),
+        case("D#", "D#", "G", "A#"),
+        case("Eb", "Eb", "G", "Bb"),
+        case("E", "E", "G#", "B"),
+        case("F", "F", "A", "C"),
+        case("F#", "F#", "A#", "C#"),
+        case("Gb", "Gb", "Bb", "Db"),
+        case("G", "G", "B", "D"),
+        case("G#", "G#", "C", "D#"),
+        case("Ab", "Ab", "C", "Eb"),
+        case("A", "A", "C#", "E"),
+        case("A#", "A#", "D", "F"),
+        case("Bb", "Bb", "D", "F"),
+        case("B", "B", "D#", "F#")
+    )]
+    fn test_from_str_major(
+        #[values("", "maj", "M")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        fifth: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(chord.notes, vec![root, third, fifth]);
+        assert_eq!(chord.chord_type, ChordType::Major);
     }
 
     #[rstest(
@@ -737,6 +1728,141 @@ mod tests {
         assert_eq!(chord.chord_type, ChordType::DominantSeventhFlatFifth);
     }
 
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        fifth,
+        seventh,
+        eleventh,
+        case("C", "C", "E", "G", "A#", "F#"),
+        case("C#", "C#", "F", "G#", "B", "G"),
+        case("Db", "Db", "F", "Ab", "B", "G"),
+        case("D", "D", "F#", "A", "C", "G#"),
+        case("D#", "D#", "G", "A#", "C#", "A"),
+        case("Eb", "Eb", "G", "Bb", "Db", "A"),
+        case("E", "E", "G#", "B", "D", "A#"),
+        case("F", "F", "A", "C", "Eb", "B"),
+        case("F#", "F#", "A#", "C#", "E", "C"),
+        case("Gb", "Gb", "Bb", "Db", "E", "C"),
+        case("G", "G", "B", "D", "F", "C#"),
+        case("G#", "G#", "C", "D#", "F#", "D"),
+        case("Ab", "Ab", "C", "Eb", "Gb", "D"),
+        case("A", "A", "C#", "E", "G", "D#"),
+        case("A#", "A#", "D", "F", "G#", "E"),
+        case("Bb", "Bb", "D", "F", "Ab", "E"),
+        case("B", "B", "D#", "F#", "A", "F")
+    )]
+    fn test_from_str_dominant_seventh_sharp_eleventh(
+        #[values("7#11")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        fifth: Note,
+        seventh: Note,
+        eleventh: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(chord.notes, vec![root, third, fifth, seventh, eleventh]);
+        assert_eq!(chord.chord_type, ChordType::DominantSeventhSharpEleventh);
+    }
+
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        fifth,
+        seventh,
+        thirteenth,
+        case("C", "C", "E", "G", "A#", "G#"),
+        case("C#", "C#", "F", "G#", "B", "A"),
+        case("Db", "Db", "F", "Ab", "B", "A"),
+        case("D", "D", "F#", "A", "C", "A#"),
+        case("D#", "D#", "G", "A#", "C#", "B"),
+        case("Eb", "Eb", "G", "Bb", "Db", "B"),
+        case("E", "E", "G#", "B", "D", "C"),
+        case("F", "F", "A", "C", "Eb", "Db"),
+        case("F#", "F#", "A#", "C#", "E", "D"),
+        case("Gb", "Gb", "Bb", "Db", "E", "D"),
+        case("G", "G", "B", "D", "F", "D#"),
+        case("G#", "G#", "C", "D#", "F#", "E"),
+        case("Ab", "Ab", "C", "Eb", "Gb", "E"),
+        case("A", "A", "C#", "E", "G", "F"),
+        case("A#", "A#", "D", "F", "G#", "F#"),
+        case("Bb", "Bb", "D", "F", "Ab", "Gb"),
+        case("B", "B", "D#", "F#", "A", "G")
+    )]
+    fn test_from_str_dominant_seventh_flat_thirteenth(
+        #[values("7b13")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        fifth: Note,
+        seventh: Note,
+        thirteenth: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(chord.notes, vec![root, third, fifth, seventh, thirteenth]);
+        assert_eq!(chord.chord_type, ChordType::DominantSeventhFlatThirteenth);
+    }
+
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        flat_fifth,
+        sharp_fifth,
+        seventh,
+        flat_ninth,
+        sharp_ninth,
+        case("C", "C", "E", "F#", "G#", "A#", "C#", "D#"),
+        case("C#", "C#", "F", "G", "A", "B", "D", "E"),
+        case("Db", "Db", "F", "G", "A", "B", "D", "E"),
+        case("D", "D", "F#", "G#", "A#", "C", "D#", "F"),
+        case("D#", "D#", "G", "A", "B", "C#", "E", "F#"),
+        case("Eb", "Eb", "G", "A", "B", "Db", "E", "Gb"),
+        case("E", "E", "G#", "A#", "C", "D", "F", "G"),
+        case("F", "F", "A", "B", "Db", "Eb", "Gb", "Ab"),
+        case("F#", "F#", "A#", "C", "D", "E", "G", "A"),
+        case("Gb", "Gb", "Bb", "C", "D", "E", "G", "A"),
+        case("G", "G", "B", "C#", "D#", "F", "G#", "A#"),
+        case("G#", "G#", "C", "D", "E", "F#", "A", "B"),
+        case("Ab", "Ab", "C", "D", "E", "Gb", "A", "B"),
+        case("A", "A", "C#", "D#", "F", "G", "A#", "C"),
+        case("A#", "A#", "D", "E", "F#", "G#", "B", "C#"),
+        case("Bb", "Bb", "D", "E", "Gb", "Ab", "B", "Db"),
+        case("B", "B", "D#", "F", "G", "A", "C", "D")
+    )]
+    fn test_from_str_altered_dominant_seventh(
+        #[values("7alt", "alt")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        flat_fifth: Note,
+        sharp_fifth: Note,
+        seventh: Note,
+        flat_ninth: Note,
+        sharp_ninth: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(
+            chord.notes,
+            vec![
+                root,
+                third,
+                flat_fifth,
+                sharp_fifth,
+                seventh,
+                flat_ninth,
+                sharp_ninth
+            ]
+        );
+        assert_eq!(chord.chord_type, ChordType::AlteredDominantSeventh);
+    }
+
     #[rstest(
         chord_base,
         root,
@@ -1501,6 +2627,113 @@ mod tests {
         assert_eq!(chord.chord_type, ChordType::AddedFourth);
     }
 
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        fifth,
+        eleventh,
+        case("C", "C", "E", "G", "F"),
+        case("C#", "C#", "F", "G#", "F#"),
+        case("Db", "Db", "F", "Ab", "Gb"),
+        case("D", "D", "F#", "A", "G"),
+        case("D#", "D#", "G", "A#", "G#"),
+        case("Eb", "Eb", "G", "Bb", "Ab"),
+        case("E", "E", "G#", "B", "A"),
+        case("F", "F", "A", "C", "Bb"),
+        case("F#", "F#", "A#", "C#", "B"),
+        case("Gb", "Gb", "Bb", "Db", "B"),
+        case("G", "G", "B", "D", "C"),
+        case("G#", "G#", "C", "D#", "C#"),
+        case("Ab", "Ab", "C", "Eb", "Db"),
+        case("A", "A", "C#", "E", "D"),
+        case("A#", "A#", "D", "F", "D#"),
+        case("Bb", "Bb", "D", "F", "Eb"),
+        case("B", "B", "D#", "F#", "E")
+    )]
+    fn test_from_str_added_eleventh(
+        #[values("add11")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        fifth: Note,
+        eleventh: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(chord.notes, vec![root, third, fifth, eleventh]);
+        assert_eq!(chord.chord_type, ChordType::AddedEleventh);
+    }
+
+    #[rstest(
+        chord_base,
+        root,
+        third,
+        fifth,
+        thirteenth,
+        case("C", "C", "E", "G", "A"),
+        case("C#", "C#", "F", "G#", "A#"),
+        case("Db", "Db", "F", "Ab", "Bb"),
+        case("D", "D", "F#", "A", "B"),
+        case("D#", "D#", "G", "A#", "C"),
+        case("Eb", "Eb", "G", "Bb", "C"),
+        case("E", "E", "G#", "B", "C#"),
+        case("F", "F", "A", "C", "D"),
+        case("F#", "F#", "A#", "C#", "D#"),
+        case("Gb", "Gb", "Bb", "Db", "Eb"),
+        case("G", "G", "B", "D", "E"),
+        case("G#", "G#", "C", "D#", "F"),
+        case("Ab", "Ab", "C", "Eb", "F"),
+        case("A", "A", "C#", "E", "F#"),
+        case("A#", "A#", "D", "F", "G"),
+        case("Bb", "Bb", "D", "F", "G"),
+        case("B", "B", "D#", "F#", "G#")
+    )]
+    fn test_from_str_added_thirteenth(
+        #[values("add13")] chord_suffix: &str,
+        chord_base: &str,
+        root: Note,
+        third: Note,
+        fifth: Note,
+        thirteenth: Note,
+    ) {
+        let chord = Chord::from_str(&format!("{chord_base}{chord_suffix}")).unwrap();
+
+        assert_eq!(chord.notes, vec![root, third, fifth, thirteenth]);
+        assert_eq!(chord.chord_type, ChordType::AddedThirteenth);
+    }
+
+    #[test]
+    fn test_voicings_custom_tuning() {
+        let chord = Chord::from_str("C").unwrap();
+        let tuning = Tuning::Custom(CustomTuning::from_str("A D F# B").unwrap());
+        let config = VoicingConfig {
+            tuning,
+            ..Default::default()
+        };
+
+        let voicing = chord.voicings(config).next().unwrap();
+        assert_eq!(voicing, Voicing::new([3, 2, 1, 1], tuning));
+
+        // The custom tuning changes the frets compared to the standard tuning.
+        let default_voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        assert_ne!(
+            voicing.fret_pattern().collect_vec(),
+            default_voicing.fret_pattern().collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_voicings_fifth_octave() {
+        // Power chords are also voiced with the root or the fifth doubled an octave higher.
+        let chord = Chord::from_str("C5").unwrap();
+        let voicing = Voicing::new([0, 0, 3, 3], Tuning::C);
+
+        assert!(chord
+            .voicings(VoicingConfig::default())
+            .any(|v| v == voicing));
+    }
+
     #[rstest(
         pitches,
         chord,
@@ -1531,6 +2764,22 @@ mod tests {
         assert_eq!(Chord::try_from(&pitches[..]).unwrap(), chord);
     }
 
+    #[rstest(
+        pitches,
+        case(vec![]),
+        case(vec![C, E]),
+        case(vec![C, CSharp, D]),
+    )]
+    fn test_get_chord_type_error(pitches: Vec<PitchClass>) {
+        fn describe(error: &dyn std::error::Error) -> String {
+            error.to_string()
+        }
+
+        let error = Chord::try_from(&pitches[..]).unwrap_err();
+        assert_eq!(error, NoMatchingChordTypeFoundError);
+        assert_eq!(describe(&error), "no matching chord type found");
+    }
+
     #[rstest(
         chord1,
         n,
@@ -1589,12 +2838,37 @@ mod tests {
         case("Adim", -3, "Gbdim"),
         case("A", -12, "A"),
         case("A#", -12, "A#"),
-        case("Ab", -12, "Ab")
+        case("Ab", -12, "Ab"),
+        case("C(no5)", 2, "D(no5)"),
+        case("C7(no3)/E", -1, "B7(no3)/D#")
     )]
     fn test_transpose(chord1: Chord, n: i8, chord2: Chord) {
         assert_eq!(chord1.transpose(n), chord2);
     }
 
+    #[rstest(
+        chord1,
+        interval,
+        chord2,
+        case("C", Interval::PerfectUnison, "C"),
+        case("C", Interval::MinorThird, "Eb"),
+        case("C", Interval::MajorThird, "E"),
+        case("Cm", Interval::MinorSecond, "Dbm"),
+        case("C7", Interval::AugmentedFifth, "G#7"),
+        case("C7", Interval::MinorSixth, "Ab7"),
+        case("A", Interval::MinorThird, "C"),
+        case("F#m", Interval::MajorSecond, "G#m"),
+        case("Eb", Interval::PerfectFourth, "Ab"),
+        case("Bb", Interval::MajorSeventh, "A")
+    )]
+    fn test_transpose_by(chord1: Chord, interval: Interval, chord2: &str) {
+        let transposed = chord1.transpose_by(interval);
+        assert_eq!(
+            transposed.to_string(),
+            Chord::from_str(chord2).unwrap().to_string()
+        );
+    }
+
     #[rstest(
         chord,
         played_notes,
@@ -1604,7 +2878,7 @@ mod tests {
         case("C13", vec!["C", "E", "Bb", "A"]),
     )]
     fn test_played_notes(chord: Chord, played_notes: Vec<&str>) {
-        let pn1: Vec<_> = chord.played_notes().collect();
+        let pn1: Vec<_> = chord.played_notes(STRING_COUNT).collect();
         let pn2: Vec<_> = played_notes
             .iter()
             .map(|&s| Note::from_str(s).unwrap())
@@ -1612,4 +2886,896 @@ mod tests {
 
         assert_eq!(pn1, pn2);
     }
+
+    #[rstest(chord, case("C"), case("G7"), case("Am"))]
+    fn test_voicing_rotated(chord: Chord) {
+        let config = VoicingConfig::default();
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+        let count = voicings.len();
+
+        // Successive offsets start with different voicings and, all together,
+        // cover the whole set of voicings.
+        let first_voicings: Vec<Voicing> = (0..count)
+            .map(|offset| chord.voicing_rotated(config, offset).next().unwrap())
+            .collect();
+        assert_eq!(first_voicings, voicings);
+
+        // Each rotation contains all the voicings in the original order.
+        for offset in 0..count {
+            let rotated: Vec<Voicing> = chord.voicing_rotated(config, offset).collect();
+            assert_eq!(rotated.len(), count);
+            assert_eq!(rotated[..count - offset], voicings[offset..]);
+            assert_eq!(rotated[count - offset..], voicings[..offset]);
+        }
+
+        // Offsets wrap around.
+        assert!(chord
+            .voicing_rotated(config, count)
+            .eq(chord.voicing_rotated(config, 0)));
+    }
+
+    #[test]
+    fn test_voicing_rotated_no_voicings() {
+        let config = VoicingConfig {
+            max_span: 0,
+            ..Default::default()
+        };
+        let chord = Chord::from_str("C").unwrap();
+        assert_eq!(chord.voicing_rotated(config, 3).count(), 0);
+    }
+
+    #[rstest(
+        chord,
+        relative,
+        case("C", "Am"),
+        case("A", "F#m"),
+        case("Eb", "Cm"),
+        case("F#", "D#m"),
+        case("Bb", "Gm"),
+        case("Cmaj7", "Am7"),
+        case("Dmaj9", "Bm9")
+    )]
+    fn test_relative_minor(chord: Chord, relative: &str) {
+        let relative_minor = chord.relative_minor().unwrap();
+        assert_eq!(relative_minor.name(), relative);
+        assert_eq!(relative_minor.relative_major().unwrap(), chord);
+    }
+
+    #[rstest(
+        chord,
+        relative,
+        case("Am", "C"),
+        case("C#m", "E"),
+        case("Cm", "Eb"),
+        case("Gm", "Bb"),
+        case("Em7", "Gmaj7")
+    )]
+    fn test_relative_major(chord: Chord, relative: &str) {
+        let relative_major = chord.relative_major().unwrap();
+        assert_eq!(relative_major.name(), relative);
+        assert_eq!(relative_major.relative_minor().unwrap(), chord);
+    }
+
+    #[rstest(chord, case("C7"), case("Cdim"), case("Csus4"), case("C6"))]
+    fn test_relative_none(chord: Chord) {
+        assert_eq!(chord.relative_minor(), None);
+        assert_eq!(chord.relative_major(), None);
+    }
+
+    #[rstest(
+        tuning,
+        max_pressed_strings,
+        included,
+        excluded,
+        case(Tuning::C, 0, vec!["C6", "Am7"], vec!["C", "Am", "F"]),
+        case(Tuning::C, 1, vec!["C6", "Am7", "C", "Am", "A7"], vec!["F", "G7"]),
+        case(Tuning::G, 0, vec!["G6", "Em7"], vec!["G", "C"]),
+    )]
+    fn test_open_chords(
+        tuning: Tuning,
+        max_pressed_strings: usize,
+        included: Vec<&str>,
+        excluded: Vec<&str>,
+    ) {
+        let config = VoicingConfig {
+            tuning,
+            max_fret: 3,
+            ..Default::default()
+        };
+        let chords = Chord::open_chords(config, max_pressed_strings);
+
+        for name in included {
+            assert!(chords.contains(&Chord::from_str(name).unwrap()));
+        }
+
+        for name in excluded {
+            assert!(!chords.contains(&Chord::from_str(name).unwrap()));
+        }
+    }
+
+    #[rstest(
+        open_strings,
+        included,
+        excluded,
+        case(vec![], vec![("C", [0, 0, 0, 3]), ("Am", [2, 0, 0, 0]), ("F", [2, 0, 1, 0]), ("G", [0, 2, 3, 2])], vec![]),
+        case(vec![0, 3], vec![("C6", [0, 0, 0, 0]), ("A7", [0, 1, 0, 0])], vec!["C", "Am", "G"]),
+    )]
+    fn test_playable_chords(
+        open_strings: Vec<usize>,
+        included: Vec<(&str, [FretID; STRING_COUNT])>,
+        excluded: Vec<&str>,
+    ) {
+        let config = VoicingConfig {
+            max_fret: 3,
+            ..Default::default()
+        };
+        let chords = Chord::playable_chords(config, &open_strings);
+
+        for (name, frets) in included {
+            let chord = Chord::from_str(name).unwrap();
+            let voicing = Voicing::new(frets, Tuning::C);
+            assert!(chords.contains(&(chord, voicing)));
+        }
+
+        for name in excluded {
+            assert!(chords.iter().all(|(chord, _)| chord.name() != name));
+        }
+
+        // The easiest voicings come first.
+        assert!(chords
+            .iter()
+            .tuple_windows()
+            .all(|((_, v1), (_, v2))| v1.difficulty() <= v2.difficulty()));
+    }
+
+    #[rstest(
+        notes,
+        chords,
+        inversion,
+        case(vec![("C", 4), ("E", 4), ("G", 4)], vec!["C"], Some(0)),
+        case(vec![("E", 4), ("G", 4), ("C", 5)], vec!["C"], Some(1)),
+        case(vec![("G", 3), ("C", 4), ("E", 4)], vec!["C"], Some(2)),
+        case(vec![("A", 3), ("C", 4), ("E", 4), ("G", 4)], vec!["Am7", "C6"], Some(0)),
+        case(vec![("G", 4), ("C", 4), ("E", 4), ("A", 4)], vec!["C6", "Am7"], Some(0)),
+        case(vec![("Eb", 4), ("G", 4), ("Bb", 4)], vec!["Eb"], Some(0)),
+        // Incomplete chords are only reported if there is no exact match.
+        case(
+            vec![("C", 4), ("B", 4), ("D", 5), ("A", 5)],
+            vec!["Cmaj13", "D13", "Am11", "B7alt"],
+            Some(0)
+        ),
+    )]
+    fn test_from_notes(notes: Vec<(&str, Octave)>, chords: Vec<&str>, inversion: Option<usize>) {
+        let notes: Vec<Note> = notes
+            .iter()
+            .map(|(s, o)| Note::from_str(s).unwrap().with_octave(*o))
+            .collect();
+        let bass = *notes.iter().min_by_key(|n| n.midi_number()).unwrap();
+
+        let found = Chord::from_notes(&notes);
+        let names: Vec<String> = found
+            .iter()
+            .map(|c| format!("{}{}", c.root, c.chord_type.to_symbol()))
+            .collect();
+
+        assert_eq!(names, chords);
+        assert_eq!(found[0].inversion(bass), inversion);
+    }
+
+    #[rstest(
+        chord,
+        note,
+        interval,
+        case("C", "C", Some("P1")),
+        case("C", "E", Some("M3")),
+        case("C7", "A#", Some("m7")),
+        case("C7#9", "Eb", Some("A9")),
+        case("C", "D", None),
+        case("C(no5)", "G", None)
+    )]
+    fn test_interval_of(chord: Chord, note: Note, interval: Option<&str>) {
+        let interval = interval.map(|s| Interval::from_str(s).unwrap());
+        assert_eq!(chord.interval_of(note), interval);
+    }
+
+    #[rstest(
+        notes,
+        case(vec![]),
+        case(vec!["C", "C#", "D"]),
+        case(vec!["C", "F#", "B", "F"]),
+    )]
+    fn test_from_notes_fail(notes: Vec<&str>) {
+        let notes: Vec<Note> = notes.iter().map(|s| Note::from_str(s).unwrap()).collect();
+        assert!(Chord::from_notes(&notes).is_empty());
+    }
+
+    #[rstest(
+        chord,
+        key,
+        numeral,
+        case("C", "C", "I"),
+        case("Dm", "C", "ii"),
+        case("Em7", "C", "iii"),
+        case("F", "C", "IV"),
+        case("G7", "C", "V"),
+        case("Am", "C", "vi"),
+        case("Bdim", "C", "vii°"),
+        case("Bb", "C", "bVII"),
+        case("Ab", "C", "bVI"),
+        case("F#dim7", "C", "#iv°"),
+        case("D", "G", "V"),
+        case("Csus4", "C", "I")
+    )]
+    fn test_roman_numeral(chord: Chord, key: Note, numeral: &str) {
+        assert_eq!(chord.roman_numeral(key), numeral);
+    }
+
+    #[rstest(
+        chord,
+        key,
+        numeral,
+        case("C", "C", "I"),
+        case("Am", "C", "vi"),
+        case("Bdim", "C", "vii°"),
+        case("Cmaj7", "C", "Imaj7"),
+        case("Dm7", "C", "ii7"),
+        case("G7", "C", "V7"),
+        case("G9", "C", "V7"),
+        case("Bm7b5", "C", "viiø7"),
+        case("Bdim7", "C", "vii°7"),
+        case("D", "C", "V/V"),
+        case("D7", "C", "V7/V"),
+        case("A7", "C", "V7/ii"),
+        case("E7", "C", "V7/vi"),
+        case("C7", "C", "V7/IV"),
+        case("B7", "C", "V7/iii"),
+        case("Bb", "C", "bVII"),
+        case("Fm", "C", "iv"),
+        case("Ab", "C", "bVI"),
+        case("Eb", "Bb", "IV"),
+        case("C7", "Bb", "V7/V")
+    )]
+    fn test_analyze(chord: Chord, key: Note, numeral: &str) {
+        assert_eq!(chord.analyze(key), numeral);
+    }
+
+    #[rstest(
+        chord,
+        bass,
+        name,
+        case("C", "E", "C/E - C major"),
+        case("C", "G", "C/G - C major"),
+        case("Am7", "C", "Am7/C - A minor 7th"),
+        case("C", "C", "C - C major")
+    )]
+    fn test_with_bass(chord: Chord, bass: Note, name: &str) {
+        assert_eq!(chord.with_bass(bass).to_string(), name);
+    }
+
+    #[test]
+    fn test_transpose_with_bass() {
+        let chord = Chord::from_str("C")
+            .unwrap()
+            .with_bass(Note::from_str("E").unwrap());
+        assert_eq!(chord.transpose(2).to_string(), "D/F# - D major");
+        assert_eq!(chord.transpose(-1).to_string(), "B/D# - B major");
+        assert_eq!(
+            chord.transpose_by(Interval::MinorThird).to_string(),
+            "Eb/G - Eb major"
+        );
+    }
+
+    #[rstest(
+        chord,
+        root,
+        chord_type,
+        bass,
+        case("C/G", "C", ChordType::Major, Some("G")),
+        case("F#m/A", "F#", ChordType::Minor, Some("A")),
+        case("D/F#", "D", ChordType::Major, Some("F#")),
+        case("Db/F", "Db", ChordType::Major, Some("F")),
+        case("C/B", "C", ChordType::Major, Some("B")),
+        case("C/C", "C", ChordType::Major, None),
+        case("C6/9", "C", ChordType::SixthNinth, None),
+        case("C6/9/E", "C", ChordType::SixthNinth, Some("E"))
+    )]
+    fn test_from_str_slash(chord: Chord, root: Note, chord_type: ChordType, bass: Option<&str>) {
+        assert_eq!(chord.root.to_string(), root.to_string());
+        assert_eq!(chord.chord_type, chord_type);
+        assert_eq!(
+            chord.bass.map(|n| n.to_string()),
+            bass.map(|s| s.to_string())
+        );
+    }
+
+    #[rstest(
+        chord,
+        played_notes,
+        case("C/G", vec!["C", "E", "G"]),
+        case("C/B", vec!["B", "C", "E", "G"]),
+    )]
+    fn test_played_notes_slash(chord: Chord, played_notes: Vec<&str>) {
+        let pn1: Vec<_> = chord.played_notes(STRING_COUNT).collect();
+        let pn2: Vec<_> = played_notes
+            .iter()
+            .map(|&s| Note::from_str(s).unwrap())
+            .collect();
+
+        assert_eq!(pn1, pn2);
+    }
+
+    #[rstest(
+        chord,
+        case("C/G"),
+        case("C/E"),
+        case("C/B"),
+        case("D/F#"),
+        case("Am/G")
+    )]
+    fn test_voicings_slash(chord: Chord) {
+        let bass = chord.bass.unwrap();
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+
+        assert_eq!(voicing.bass(), bass);
+        assert!(voicing.notes().any(|n| n == bass));
+    }
+
+    #[rstest(chord, case("C"), case("Cmaj7"), case("G7"), case("Bbm"))]
+    fn test_voicings_only_playable(chord: Chord) {
+        let config = VoicingConfig {
+            max_span: 7,
+            ..Default::default()
+        };
+        let playable_config = VoicingConfig {
+            only_playable: true,
+            ..config
+        };
+
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+        let playable: Vec<Voicing> = chord.voicings(playable_config).collect();
+
+        // Wide shapes pressing four distinct frets are rejected.
+        assert!(voicings
+            .iter()
+            .any(|v| v.frets().unique().count() == 4 && !playable.contains(v)));
+        assert!(playable.len() < voicings.len());
+        assert!(playable.iter().all(|v| voicings.contains(v)));
+        assert!(playable.iter().all(Fingering::is_feasible));
+
+        // The option is disabled by default.
+        assert!(!VoicingConfig::default().only_playable);
+    }
+
+    #[test]
+    fn test_voicings_sort_by_difficulty() {
+        let chord = Chord::from_str("C").unwrap();
+        let config = VoicingConfig {
+            sort_by_difficulty: true,
+            ..Default::default()
+        };
+
+        let by_position: Vec<Voicing> = chord.voicings(VoicingConfig::default()).collect();
+        let by_difficulty: Vec<Voicing> = chord.voicings(config).collect();
+
+        // The same voicings are found, just in a different order.
+        assert_eq!(
+            by_position.iter().sorted().collect_vec(),
+            by_difficulty.iter().sorted().collect_vec()
+        );
+        assert!(by_difficulty
+            .iter()
+            .tuple_windows()
+            .all(|(v1, v2)| v1.difficulty() <= v2.difficulty()));
+        assert_ne!(by_position, by_difficulty);
+    }
+
+    #[rstest(
+        chord,
+        tuning,
+        frets,
+        notes,
+        case("C", Tuning::CLowG, [5, 4, 3, 3], "C E G C"),
+        case("C", Tuning::C, [0, 12, 12, 10], "G C E G"),
+        case("G7", Tuning::CLowG, [0, 2, 1, 2], "G D F B"),
+        case("Am", Tuning::Baritone, [2, 2, 1, 0], "E A C E"),
+    )]
+    fn test_arpeggio_voicings(
+        chord: Chord,
+        tuning: Tuning,
+        frets: [FretID; STRING_COUNT],
+        notes: &str,
+    ) {
+        let config = VoicingConfig {
+            tuning,
+            ..Default::default()
+        };
+        let voicing = chord
+            .arpeggio_voicings(config)
+            .find(|v| v.frets().collect_vec() == frets)
+            .unwrap();
+
+        assert_eq!(voicing.notes().map(|n| n.to_string()).join(" "), notes);
+    }
+
+    #[rstest(
+        chord,
+        tuning,
+        frets,
+        // Among the voicings up to the 12th fret, those with a repeated or
+        // descending note (e.g. 0 4 0 3 or 5 7 0 7) are left out.
+        case(
+            "C",
+            Tuning::CLowG,
+            vec![
+                [0, 0, 0, 3],
+                [0, 4, 3, 3],
+                [5, 4, 3, 3],
+                [5, 7, 8, 7],
+                [0, 0, 0, 7],
+                [0, 0, 8, 7],
+                [0, 7, 8, 7],
+                [9, 7, 8, 7],
+                [9, 7, 8, 10],
+                [9, 12, 12, 10],
+                [0, 0, 0, 10],
+                [0, 0, 12, 10],
+                [0, 12, 12, 10],
+                [12, 12, 12, 10],
+            ]
+        ),
+        // With the high G string, only one voicing is left.
+        case("C", Tuning::C, vec![[0, 12, 12, 10]])
+    )]
+    fn test_arpeggio_voicings_ascending(
+        chord: Chord,
+        tuning: Tuning,
+        frets: Vec<[FretID; STRING_COUNT]>,
+    ) {
+        let config = VoicingConfig {
+            tuning,
+            max_fret: 12,
+            ..Default::default()
+        };
+        let voicings = chord
+            .arpeggio_voicings(config)
+            .map(|voicing| voicing.frets().collect_vec())
+            .collect_vec();
+
+        assert_eq!(voicings, frets);
+    }
+
+    #[rstest(chord, case("C"), case("G7"), case("Am"), case("F#m7b5"))]
+    fn test_voicings_six_strings(chord: Chord) {
+        let config = VoicingConfig {
+            tuning: Tuning::Guitalele,
+            ..Default::default()
+        };
+        let mut voicings = chord.voicings(config).peekable();
+
+        assert!(voicings.peek().is_some());
+
+        for voicing in voicings {
+            assert_eq!(voicing.strings().len(), 6);
+            assert_eq!(voicing.fingers_on_strings().len(), 6);
+            assert!(voicing.spells_out(&chord));
+        }
+    }
+
+    #[rstest(
+        chord,
+        string_count,
+        played_notes,
+        case("C9", 4, vec!["C", "E", "A#", "D"]),
+        case("C9", 6, vec!["C", "E", "A#", "D", "G"]),
+        case("C13", 6, vec!["C", "E", "Bb", "A", "G", "D"]),
+    )]
+    fn test_played_notes_string_count(chord: Chord, string_count: usize, played_notes: Vec<&str>) {
+        let pn1 = chord.played_notes(string_count).collect_vec();
+        let pn2 = played_notes
+            .iter()
+            .map(|s| Note::from_str(s).unwrap())
+            .collect_vec();
+        assert_eq!(pn1, pn2);
+    }
+
+    #[rstest(
+        chord,
+        sort_by_difficulty,
+        case("Cmaj13", false),
+        case("Cmaj13", true),
+        case("G7", false),
+        case("C/G", false),
+        case("Am7/G", true)
+    )]
+    fn test_voicings_capped(chord: Chord, sort_by_difficulty: bool) {
+        let configs = [
+            VoicingConfig {
+                sort_by_difficulty,
+                ..Default::default()
+            },
+            VoicingConfig {
+                min_fret: 3,
+                max_fret: 9,
+                max_span: 3,
+                sort_by_difficulty,
+                ..Default::default()
+            },
+        ];
+
+        for config in configs {
+            let voicings = chord.voicings(config).collect_vec();
+
+            for max_count in [0, 1, 3, 10, 1000] {
+                let capped = chord.voicings_capped(config, max_count);
+                assert_eq!(capped.len(), max_count.min(voicings.len()));
+                assert_eq!(capped[..], voicings[..capped.len()]);
+            }
+        }
+    }
+
+    #[rstest(
+        chord,
+        spelling,
+        notes,
+        case("D", Spelling::Sharps, "D F# A"),
+        case("D", Spelling::Flats, "D Gb A"),
+        case("D", Spelling::FromKey, "D F# A"),
+        case("Eb7", Spelling::Sharps, "D# G A# C#"),
+        case("Eb7", Spelling::FromKey, "Eb G Bb Db"),
+        case("C#m/G#", Spelling::Flats, "Db E Ab")
+    )]
+    fn test_respell(chord: Chord, spelling: Spelling, notes: &str) {
+        let respelled = chord.respell(spelling);
+        assert_eq!(respelled.notes.iter().join(" "), notes);
+        assert_eq!(respelled.root.to_string(), notes.split(' ').next().unwrap());
+        assert_eq!(respelled, chord);
+    }
+
+    #[rstest(
+        chord,
+        spelling,
+        case("Bb", Spelling::Flats),
+        case("Ebm7", Spelling::Flats),
+        case("C#", Spelling::Sharps),
+        case("F#/A#", Spelling::Sharps),
+        case("C", Spelling::FromKey),
+        case("F/C", Spelling::FromKey)
+    )]
+    fn test_spelling(chord: Chord, spelling: Spelling) {
+        assert_eq!(chord.spelling(), spelling);
+    }
+
+    #[rstest(
+        chord,
+        frets,
+        case("C", [0, 0, 0, 3]),
+        case("Am", [2, 0, 0, 0]),
+        case("F", [2, 0, 1, 0]),
+        case("G7", [0, 2, 1, 2]),
+        case("D", [2, 2, 2, 0]),
+        case("C/E", [0, 4, 0, 3])
+    )]
+    fn test_best_voicing(chord: Chord, frets: [FretID; STRING_COUNT]) {
+        let config = VoicingConfig::default();
+        let best = chord.best_voicing(config).unwrap();
+
+        assert_eq!(best, Voicing::new(frets, config.tuning));
+
+        // No other voicing (with the bass note in the bass for slash chords) is easier.
+        assert!(chord
+            .voicings(config)
+            .filter(|v| chord.bass.is_none() || v.bass() == best.bass())
+            .all(|v| v.difficulty() >= best.difficulty()));
+    }
+
+    #[test]
+    fn test_best_voicing_none() {
+        // Cm cannot be played using open strings only.
+        let chord = Chord::from_str("Cm").unwrap();
+        let config = VoicingConfig {
+            max_fret: 0,
+            ..Default::default()
+        };
+        assert_eq!(chord.best_voicing(config), None);
+    }
+
+    #[rstest(
+        chord,
+        lowest_note,
+        highest_note,
+        voicing_count,
+        case("C", Some("G4"), None, 7),
+        case("C", None, Some("E5"), 10),
+        case("C", Some("C4"), Some("C5"), 4),
+        case("C", Some("C4"), Some("A4"), 0),
+        case("G7", None, None, 6)
+    )]
+    fn test_voicings_pitch_range(
+        chord: Chord,
+        lowest_note: Option<&str>,
+        highest_note: Option<&str>,
+        voicing_count: usize,
+    ) {
+        let lowest_note = lowest_note.map(|s| Note::parse_with_octave(s).unwrap());
+        let highest_note = highest_note.map(|s| Note::parse_with_octave(s).unwrap());
+        let config = VoicingConfig {
+            lowest_note,
+            highest_note,
+            ..Default::default()
+        };
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+
+        assert_eq!(voicings.len(), voicing_count);
+        for voicing in voicings {
+            for note in voicing.notes() {
+                if let Some(low) = lowest_note {
+                    assert!(note.midi_number() >= low.midi_number());
+                }
+                if let Some(high) = highest_note {
+                    assert!(note.midi_number() <= high.midi_number());
+                }
+            }
+        }
+    }
+
+    #[rstest(
+        chord,
+        drone,
+        drone_chord_tone,
+        case("C", 1, false),
+        case("C", 1, true),
+        case("C", 2, true),
+        case("C", 4, false),
+        case("G7", 4, false),
+        case("F", 3, false)
+    )]
+    fn test_voicings_drone(chord: Chord, drone: usize, drone_chord_tone: bool) {
+        let config = VoicingConfig {
+            drone: Some(drone),
+            drone_chord_tone,
+            ..Default::default()
+        };
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+
+        assert!(!voicings.is_empty());
+        for voicing in voicings {
+            assert_eq!(voicing.fret_pattern().nth(drone - 1), Some(Some(0)));
+        }
+    }
+
+    #[test]
+    fn test_voicings_drone_chord_tone() {
+        let chord = Chord::from_str("C").unwrap();
+        let config = VoicingConfig {
+            drone: Some(1),
+            ..Default::default()
+        };
+        let all: Vec<Voicing> = chord.voicings(VoicingConfig::default()).collect();
+
+        // G is a chord tone, so the drone does not add any voicings.
+        for voicing in chord.voicings(config) {
+            assert!(all.contains(&voicing));
+        }
+
+        // A is not a chord tone of C.
+        let config = VoicingConfig {
+            drone: Some(4),
+            drone_chord_tone: true,
+            ..Default::default()
+        };
+        assert_eq!(chord.voicings(config).count(), 0);
+
+        let config = VoicingConfig {
+            drone: Some(4),
+            ..Default::default()
+        };
+        let voicing = chord.voicings(config).next().unwrap();
+        assert_eq!(voicing.to_fret_pattern().to_string(), "0000");
+        assert!(!voicing.spells_out(&chord));
+    }
+
+    #[rstest(drone, case(0), case(5))]
+    fn test_voicings_drone_invalid(drone: usize) {
+        let chord = Chord::from_str("C").unwrap();
+        let config = VoicingConfig {
+            drone: Some(drone),
+            ..Default::default()
+        };
+        assert_eq!(
+            chord.voicings(config).count(),
+            chord.voicings(VoicingConfig::default()).count()
+        );
+    }
+
+    #[rstest(
+        chord,
+        tuning,
+        case("C", Tuning::C),
+        case("C", Tuning::CLowG),
+        case("G7", Tuning::C),
+        case("Am", Tuning::Baritone),
+        case("C/E", Tuning::C),
+        case("D", Tuning::Guitalele)
+    )]
+    fn test_voicings_root_in_bass(chord: Chord, tuning: Tuning) {
+        let config = VoicingConfig {
+            tuning,
+            root_in_bass: true,
+            ..Default::default()
+        };
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+        let bass = chord.bass.unwrap_or(chord.root);
+
+        assert!(!voicings.is_empty());
+        for voicing in &voicings {
+            assert_eq!(voicing.bass(), bass);
+            assert!(voicing
+                .notes()
+                .all(|note| note.midi_number() >= voicing.bass().midi_number()));
+        }
+
+        // Without the flag, there are more voicings, but they include all
+        // the root-position ones.
+        let config = VoicingConfig {
+            root_in_bass: false,
+            ..config
+        };
+        let all: Vec<Voicing> = chord.voicings(config).collect();
+        assert!(all.len() > voicings.len());
+        assert!(voicings.iter().all(|voicing| all.contains(voicing)));
+    }
+
+    #[rstest(
+        chord,
+        string_set,
+        first,
+        case("C", [1, 2, 3], "0 0 0 x"),
+        case("C", [2, 3, 4], "x 4 3 3"),
+        case("Am", [2, 3, 4], "x 0 0 0"),
+        case("G7", [1, 2, 4], "0 5 x 2"),
+        case("C", [1, 3, 4], "0 x 0 3")
+    )]
+    fn test_triad_voicings(chord: Chord, string_set: [usize; 3], first: FretPattern) {
+        let config = VoicingConfig::default();
+        let voicings = chord.triad_voicings(config, string_set).collect_vec();
+
+        assert_eq!(voicings[0].to_fret_pattern(), first);
+
+        for voicing in voicings {
+            assert_eq!(voicing.uke_strings().count(), 3);
+            for (i, fret) in voicing.fret_pattern().enumerate() {
+                assert_eq!(fret.is_some(), string_set.contains(&(i + 1)));
+            }
+            assert!(voicing
+                .notes()
+                .all(|note| chord.interval_of(note).is_some()));
+            assert_eq!(
+                voicing
+                    .notes()
+                    .map(|n| n.pitch_class)
+                    .sorted()
+                    .dedup()
+                    .count(),
+                3
+            );
+        }
+    }
+
+    #[test]
+    fn test_triad_voicings_top_strings() {
+        let chord = Chord::from_str("C").unwrap();
+        let voicings = chord
+            .triad_voicings(VoicingConfig::default(), [1, 2, 3])
+            .collect_vec();
+
+        assert!(!voicings.is_empty());
+        for voicing in voicings {
+            assert_eq!(voicing.fret_pattern().last(), Some(None));
+            assert!(voicing.spells_out(&chord));
+        }
+    }
+
+    #[rstest(
+        string_set,
+        case([1, 1, 2]),
+        case([0, 1, 2]),
+        case([2, 3, 5])
+    )]
+    fn test_triad_voicings_invalid(string_set: [usize; 3]) {
+        let chord = Chord::from_str("C").unwrap();
+        assert_eq!(
+            chord
+                .triad_voicings(VoicingConfig::default(), string_set)
+                .count(),
+            0
+        );
+    }
+
+    #[rstest(
+        chord,
+        string_set,
+        config,
+        first,
+        case("C", [2, 3, 4], VoicingConfig { root_in_bass: true, ..Default::default() }, "x 0 0 10"),
+        case("C", [1, 2, 3], VoicingConfig { max_open_strings: Some(0), ..Default::default() }, "5 4 3 x"),
+        // The drone string is played open besides the triad.
+        case("C", [2, 3, 4], VoicingConfig { drone: Some(1), ..Default::default() }, "0 4 3 3"),
+        // The drone string is played open as part of the triad.
+        case("F", [2, 3, 4], VoicingConfig { drone: Some(2), min_fret: 3, ..Default::default() }, "x 0 5 8")
+    )]
+    fn test_triad_voicings_config(
+        chord: Chord,
+        string_set: [usize; 3],
+        config: VoicingConfig,
+        first: FretPattern,
+    ) {
+        let voicings = chord.triad_voicings(config, string_set).collect_vec();
+
+        assert_eq!(voicings[0].to_fret_pattern(), first);
+    }
+
+    #[rstest(
+        chord,
+        max_open_strings,
+        case("C", 0),
+        case("C", 1),
+        case("Am", 2),
+        case("G7", 0),
+        case("F#m", 0)
+    )]
+    fn test_voicings_max_open_strings(chord: Chord, max_open_strings: usize) {
+        let config = VoicingConfig {
+            max_open_strings: Some(max_open_strings),
+            ..Default::default()
+        };
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+
+        assert!(!voicings.is_empty());
+        assert!(voicings
+            .iter()
+            .all(|voicing| voicing.count_open_strings() <= max_open_strings));
+
+        let all: Vec<Voicing> = chord.voicings(VoicingConfig::default()).collect();
+        assert!(voicings.iter().all(|voicing| all.contains(voicing)));
+    }
+
+    #[test]
+    fn test_voicings_closed() {
+        let chord = Chord::from_str("C").unwrap();
+        let config = VoicingConfig {
+            max_open_strings: Some(0),
+            ..Default::default()
+        };
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+
+        // The open C shape is excluded, barre shapes are kept.
+        assert!(!voicings.contains(&Voicing::new([0, 0, 0, 3], Tuning::C)));
+        assert!(voicings.contains(&Voicing::new([5, 4, 3, 3], Tuning::C)));
+        assert!(voicings.iter().any(|voicing| voicing.has_barre()));
+    }
+
+    #[rstest(
+        chord,
+        key,
+        scale_type,
+        is_diatonic,
+        case("C", "C", ScaleType::Major, true),
+        case("Dm7", "C", ScaleType::Major, true),
+        case("Bdim", "C", ScaleType::Major, true),
+        case("E7", "C", ScaleType::Major, false),
+        case("Bb", "C", ScaleType::Major, false),
+        case("C/B", "C", ScaleType::Major, true),
+        case("C/Bb", "C", ScaleType::Major, false),
+        case("Bb", "F", ScaleType::Major, true),
+        case("G", "A", ScaleType::NaturalMinor, true),
+        case("E7", "A", ScaleType::NaturalMinor, true),
+        case("G#dim", "A", ScaleType::NaturalMinor, true),
+        case("D", "A", ScaleType::NaturalMinor, false),
+        case("E7", "A", ScaleType::Major, true),
+        case("Cm", "A", ScaleType::NaturalMinor, false)
+    )]
+    fn test_is_diatonic(chord: Chord, key: Note, scale_type: ScaleType, is_diatonic: bool) {
+        let key = Scale::new(key, scale_type);
+        assert_eq!(chord.is_diatonic(key), is_diatonic);
+    }
 }
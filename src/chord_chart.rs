@@ -1,20 +1,279 @@
 use std::{cmp::max, fmt, fmt::Write};
 
-use crate::{FretID, Semitones, UkeString, Voicing, MIN_CHART_WIDTH};
+use clap::ValueEnum;
+use itertools::Itertools;
+
+use crate::{Chord, FretID, Interval, Note, Semitones, UkeString, Voicing, MIN_CHART_WIDTH};
+
+/// Distance between two strings in an SVG chord chart.
+const SVG_STRING_SPACING: usize = 20;
+
+/// Distance between two frets in an SVG chord chart.
+const SVG_FRET_SPACING: usize = 24;
+
+/// Space around the fretboard in an SVG chord chart.
+const SVG_MARGIN: usize = 30;
+
+/// Characters used to draw the strings and frets of text chord charts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ChartStyle {
+    /// Plain ASCII characters, e.g. `-`, `|` and `=`.
+    #[default]
+    Ascii,
+    /// Unicode box-drawing characters, e.g. `─`, `┼` and `━`.
+    Unicode,
+}
+
+impl ChartStyle {
+    /// Return the symbol for the nut if the chord is played on the lower
+    /// end of the fretboard or the symbol for ongoing strings otherwise.
+    fn nut(&self, base_fret: FretID) -> &'static str {
+        match (self, base_fret) {
+            (Self::Ascii, 1) => "||",
+            (Self::Ascii, _) => "-|",
+            (Self::Unicode, 1) => "║║",
+            (Self::Unicode, _) => "─┼",
+        }
+    }
+
+    /// Draw `diagram`, which is given in ASCII characters, in this style.
+    fn draw(&self, diagram: &str) -> String {
+        match self {
+            Self::Ascii => diagram.to_string(),
+            Self::Unicode => diagram
+                .chars()
+                .map(|c| match c {
+                    '-' => '─',
+                    '|' => '┼',
+                    '=' => '━',
+                    'o' => '○',
+                    'x' => '×',
+                    c => c,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Lay out blocks of lines (e.g. the lines of several chord charts)
+/// next to each other, separated by `gap` spaces.
+///
+/// Each block is padded to its widest line and to the height of the
+/// highest block, so that all the columns are aligned.
+pub fn side_by_side(blocks: &[Vec<String>], gap: usize) -> Vec<String> {
+    let height = blocks.iter().map(|block| block.len()).max().unwrap_or(0);
+    let widths = blocks
+        .iter()
+        .map(|block| block.iter().map(|line| line.chars().count()).max())
+        .map(|width| width.unwrap_or(0))
+        .collect_vec();
+
+    (0..height)
+        .map(|row| {
+            blocks
+                .iter()
+                .zip(&widths)
+                .map(|(block, &width)| {
+                    let line = block.get(row).map_or("", |line| line.as_str());
+                    format!("{line:width$}")
+                })
+                .join(&" ".repeat(gap))
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
 
 pub struct ChordChart {
+    chord: Chord,
     voicing: Voicing,
     /// Number of frets to use to display the chord voicing
     width: Semitones,
+    /// Whether the strings are shown in mirrored order (for left-handed players)
+    left_handed: bool,
+    /// Whether each note is labelled with its function in the chord
+    /// (e.g. `R`, `3`, `5` or `b7`)
+    show_intervals: bool,
+    /// Whether the sounding note of each string is shown next to the string
+    show_notes: bool,
+    /// Whether the open-string notes of the tuning are shown above the chart
+    show_tuning: bool,
+    /// Characters used to draw the strings and frets
+    style: ChartStyle,
 }
 
 impl ChordChart {
-    pub fn new(voicing: Voicing, width: Semitones) -> Self {
+    /// Create a chart of `voicing`, which is played for `chord`.
+    /// The notes are spelled according to the chord's spelling,
+    /// e.g. a Bb chord is shown with Bb rather than A#.
+    pub fn new(chord: &Chord, voicing: Voicing, width: Semitones) -> Self {
         let width = max(width, MIN_CHART_WIDTH);
 
         assert!(voicing.get_span() <= width);
 
-        Self { voicing, width }
+        let voicing = voicing.respell(chord.spelling());
+
+        Self {
+            chord: chord.clone(),
+            voicing,
+            width,
+            left_handed: false,
+            show_intervals: false,
+            show_notes: true,
+            show_tuning: false,
+            style: ChartStyle::default(),
+        }
+    }
+
+    /// Return the same chart with the order of the strings mirrored
+    /// if `left_handed` is `true`.
+    pub fn with_left_handed(self, left_handed: bool) -> Self {
+        Self {
+            left_handed,
+            ..self
+        }
+    }
+
+    /// Return the same chart without the names of the notes played on the strings
+    /// if `show_notes` is `false`.
+    pub fn with_notes(self, show_notes: bool) -> Self {
+        Self { show_notes, ..self }
+    }
+
+    /// Return the same chart with a header line listing the open-string notes
+    /// of the tuning (e.g. `Tuning: G C E A`) if `show_tuning` is `true`.
+    pub fn with_tuning(self, show_tuning: bool) -> Self {
+        Self {
+            show_tuning,
+            ..self
+        }
+    }
+
+    /// Return the same chart drawn with the characters of `style`.
+    pub fn with_style(self, style: ChartStyle) -> Self {
+        Self { style, ..self }
+    }
+
+    /// Return the same chart showing only the smallest range of frets containing
+    /// the voicing (but at least `MIN_CHART_WIDTH` frets) if `window` is `true`.
+    ///
+    /// Voicings that do not fit in the first frets of the range are then shown
+    /// starting from their lowest pressed fret (see `get_base_fret`).
+    pub fn with_window(self, window: bool) -> Self {
+        let width = match window {
+            true => max(self.voicing.get_span(), MIN_CHART_WIDTH),
+            false => self.width,
+        };
+
+        Self { width, ..self }
+    }
+
+    /// Return the same chart with each note labelled with its interval
+    /// from the root of the chord if `show_intervals` is `true`.
+    /// A legend explaining the labels is printed below the chart.
+    pub fn with_intervals(self, show_intervals: bool) -> Self {
+        Self {
+            show_intervals,
+            ..self
+        }
+    }
+
+    /// Return the interval between the root of the chord and `note`,
+    /// preferring the spelling used by the chord (e.g. `#5` rather than `b6`).
+    fn interval_of(&self, note: Note) -> Interval {
+        self.chord
+            .interval_of(note)
+            .unwrap_or(note - self.chord.root)
+    }
+
+    /// Return the label of `note` showing its function in the chord,
+    /// e.g. `R` for the root or `b7` for a minor seventh.
+    fn interval_label(&self, note: Note) -> &'static str {
+        match self.interval_of(note) {
+            Interval::PerfectUnison => "R",
+            interval => interval.short(),
+        }
+    }
+
+    /// Return a legend for the interval labels of the notes in the chart,
+    /// e.g. `R: root, 3: major third, 5: perfect fifth`.
+    pub fn legend(&self) -> String {
+        self.voicing
+            .notes()
+            .map(|note| self.interval_of(note))
+            .sorted_by_key(|interval| (interval.to_number(), interval.to_semitones()))
+            .dedup()
+            .map(|interval| match interval {
+                Interval::PerfectUnison => "R: root".to_string(),
+                _ => format!("{}: {}", interval.short(), interval.name()),
+            })
+            .join(", ")
+    }
+
+    /// Return the lines of the chart (without line breaks), e.g. for
+    /// embedding the chart in a user interface or for laying out
+    /// several charts with `side_by_side`.
+    pub fn lines(&self) -> impl Iterator<Item = String> {
+        // Determine from which fret to show the fretboard.
+        let base_fret = self.get_base_fret();
+
+        // Get the width of the space that we need to print the name
+        // of the root notes (the names of the strings).
+        let root_width = self.get_root_width();
+
+        let fingers = self.fingers();
+        let barre = self.voicing.barre();
+
+        // Create a diagram for each ukulele string.
+        let mut string_lines: Vec<String> = self
+            .voicing
+            .roots()
+            .zip(self.voicing.strings())
+            .zip(fingers.iter())
+            .enumerate()
+            .map(|(i, ((root, us), f))| {
+                let barre_fret = match &barre {
+                    Some((fret, strings)) if strings.contains(&i) => Some(*fret),
+                    _ => None,
+                };
+                let line = self.format_line(root, us, base_fret, root_width, *f, barre_fret);
+                line.trim_end_matches('\n').to_string()
+            })
+            .collect();
+
+        // Usually, the highest string is shown on top. Left-handed charts
+        // are mirrored, so that the lowest string is shown on top.
+        if !self.left_handed {
+            string_lines.reverse();
+        }
+
+        let mut lines = vec![];
+
+        if self.show_tuning {
+            lines.push(format!("Tuning: {}", self.voicing.roots().join(" ")));
+            lines.push(String::new());
+        }
+
+        lines.extend(string_lines);
+
+        // If the fretboard section shown does not include the nut,
+        // indicate the number of the first fret shown.
+        if base_fret > 1 {
+            lines.push(format!("{base_fret:width$}", width = root_width + 6));
+        }
+
+        if self.show_intervals {
+            lines.push(String::new());
+            lines.push(self.legend());
+        }
+
+        lines.into_iter()
+    }
+
+    /// Return the finger shown on each string of the chart (in the string
+    /// order of the tuning), with `0` for open and muted strings.
+    pub fn fingers(&self) -> Vec<u8> {
+        self.voicing.fingers_on_strings()
     }
 
     /// Determine from which fret to show the fretboard.
@@ -42,85 +301,251 @@ impl ChordChart {
     }
 
     /// Format a line that represents a ukulele string in a chord diagram.
+    ///
+    /// `barre` is the fret of the barre if the string is covered by one.
+    /// The barre is drawn as `=` on the string's line.
+    /// If `uke_string` is `None`, the string with the given `root` is muted.
     pub fn format_line(
         &self,
-        uke_string: UkeString,
+        root: Note,
+        uke_string: Option<&UkeString>,
         base_fret: FretID,
         root_width: usize,
         finger: u8,
+        barre: Option<FretID>,
     ) -> String {
-        let (root, fret, note) = uke_string;
+        let fret = uke_string.map(|(_root, fret, _note)| *fret);
 
         let root_str = format!("{:width$}", root.to_string(), width = root_width);
 
         // Show a symbol for the nut if the chord is played on the lower
         // end of the fretboard. Indicate ongoing strings otherwise.
-        let nut = match base_fret {
-            1 => "||",
-            _ => "-|",
-        };
+        let nut = self.style.nut(base_fret);
 
-        // Mark open strings with a special symbol.
+        // Mark open and muted strings with a special symbol.
         let sym = match fret {
-            0 => "o",
-            _ => " ",
+            Some(0) => "o",
+            Some(_) => " ",
+            None => "x",
         };
 
         // Create a line representing the string with the fret to be pressed.
         let s: String = (base_fret..base_fret + self.width)
-            .map(|i| {
-                if fret == i {
-                    finger.to_string()
-                } else {
-                    "-".to_string()
-                }
+            .map(|i| match (fret == Some(i), barre == Some(i)) {
+                (true, true) => format!("={finger}="),
+                (false, true) => "===".to_string(),
+                (true, false) => format!("-{finger}-"),
+                (false, false) => "---".to_string(),
             })
             .fold(String::new(), |mut output, c| {
-                let _ = write!(output, "-{c}-|");
+                let _ = write!(output, "{c}|");
                 output
             });
+        let sym = self.style.draw(sym);
+        let s = self.style.draw(&format!("{s}-"));
 
-        format!("{root_str} {sym}{nut}{s}- {note}\n")
+        match uke_string {
+            Some(_) if !self.show_notes => format!("{root_str} {sym}{nut}{s}\n"),
+            Some((_root, _fret, note)) if self.show_intervals => {
+                // Spell the note according to its function in the chord,
+                // e.g. Bb rather than A# for the minor seventh of C7.
+                let label = self.interval_label(*note);
+                let note = (self.chord.root + self.interval_of(*note)).to_string();
+                format!("{root_str} {sym}{nut}{s} {note:<2} {label}\n")
+            }
+            Some((_root, _fret, note)) => format!("{root_str} {sym}{nut}{s} {note}\n"),
+            None => format!("{root_str} {sym}{nut}{s}\n"),
+        }
     }
-}
 
-impl fmt::Display for ChordChart {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Determine from which fret to show the fretboard.
+    /// Render the chord chart as a standalone SVG image.
+    ///
+    /// In contrast to the text representation, the strings are drawn vertically
+    /// (with the leftmost string of the tuning on the left) and the frets
+    /// horizontally. Each pressed fret is marked by a dot, open strings
+    /// are marked by a circle and muted strings by a cross above the nut.
+    /// In left-handed charts, the leftmost string of the tuning is on the right.
+    pub fn to_svg(&self) -> String {
         let base_fret = self.get_base_fret();
+        let string_count = self.voicing.strings().len();
+        let width = self.width as usize;
 
-        // Get the width of the space that we need to print the name
-        // of the root notes (the names of the strings).
-        let root_width = self.get_root_width();
+        // Coordinates of the fretboard.
+        let left = SVG_MARGIN;
+        let right = left + (string_count - 1) * SVG_STRING_SPACING;
+        let top = SVG_MARGIN;
+        let bottom = top + width * SVG_FRET_SPACING;
 
-        let fingers_on_strings = self.voicing.fingers_on_strings();
+        // Horizontal coordinate of the string with the given index.
+        let string_x = |i: usize| match self.left_handed {
+            true => right - i * SVG_STRING_SPACING,
+            false => left + i * SVG_STRING_SPACING,
+        };
 
-        // Create a diagram for each ukulele string.
-        let s: String = self
-            .voicing
-            .uke_strings()
-            .rev()
-            .zip(fingers_on_strings.iter().rev())
-            .map(|(us, f)| self.format_line(*us, base_fret, root_width, *f))
-            .collect();
+        let mut svg = String::new();
 
-        // If the fretboard section shown does not include the nut,
-        // indicate the number of the first fret shown.
-        if base_fret > 1 {
-            return writeln!(f, "{s}{base_fret:width$}", width = root_width + 6);
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}">"#,
+            right + SVG_MARGIN,
+            bottom + SVG_MARGIN
+        );
+
+        // Draw the nut if the chord is played on the lower end of the fretboard.
+        // Indicate the number of the first fret shown otherwise.
+        match base_fret {
+            1 => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <line x1="{left}" y1="{top}" x2="{right}" y2="{top}" stroke="black" stroke-width="4"/>"#
+                );
+            }
+            _ if self.left_handed => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="12" text-anchor="start">{base_fret}</text>"#,
+                    right + 8,
+                    top + SVG_FRET_SPACING / 2 + 4
+                );
+            }
+            _ => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="12" text-anchor="end">{base_fret}</text>"#,
+                    left - 8,
+                    top + SVG_FRET_SPACING / 2 + 4
+                );
+            }
+        }
+
+        // Draw the frets.
+        for i in 0..=width {
+            let y = top + i * SVG_FRET_SPACING;
+            let _ = writeln!(
+                svg,
+                r#"  <line x1="{left}" y1="{y}" x2="{right}" y2="{y}" stroke="black"/>"#
+            );
+        }
+
+        // Draw the strings.
+        for i in 0..string_count {
+            let x = left + i * SVG_STRING_SPACING;
+            let _ = writeln!(
+                svg,
+                r#"  <line x1="{x}" y1="{top}" x2="{x}" y2="{bottom}" stroke="black"/>"#
+            );
+        }
+
+        // Draw the barre as a bar across the strings it covers.
+        if let Some((fret, strings)) = self.voicing.barre() {
+            let x = string_x(*strings.start()).min(string_x(*strings.end()));
+            let y = top + (fret - base_fret) as usize * SVG_FRET_SPACING + SVG_FRET_SPACING / 2;
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="14" rx="7" fill="black"/>"#,
+                x - 7,
+                y - 7,
+                (strings.end() - strings.start()) * SVG_STRING_SPACING + 14
+            );
         }
 
-        write!(f, "{s}")
+        // Mark open and muted strings and pressed frets.
+        for (i, fret) in self.voicing.fret_pattern().enumerate() {
+            let x = string_x(i);
+
+            match fret {
+                None => {
+                    let y = top - 10;
+                    let _ = writeln!(
+                        svg,
+                        r#"  <path d="M{} {} L{} {} M{} {} L{} {}" stroke="black"/>"#,
+                        x - 4,
+                        y - 4,
+                        x + 4,
+                        y + 4,
+                        x - 4,
+                        y + 4,
+                        x + 4,
+                        y - 4
+                    );
+                }
+                Some(0) => {
+                    let _ = writeln!(
+                        svg,
+                        r#"  <circle cx="{x}" cy="{}" r="5" fill="none" stroke="black"/>"#,
+                        top - 10
+                    );
+                }
+                Some(fret) => {
+                    let y =
+                        top + (fret - base_fret) as usize * SVG_FRET_SPACING + SVG_FRET_SPACING / 2;
+                    let _ = writeln!(svg, r#"  <circle cx="{x}" cy="{y}" r="7" fill="black"/>"#);
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+impl fmt::Display for ChordChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.lines() {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
+    use itertools::Itertools;
     use rstest::rstest;
 
+    use std::str::FromStr;
+
     use super::*;
-    use crate::{Chord, Tuning, VoicingConfig};
+    use crate::{Chord, FretPattern, Tuning, VoicingConfig, STRING_COUNT};
+
+    #[rstest(
+        frets, fingers,
+        case([0, 0, 0, 3], [0, 0, 0, 3]),
+        case([3, 2, 1, 1], [3, 2, 1, 1]),
+        case([2, 2, 2, 2], [1, 1, 1, 1]),
+        case([0, 2, 3, 2], [0, 1, 3, 2]),
+    )]
+    fn test_fingers(frets: [FretID; STRING_COUNT], fingers: [u8; STRING_COUNT]) {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = Voicing::new(frets, Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, MIN_CHART_WIDTH);
+        assert_eq!(chart.fingers(), fingers);
+    }
+
+    #[test]
+    fn test_muted_string() {
+        let fret_pattern = FretPattern::from_str("x232").unwrap();
+        let voicing = Voicing::new(fret_pattern, Tuning::C);
+        let chord = Chord::from_str("G").unwrap();
+        let chart = ChordChart::new(&chord, voicing, 4);
+
+        let diagram = indoc!(
+            "
+            A  ||---|-2-|---|---|- B
+            E  ||---|---|-3-|---|- G
+            C  ||---|-1-|---|---|- D
+            G x||---|---|---|---|-
+        "
+        );
+        assert_eq!(chart.to_string(), diagram);
+
+        let svg = chart.to_svg();
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert_eq!(svg.matches(r#"r="7""#).count(), 3);
+    }
 
     #[rstest(chord, tuning, diagram,
         case(
@@ -138,9 +563,9 @@ mod tests {
             Tuning::C,
             indoc!("
                 A  ||---|---|---|-4-|- C#
-                E  ||-1-|---|---|---|- F
-                C  ||-1-|---|---|---|- C#
-                G  ||-1-|---|---|---|- G#
+                E  ||=1=|---|---|---|- F
+                C  ||=1=|---|---|---|- C#
+                G  ||=1=|---|---|---|- G#
             ")
         ),
         case(
@@ -148,9 +573,9 @@ mod tests {
             Tuning::C,
             indoc!("
                 A  ||---|---|---|-4-|- Db
-                E  ||-1-|---|---|---|- F
-                C  ||-1-|---|---|---|- Db
-                G  ||-1-|---|---|---|- Ab
+                E  ||=1=|---|---|---|- F
+                C  ||=1=|---|---|---|- Db
+                G  ||=1=|---|---|---|- Ab
             ")
         ),
         case(
@@ -183,6 +608,26 @@ mod tests {
                 A  o||---|---|---|---|- A
             "),
         ),
+        case(
+            "Bb",
+            Tuning::C,
+            indoc!("
+                A  ||=1=|---|---|---|- Bb
+                E  ||=1=|---|---|---|- F
+                C  ||---|-2-|---|---|- D
+                G  ||---|---|-3-|---|- Bb
+            "),
+        ),
+        case(
+            "Bbm",
+            Tuning::C,
+            indoc!("
+                A  ||=1=|---|---|---|- Bb
+                E  ||=1=|---|---|---|- F
+                C  ||=1=|---|---|---|- Db
+                G  ||---|---|-3-|---|- Bb
+            "),
+        ),
         case(
             "G",
             Tuning::G,
@@ -200,7 +645,423 @@ mod tests {
             ..Default::default()
         };
         let voicing = chord.voicings(config).next().unwrap();
-        let chord_chart = ChordChart::new(voicing, 4);
+        let chord_chart = ChordChart::new(&chord, voicing, 4);
         assert_eq!(chord_chart.to_string(), diagram);
     }
+
+    #[test]
+    fn test_to_svg() {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        let chord_chart = ChordChart::new(&chord, voicing, 4);
+
+        let svg = indoc!(
+            r#"
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 120 156">
+              <line x1="30" y1="30" x2="90" y2="30" stroke="black" stroke-width="4"/>
+              <line x1="30" y1="30" x2="90" y2="30" stroke="black"/>
+              <line x1="30" y1="54" x2="90" y2="54" stroke="black"/>
+              <line x1="30" y1="78" x2="90" y2="78" stroke="black"/>
+              <line x1="30" y1="102" x2="90" y2="102" stroke="black"/>
+              <line x1="30" y1="126" x2="90" y2="126" stroke="black"/>
+              <line x1="30" y1="30" x2="30" y2="126" stroke="black"/>
+              <line x1="50" y1="30" x2="50" y2="126" stroke="black"/>
+              <line x1="70" y1="30" x2="70" y2="126" stroke="black"/>
+              <line x1="90" y1="30" x2="90" y2="126" stroke="black"/>
+              <circle cx="30" cy="20" r="5" fill="none" stroke="black"/>
+              <circle cx="50" cy="20" r="5" fill="none" stroke="black"/>
+              <circle cx="70" cy="20" r="5" fill="none" stroke="black"/>
+              <circle cx="90" cy="90" r="7" fill="black"/>
+            </svg>
+        "#
+        );
+
+        assert_eq!(chord_chart.to_svg(), svg);
+    }
+
+    #[test]
+    fn test_to_svg_base_fret() {
+        let chord = Chord::from_str("C").unwrap();
+        let config = VoicingConfig {
+            min_fret: 3,
+            ..Default::default()
+        };
+        let voicing = chord.voicings(config).next().unwrap();
+        let svg = ChordChart::new(&chord, voicing, 4).to_svg();
+
+        // No nut but a label for the first fret shown.
+        assert!(!svg.contains(r#"stroke-width="4""#));
+        assert!(svg.contains(r#"text-anchor="end">3</text>"#));
+        // The first fret shown is fret 3.
+        assert!(svg.contains(r#"<circle cx="90" cy="42" r="7" fill="black"/>"#));
+    }
+
+    #[rstest(
+        chord,
+        barre,
+        case("C", None),
+        case(
+            "Bb",
+            Some(r#"<rect x="63" y="35" width="34" height="14" rx="7" fill="black"/>"#)
+        )
+    )]
+    fn test_to_svg_barre(chord: Chord, barre: Option<&str>) {
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        let svg = ChordChart::new(&chord, voicing, 4).to_svg();
+
+        match barre {
+            Some(rect) => assert!(svg.contains(rect)),
+            None => assert!(!svg.contains("<rect")),
+        }
+    }
+
+    #[test]
+    fn test_chord_spelling() {
+        // Without a chord, the notes are spelled as sharps.
+        let voicing = Voicing::new([3, 2, 1, 1], Tuning::C);
+        assert!(voicing.notes().any(|n| n.to_string() == "A#"));
+
+        let chord = Chord::from_str("Bb").unwrap();
+        let chart = ChordChart::new(&chord, voicing, 4);
+        let diagram = indoc!(
+            "
+            A  ||=1=|---|---|---|- Bb
+            E  ||=1=|---|---|---|- F
+            C  ||---|-2-|---|---|- D
+            G  ||---|---|-3-|---|- Bb
+        "
+        );
+        assert_eq!(chart.to_string(), diagram);
+
+        for voicing in chord.voicings(VoicingConfig::default()) {
+            let chart = ChordChart::new(&chord, voicing, 4).to_string();
+            assert!(!chart.contains("A#"));
+            assert!(!chart.contains("D#"));
+        }
+    }
+
+    #[rstest(show_tuning, show_notes, diagram,
+        case(
+            true,
+            true,
+            indoc!("
+                Tuning: G C E A
+
+                A  ||---|---|-3-|---|- C
+                E o||---|---|---|---|- E
+                C o||---|---|---|---|- C
+                G o||---|---|---|---|- G
+            ")
+        ),
+        case(
+            false,
+            false,
+            indoc!("
+                A  ||---|---|-3-|---|-
+                E o||---|---|---|---|-
+                C o||---|---|---|---|-
+                G o||---|---|---|---|-
+            ")
+        ),
+        case(
+            true,
+            false,
+            indoc!("
+                Tuning: G C E A
+
+                A  ||---|---|-3-|---|-
+                E o||---|---|---|---|-
+                C o||---|---|---|---|-
+                G o||---|---|---|---|-
+            ")
+        ),
+    )]
+    fn test_with_tuning_and_notes(show_tuning: bool, show_notes: bool, diagram: &str) {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = Voicing::new([0, 0, 0, 3], Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 4)
+            .with_tuning(show_tuning)
+            .with_notes(show_notes);
+        assert_eq!(chart.to_string(), diagram);
+    }
+
+    #[rstest(fret_pattern, window, diagram,
+        case(
+            "0 7 8 7",
+            false,
+            indoc!("
+                A  -|-2-|---|---|---|---|- E
+                E  -|---|-3-|---|---|---|- C
+                C  -|-1-|---|---|---|---|- G
+                G o-|---|---|---|---|---|- G
+                      7
+            ")
+        ),
+        case(
+            "0 7 8 7",
+            true,
+            indoc!("
+                A  -|-2-|---|---|---|- E
+                E  -|---|-3-|---|---|- C
+                C  -|-1-|---|---|---|- G
+                G o-|---|---|---|---|- G
+                      7
+            ")
+        ),
+        case(
+            "5 4 3 3",
+            false,
+            indoc!("
+                A  ||---|---|=1=|---|---|- C
+                E  ||---|---|=1=|---|---|- G
+                C  ||---|---|---|-2-|---|- E
+                G  ||---|---|---|---|-3-|- C
+            ")
+        ),
+        case(
+            "5 4 3 3",
+            true,
+            indoc!("
+                A  -|=1=|---|---|---|- C
+                E  -|=1=|---|---|---|- G
+                C  -|---|-2-|---|---|- E
+                G  -|---|---|-3-|---|- C
+                      3
+            ")
+        ),
+    )]
+    fn test_with_window(fret_pattern: FretPattern, window: bool, diagram: &str) {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = Voicing::new(fret_pattern, Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 5).with_window(window);
+        assert_eq!(chart.to_string(), diagram);
+    }
+
+    #[rstest(chord, frets, diagram,
+        case(
+            "C7",
+            [0, 0, 0, 1],
+            indoc!("
+                A  ||-1-|---|---|---|- Bb b7
+                E o||---|---|---|---|- E  3
+                C o||---|---|---|---|- C  R
+                G o||---|---|---|---|- G  5
+
+                R: root, 3: major third, 5: perfect fifth, b7: minor seventh
+            ")
+        ),
+        case(
+            "C7",
+            [3, 4, 3, 3],
+            indoc!("
+                A  ||---|---|=1=|---|- C  R
+                E  ||---|---|=1=|---|- G  5
+                C  ||---|---|===|-2-|- E  3
+                G  ||---|---|=1=|---|- Bb b7
+
+                R: root, 3: major third, 5: perfect fifth, b7: minor seventh
+            ")
+        ),
+        case(
+            "Caug",
+            [1, 0, 0, 3],
+            indoc!("
+                A  ||---|---|-3-|---|- C  R
+                E o||---|---|---|---|- E  3
+                C o||---|---|---|---|- C  R
+                G  ||-1-|---|---|---|- G# #5
+
+                R: root, 3: major third, #5: augmented fifth
+            ")
+        ),
+    )]
+    fn test_with_intervals(chord: Chord, frets: [FretID; STRING_COUNT], diagram: &str) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 4).with_intervals(true);
+        assert_eq!(chart.to_string(), diagram);
+    }
+
+    #[rstest(chord, min_fret, case("C", 0), case("Bb", 0), case("C", 3))]
+    fn test_left_handed(chord: Chord, min_fret: FretID) {
+        let config = VoicingConfig {
+            min_fret,
+            ..Default::default()
+        };
+        let voicing = chord.voicings(config).next().unwrap();
+        let chart = ChordChart::new(&chord, voicing.clone(), 4).to_string();
+        let mirrored = ChordChart::new(&chord, voicing, 4)
+            .with_left_handed(true)
+            .to_string();
+
+        // The strings are reversed while the fret label stays at the bottom.
+        let mut lines = chart.lines().collect_vec();
+        lines[..STRING_COUNT].reverse();
+        assert_eq!(mirrored.lines().collect_vec(), lines);
+    }
+
+    #[test]
+    fn test_left_handed_svg() {
+        let chord = Chord::from_str("Bb").unwrap();
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        let svg = ChordChart::new(&chord, voicing, 4)
+            .with_left_handed(true)
+            .to_svg();
+
+        // The barre covers the two rightmost strings in a standard chart
+        // and the two leftmost strings in a left-handed one.
+        assert!(svg.contains(r#"<rect x="23" y="35" width="34" height="14" rx="7" fill="black"/>"#));
+        // The G string (third fret) is on the right.
+        assert!(svg.contains(r#"<circle cx="90" cy="90" r="7" fill="black"/>"#));
+        assert!(!svg.contains(r#"<circle cx="30" cy="90" r="7" fill="black"/>"#));
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let chord = Chord::from_str("C").unwrap();
+        let charts = [[0, 0, 0, 3], [5, 4, 3, 3]]
+            .iter()
+            .map(|&frets| ChordChart::new(&chord, Voicing::new(frets, Tuning::C), 4))
+            .collect_vec();
+        let blocks = charts
+            .iter()
+            .map(|chart| chart.lines().collect_vec())
+            .collect_vec();
+
+        // The first chart has no line with the base fret, so it is padded.
+        assert_eq!(blocks[0].len() + 1, blocks[1].len());
+
+        let diagram = indoc!(
+            "
+            A  ||---|---|-3-|---|- C  A  -|=1=|---|---|---|- C
+            E o||---|---|---|---|- E  E  -|=1=|---|---|---|- G
+            C o||---|---|---|---|- C  C  -|---|-2-|---|---|- E
+            G o||---|---|---|---|- G  G  -|---|---|-3-|---|- C
+                                            3
+            "
+        );
+        let lines = side_by_side(&blocks, 2);
+        assert_eq!(lines.len(), blocks[1].len());
+        assert_eq!(format!("{}\n", lines.join("\n")), diagram);
+    }
+
+    #[test]
+    fn test_side_by_side_empty() {
+        assert!(side_by_side(&[], 2).is_empty());
+    }
+
+    #[rstest(
+        chord,
+        frets,
+        left_handed,
+        show_intervals,
+        show_tuning,
+        case("C", [0, 0, 0, 3], false, false, false),
+        case("C", [5, 4, 3, 3], false, false, false),
+        case("Bb7", [1, 2, 1, 1], true, true, false),
+        case("G", [7, 7, 7, 10], false, true, true),
+        case("D", [2, 2, 2, 0], true, false, true)
+    )]
+    fn test_lines(
+        chord: Chord,
+        frets: [FretID; STRING_COUNT],
+        left_handed: bool,
+        show_intervals: bool,
+        show_tuning: bool,
+    ) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 4)
+            .with_left_handed(left_handed)
+            .with_intervals(show_intervals)
+            .with_tuning(show_tuning);
+
+        let lines = chart.lines().collect_vec();
+        assert_eq!(format!("{}\n", lines.join("\n")), chart.to_string());
+    }
+
+    #[rstest(
+        chord,
+        style,
+        frets,
+        diagram,
+        case(
+            "C",
+            ChartStyle::Ascii,
+            [0, 0, 0, 3],
+            indoc!("
+                A  ||---|---|-3-|---|- C
+                E o||---|---|---|---|- E
+                C o||---|---|---|---|- C
+                G o||---|---|---|---|- G
+            ")
+        ),
+        case(
+            "C",
+            ChartStyle::Unicode,
+            [0, 0, 0, 3],
+            indoc!("
+                A  ║║───┼───┼─3─┼───┼─ C
+                E ○║║───┼───┼───┼───┼─ E
+                C ○║║───┼───┼───┼───┼─ C
+                G ○║║───┼───┼───┼───┼─ G
+            ")
+        ),
+        case(
+            "C",
+            ChartStyle::Ascii,
+            [5, 4, 3, 3],
+            indoc!("
+                A  -|=1=|---|---|---|- C
+                E  -|=1=|---|---|---|- G
+                C  -|---|-2-|---|---|- E
+                G  -|---|---|-3-|---|- C
+                      3
+            ")
+        ),
+        case(
+            "C",
+            ChartStyle::Unicode,
+            [5, 4, 3, 3],
+            indoc!("
+                A  ─┼━1━┼───┼───┼───┼─ C
+                E  ─┼━1━┼───┼───┼───┼─ G
+                C  ─┼───┼─2─┼───┼───┼─ E
+                G  ─┼───┼───┼─3─┼───┼─ C
+                      3
+            ")
+        ),
+        case(
+            "G",
+            ChartStyle::Unicode,
+            [0, 2, 3, 2],
+            indoc!("
+                A  ║║───┼─2─┼───┼───┼─ B
+                E  ║║───┼───┼─3─┼───┼─ G
+                C  ║║───┼─1─┼───┼───┼─ D
+                G ○║║───┼───┼───┼───┼─ G
+            ")
+        )
+    )]
+    fn test_with_style(
+        chord: Chord,
+        style: ChartStyle,
+        frets: [FretID; STRING_COUNT],
+        diagram: &str,
+    ) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 4).with_style(style);
+        assert_eq!(chart.to_string(), diagram);
+    }
+
+    #[test]
+    fn test_with_style_muted_string() {
+        let chord = Chord::from_str("G").unwrap();
+        let voicing = Voicing::new(FretPattern::from_str("x232").unwrap(), Tuning::C);
+        let chart = ChordChart::new(&chord, voicing, 4).with_style(ChartStyle::Unicode);
+
+        assert!(!chart.to_string().is_ascii());
+        assert!(chart.to_string().contains("G ×║║───┼───┼───┼───┼─\n"));
+
+        let chart = chart.with_style(ChartStyle::Ascii);
+        assert!(chart.to_string().is_ascii());
+    }
 }
@@ -1,46 +1,374 @@
-use std::{slice::Iter, str::FromStr};
+use std::{
+    fmt, iter,
+    slice::{Chunks, Iter},
+    str::FromStr,
+};
 
-use crate::Chord;
+use itertools::{Itertools, TupleWindows};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    Chord, ChordType, FretID, Interval, Note, Scale, Spelling, VoicingConfig, PITCH_CLASS_COUNT,
+};
+
+/// Highest fret at which a capo is suggested by `ChordSequence::suggest_capo`.
+const MAX_CAPO_FRET: FretID = 7;
+
+/// Highest number of times a chord can be played by a repeat such as `x4`.
+const MAX_REPEAT_COUNT: usize = 64;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ChordSequence {
     chords: Vec<Chord>,
+    /// Free-form annotations of the chords such as strum patterns (e.g. `DUDU`
+    /// for `C(DUDU)`), one per chord.
+    annotations: Vec<Option<String>>,
 }
 
 impl ChordSequence {
+    /// Parse a chord sequence written in Nashville number notation such as
+    /// `1 6m 4 5` in the given `key`.
+    ///
+    /// Each chord is given by a scale degree of the major scale (optionally
+    /// preceded by `b` or `#`) followed by a chord type symbol such as `m`,
+    /// `7` or `dim`. Bass notes are given by scale degrees as well, e.g. `1/3`.
+    ///
+    /// Repeats are written in the same way as in `from_str`.
+    pub fn from_nashville(s: &str, key: Note) -> Result<Self, ParseChordSequenceError> {
+        parse_chords(s, |number| parse_nashville_chord(number, key))
+    }
+
     pub fn chords(&self) -> Iter<'_, Chord> {
         self.chords.iter()
     }
 
+    /// Return an iterator over the annotations of the chords (in the same order
+    /// as `chords`), e.g. `Some("DUDU")` for `C(DUDU)` and `None` for `Am`.
+    ///
+    /// Annotations are kept as they are and do not affect the chords or their voicings.
+    pub fn annotations(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.annotations
+            .iter()
+            .map(|annotation| annotation.as_deref())
+    }
+
+    /// Return an iterator over all pairs of adjacent chords, e.g. `(C, F)` and
+    /// `(F, G)` for `C F G`.
+    pub fn pairs(&self) -> TupleWindows<Iter<'_, Chord>, (&Chord, &Chord)> {
+        self.chords().tuple_windows()
+    }
+
+    /// Return an iterator over groups of `n` consecutive chords, e.g. to split
+    /// the sequence into bars. The last group contains fewer than `n` chords if
+    /// the length of the sequence is not a multiple of `n`.
+    ///
+    /// Panics if `n` is 0.
+    pub fn chunks(&self, n: usize) -> Chunks<'_, Chord> {
+        self.chords.chunks(n)
+    }
+
     pub fn transpose(&self, semitones: i8) -> Self {
         let chords = self.chords().map(|c| c.transpose(semitones)).collect();
-        Self { chords }
+        let annotations = self.annotations.clone();
+        Self {
+            chords,
+            annotations,
+        }
+    }
+
+    /// Transpose the chord sequence so that the first chord has `root` as its root.
+    ///
+    /// The chords are moved by at most six semitones (up or down) and spelled
+    /// according to `root`, e.g. transposing to Bb results in flat notes.
+    pub fn transpose_to(&self, root: Note) -> Self {
+        let semitones = match self.chords().next() {
+            Some(chord) => (root.pitch_class - chord.root.pitch_class) as i8,
+            None => return self.clone(),
+        };
+
+        // Prefer the shorter way.
+        let semitones = match semitones {
+            s if s > PITCH_CLASS_COUNT as i8 / 2 => s - PITCH_CLASS_COUNT as i8,
+            s => s,
+        };
+
+        self.transpose(semitones).respell(root.spelling())
+    }
+
+    /// Return the Roman numeral analysis of each chord in the major key of `key`
+    /// (see `Chord::analyze`).
+    pub fn analyze(&self, key: Note) -> Vec<String> {
+        self.chords().map(|c| c.analyze(key)).collect()
+    }
+
+    /// Return for each chord whether it is diatonic to `key`
+    /// (see `Chord::is_diatonic`).
+    pub fn diatonic_chords(&self, key: Scale) -> Vec<bool> {
+        self.chords().map(|c| c.is_diatonic(key)).collect()
+    }
+
+    /// Return the chord sequence with all the chords spelled according to `spelling`.
+    pub fn respell(&self, spelling: Spelling) -> Self {
+        let chords = self.chords().map(|c| c.respell(spelling)).collect();
+        let annotations = self.annotations.clone();
+        Self {
+            chords,
+            annotations,
+        }
+    }
+
+    /// Return the fret at which to place a capo to make the chord sequence
+    /// easiest to play, together with the chords whose shapes are then played
+    /// (i.e. the chords transposed down by the number of frets of the capo).
+    ///
+    /// Capo positions up to fret 7 are tried, and each one is scored by
+    /// the sum of the difficulties of the easiest voicings (see
+    /// `Voicing::difficulty`) of the chord shapes played relative to the capo.
+    /// If several positions score the same, the lowest one is chosen.
+    ///
+    /// Return `None` if the chords cannot be played with any capo position.
+    pub fn suggest_capo(&self, config: VoicingConfig) -> Option<(FretID, Self)> {
+        (0..=MAX_CAPO_FRET.min(config.max_fret))
+            .filter_map(|capo| {
+                let shapes = self.transpose(-(capo as i8));
+
+                // No strings can be played beyond the last fret.
+                let config = VoicingConfig {
+                    max_fret: config.max_fret - capo,
+                    ..config
+                };
+                let difficulty: Option<u32> = shapes
+                    .chords()
+                    .map(|chord| chord.best_voicing(config).map(|v| v.difficulty()))
+                    .sum();
+
+                difficulty.map(|difficulty| (difficulty, capo, shapes))
+            })
+            .min_by_key(|(difficulty, capo, _shapes)| (*difficulty, *capo))
+            .map(|(_difficulty, capo, shapes)| (capo, shapes))
+    }
+
+    /// Return a chord sequence in which consecutive repetitions of the same
+    /// chord (with the same annotation) are merged into a single chord.
+    pub fn merge_repeats(&self) -> Self {
+        let (chords, annotations) = self
+            .chords
+            .iter()
+            .cloned()
+            .zip(self.annotations.iter().cloned())
+            .dedup()
+            .unzip();
+        Self {
+            chords,
+            annotations,
+        }
     }
 }
 
+/// Custom error for strings that cannot be parsed into chord sequences.
 #[derive(Debug, thiserror::Error)]
-#[error("could not parse chord sequence")]
-pub struct ParseChordSequenceError;
+pub enum ParseChordSequenceError {
+    #[error("could not parse chord sequence (invalid chord '{0}')")]
+    InvalidChord(String),
+    #[error("could not parse chord sequence ('{0}' is not preceded by a chord to repeat)")]
+    NothingToRepeat(String),
+    #[error(
+        "could not parse chord sequence ('{0}' repeats a chord more than {MAX_REPEAT_COUNT} times)"
+    )]
+    TooManyRepeats(String),
+}
+
+/// Split a chord sequence into its chords.
+///
+/// Besides whitespace (including newlines), bar lines `|` and commas separate
+/// the chords, so that lead sheets such as `C | Am | F G |` can be used directly.
+fn tokens(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| c.is_whitespace() || c == '|' || c == ',')
+        .filter(|token| !token.is_empty())
+}
+
+/// Parse a repeat token such as `x4` and return the number of times
+/// the previous chord is played in total.
+fn parse_repeat(token: &str) -> Option<usize> {
+    token
+        .strip_prefix('x')?
+        .parse()
+        .ok()
+        .filter(|&count| count > 0)
+}
+
+/// Split an annotated chord such as `C(DUDU)` into the chord symbol and
+/// the annotation. Parentheses with `no` followed by a digit denote omitted
+/// intervals (e.g. `C(no5)`) and are not taken as annotations, but `C(normal)` is.
+fn split_annotation(token: &str) -> Option<(&str, &str)> {
+    let (symbol, annotation) = token.strip_suffix(')')?.rsplit_once('(')?;
+
+    let is_omission = annotation
+        .strip_prefix("no")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+
+    match annotation.is_empty() || is_omission {
+        true => None,
+        false => Some((symbol, annotation)),
+    }
+}
+
+/// Parse the chords of a chord sequence using `parse_chord` and expand repeats:
+/// `%` repeats the previous chord once and `xN` plays the previous chord
+/// `N` times in total (at most 64 times), e.g. `C % Am x2` results in `C C Am Am`.
+fn parse_chords(
+    s: &str,
+    parse_chord: impl Fn(&str) -> Option<Chord>,
+) -> Result<ChordSequence, ParseChordSequenceError> {
+    let mut chords: Vec<Chord> = vec![];
+    let mut annotations: Vec<Option<String>> = vec![];
+
+    for token in tokens(s) {
+        let repeat_count = match (token, parse_repeat(token)) {
+            ("%", _) => 1,
+            (_, Some(count)) if count > MAX_REPEAT_COUNT => {
+                return Err(ParseChordSequenceError::TooManyRepeats(token.to_string()))
+            }
+            (_, Some(count)) => count - 1,
+            _ => {
+                let (chord, annotation) = parse_chord(token)
+                    .map(|chord| (chord, None))
+                    .or_else(|| {
+                        let (symbol, annotation) = split_annotation(token)?;
+                        Some((parse_chord(symbol)?, Some(annotation.to_string())))
+                    })
+                    .ok_or_else(|| ParseChordSequenceError::InvalidChord(token.to_string()))?;
+                chords.push(chord);
+                annotations.push(annotation);
+                continue;
+            }
+        };
+
+        let (chord, annotation) = chords
+            .last()
+            .cloned()
+            .zip(annotations.last().cloned())
+            .ok_or_else(|| ParseChordSequenceError::NothingToRepeat(token.to_string()))?;
+        chords.extend(iter::repeat(chord).take(repeat_count));
+        annotations.extend(iter::repeat(annotation).take(repeat_count));
+    }
+
+    Ok(ChordSequence {
+        chords,
+        annotations,
+    })
+}
+
+/// Parse a scale degree such as `4`, `b7` or `#4` at the beginning of `s`.
+/// Return the corresponding note in `key` and the rest of the string.
+fn parse_nashville_degree(s: &str, key: Note) -> Option<(Note, &str)> {
+    use Interval::*;
+
+    let (accidental, s) = match s.get(0..1)? {
+        "b" => (Some('b'), &s[1..]),
+        "#" => (Some('#'), &s[1..]),
+        _ => (None, s),
+    };
+
+    let interval = match s.get(0..1)? {
+        "1" => PerfectUnison,
+        "2" => MajorSecond,
+        "3" => MajorThird,
+        "4" => PerfectFourth,
+        "5" => PerfectFifth,
+        "6" => MajorSixth,
+        "7" => MajorSeventh,
+        _ => return None,
+    };
+
+    let note = match accidental {
+        Some('b') => (key + interval) - 1,
+        Some(_) => (key + interval) + 1,
+        None => key + interval,
+    };
+
+    Some((note, &s[1..]))
+}
+
+/// Parse a chord in Nashville number notation such as `6m` or `1/3` in the given `key`.
+fn parse_nashville_chord(s: &str, key: Note) -> Option<Chord> {
+    let (symbol, bass) = match s.rsplit_once('/') {
+        Some((symbol, bass)) => match parse_nashville_degree(bass, key)? {
+            (bass, "") => (symbol, Some(bass)),
+            _ => return None,
+        },
+        None => (s, None),
+    };
+
+    let (root, suffix) = parse_nashville_degree(symbol, key)?;
+    let chord = Chord::new(root, ChordType::from_str(suffix).ok()?);
+
+    match bass {
+        Some(bass) => Some(chord.with_bass(bass)),
+        None => Some(chord),
+    }
+}
 
 impl FromStr for ChordSequence {
     type Err = ParseChordSequenceError;
 
+    /// Parse a chord sequence such as `C Am F G`.
+    ///
+    /// Chords are separated by whitespace, bar lines or commas (see `tokens`).
+    /// Optionally, `%` can be used to repeat the previous chord and `xN` to play
+    /// the previous chord `N` times, e.g. `C % | Am x2` results in `C C Am Am`.
+    /// Each chord may be followed by an annotation in parentheses such as
+    /// a strum pattern, e.g. `C(DUDU)` (see `annotations`).
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let res: Result<Vec<_>, _> = s.split_whitespace().map(Chord::from_str).collect();
+        parse_chords(s, |token| Chord::from_str(token).ok())
+    }
+}
 
-        if let Ok(chords) = res {
-            return Ok(Self { chords });
-        }
+/// Chord sequences are displayed as chord symbols separated by spaces,
+/// followed by their annotations (if any), e.g. `C(DUDU) Am F G`.
+impl fmt::Display for ChordSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tokens = self
+            .chords()
+            .zip(self.annotations())
+            .map(|(chord, annotation)| match annotation {
+                Some(annotation) => format!("{}({annotation})", chord.name()),
+                None => chord.name(),
+            });
+
+        write!(f, "{}", tokens.format(" "))
+    }
+}
 
-        Err(ParseChordSequenceError)
+/// Chord sequences are serialized as lists of chord symbols (without annotations),
+/// e.g. `["C", "Am", "F", "G7"]`.
+#[cfg(feature = "serde")]
+impl Serialize for ChordSequence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.chords())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ChordSequence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let chords: Vec<Chord> = Vec::deserialize(deserializer)?;
+        let annotations = vec![None; chords.len()];
+        Ok(Self {
+            chords,
+            annotations,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
     use rstest::rstest;
 
     use super::*;
+    use crate::ScaleType;
 
     #[rstest(
         chord_seq,
@@ -49,6 +377,19 @@ mod tests {
         case("C", &["C"]),
         case("C F G", &["C", "F", "G"]),
         case("Dsus2 Am7 C#", &["Dsus2", "Am7", "C#"]),
+        case("C | Am | F G |", &["C", "Am", "F", "G"]),
+        case("| C | Am |\n| F | G |\n", &["C", "Am", "F", "G"]),
+        case("C|Am|F,G", &["C", "Am", "F", "G"]),
+        case("C, Am, F, G", &["C", "Am", "F", "G"]),
+        case("C/G | D/F#", &["C/G", "D/F#"]),
+        case(" | \n", &[]),
+        case("C x4", &["C", "C", "C", "C"]),
+        case("C x1 F", &["C", "F"]),
+        case("C % Am x2", &["C", "C", "Am", "Am"]),
+        case("C % %", &["C", "C", "C"]),
+        case("| C | % | Am x2 | F G |", &["C", "C", "Am", "Am", "F", "G"]),
+        case("C x2 % F", &["C", "C", "C", "F"]),
+        case("C x64", &["C"; 64]),
     )]
     fn test_from_str(chord_seq: ChordSequence, chords: &[&str]) {
         let chords1: Vec<Chord> = chord_seq.chords().cloned().collect();
@@ -56,9 +397,133 @@ mod tests {
         assert_eq!(chords1, chords2);
     }
 
-    #[rstest(chord_seq, case("Z"), case("A Z"))]
-    fn test_from_str_fail(chord_seq: &str) {
-        assert!(ChordSequence::from_str(chord_seq).is_err());
+    #[rstest(
+        chord_seq,
+        token,
+        case("Z", "Z"),
+        case("A Z", "Z"),
+        case("C | H", "H"),
+        case("C | Am\nF Gx |", "Gx"),
+        case("C(DUDU) H(DU)", "H(DU)"),
+        case("C(no9)", "C(no9)"),
+        case("C()", "C()"),
+        case("C x0", "x0"),
+        case("C x", "x"),
+        case("C xx2", "xx2")
+    )]
+    fn test_from_str_fail(chord_seq: &str, token: &str) {
+        let err = ChordSequence::from_str(chord_seq).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("could not parse chord sequence (invalid chord '{token}')")
+        );
+    }
+
+    #[rstest(
+        chord_seq,
+        token,
+        case("%", "%"),
+        case("% C", "%"),
+        case("| % | C |", "%"),
+        case("x2 C", "x2")
+    )]
+    fn test_from_str_nothing_to_repeat(chord_seq: &str, token: &str) {
+        let err = ChordSequence::from_str(chord_seq).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "could not parse chord sequence ('{token}' is not preceded by a chord to repeat)"
+            )
+        );
+    }
+
+    #[rstest(
+        chord_seq,
+        token,
+        case("C x65", "x65"),
+        case("C x99999999999", "x99999999999"),
+        case("x65", "x65")
+    )]
+    fn test_from_str_too_many_repeats(chord_seq: &str, token: &str) {
+        let err = ChordSequence::from_str(chord_seq).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "could not parse chord sequence ('{token}' repeats a chord more than 64 times)"
+            )
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest(
+        chord_seq,
+        json,
+        case("", r#"[]"#),
+        case("C Am F G", r#"["C","Am","F","G"]"#),
+        case("C Am F G7", r#"["C","Am","F","G7"]"#),
+        case("Cmaj7 F#m7b5 Bb/D C(no3)", r#"["Cmaj7","F#m7b5","Bb/D","C(no3)"]"#)
+    )]
+    fn test_serde_round_trip(chord_seq: ChordSequence, json: &str) {
+        assert_eq!(serde_json::to_string(&chord_seq).unwrap(), json);
+
+        let deserialized: ChordSequence = serde_json::from_str(json).unwrap();
+        assert_eq!(deserialized, chord_seq);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest(json, case(r#"["C","H"]"#), case(r#""C Am""#), case(r#"[1]"#))]
+    fn test_deserialize_fail(json: &str) {
+        assert!(serde_json::from_str::<ChordSequence>(json).is_err());
+    }
+
+    #[rstest(
+        chord_seq,
+        pairs,
+        case("", &[]),
+        case("C", &[]),
+        case("C F", &[("C", "F")]),
+        case("C F G C", &[("C", "F"), ("F", "G"), ("G", "C")]),
+    )]
+    fn test_pairs(chord_seq: ChordSequence, pairs: &[(&str, &str)]) {
+        let pairs1 = chord_seq
+            .pairs()
+            .map(|(c1, c2)| (c1.clone(), c2.clone()))
+            .collect_vec();
+        let pairs2 = pairs
+            .iter()
+            .map(|(c1, c2)| (Chord::from_str(c1).unwrap(), Chord::from_str(c2).unwrap()))
+            .collect_vec();
+        assert_eq!(pairs1, pairs2);
+        assert_eq!(
+            chord_seq.pairs().count(),
+            chord_seq.chords().len().saturating_sub(1)
+        );
+    }
+
+    #[rstest(
+        chord_seq,
+        n,
+        chunks,
+        case("", 2, &[]),
+        case("C F G C", 2, &["C F", "G C"]),
+        case("C F G C Am", 2, &["C F", "G C", "Am"]),
+        case("C F G C Am", 4, &["C F G C", "Am"]),
+        case("C F G", 4, &["C F G"]),
+        case("C F G", 1, &["C", "F", "G"]),
+    )]
+    fn test_chunks(chord_seq: ChordSequence, n: usize, chunks: &[&str]) {
+        let chunks1 = chord_seq.chunks(n).map(|c| c.to_vec()).collect_vec();
+        let chunks2 = chunks
+            .iter()
+            .map(|c| {
+                ChordSequence::from_str(c)
+                    .unwrap()
+                    .chords()
+                    .cloned()
+                    .collect_vec()
+            })
+            .collect_vec();
+        assert_eq!(chunks1, chunks2);
     }
 
     #[rstest(
@@ -74,4 +539,206 @@ mod tests {
     fn test_transpose(chord_seq1: ChordSequence, semitones: i8, chord_seq2: ChordSequence) {
         assert_eq!(chord_seq1.transpose(semitones), chord_seq2);
     }
+
+    #[rstest(
+        chord_seq1,
+        chord_seq2,
+        case("", ""),
+        case("C", "C"),
+        case("C C G", "C G"),
+        case("C C G G G C", "C G C"),
+        case("C Cm C7", "C Cm C7"),
+        case("C(DUDU) % C(DDU) C(DDU)", "C(DUDU) C(DDU)")
+    )]
+    fn test_merge_repeats(chord_seq1: ChordSequence, chord_seq2: ChordSequence) {
+        assert_eq!(chord_seq1.merge_repeats(), chord_seq2);
+    }
+
+    #[rstest(
+        chord_seq,
+        root,
+        names,
+        case("", "D", ""),
+        case("C Am F G", "D", "D Bm G A"),
+        case("C Am F G", "C", "C Am F G"),
+        case("C Am F G", "Bb", "Bb Gm Eb F"),
+        case("C Am F G", "F#", "F# D#m B C#"),
+        case("Am7 D7", "C", "Cm7 F7")
+    )]
+    fn test_transpose_to(chord_seq: ChordSequence, root: Note, names: &str) {
+        let transposed = chord_seq.transpose_to(root);
+        assert_eq!(transposed.chords().map(|c| c.name()).join(" "), names);
+    }
+
+    #[rstest(
+        numbers,
+        key,
+        chords,
+        case("", "C", ""),
+        case("1 6m 4 5", "C", "C Am F G"),
+        case("1 6m 4 5", "Eb", "Eb Cm Ab Bb"),
+        case("1 4 b7 5", "G", "G C F D"),
+        case("1 #4dim 5", "C", "C F#dim G"),
+        case("2m7 57 1maj7 7m7b5", "C", "Dm7 G7 Cmaj7 Bm7b5"),
+        case("1 1/3 4 5/7", "D", "D D/F# G A/C#"),
+        case("b3 b6 b7", "A", "C F G"),
+        case("| 1 | 6m | 4 5 |", "C", "C Am F G"),
+        case("1 % 6m x2", "C", "C C Am Am")
+    )]
+    fn test_from_nashville(numbers: &str, key: Note, chords: &str) {
+        let chord_seq = ChordSequence::from_nashville(numbers, key).unwrap();
+        assert_eq!(chord_seq.chords().map(|c| c.name()).join(" "), chords);
+    }
+
+    #[rstest(
+        numbers,
+        case("8"),
+        case("0"),
+        case("C"),
+        case("1x"),
+        case("b"),
+        case("1/3m"),
+        case("1 6m Z")
+    )]
+    fn test_from_nashville_fail(numbers: &str) {
+        assert!(ChordSequence::from_nashville(numbers, Note::from_str("C").unwrap()).is_err());
+    }
+
+    #[rstest(
+        chord_seq,
+        key,
+        numerals,
+        case("", "C", ""),
+        case("C Am F G", "C", "I vi IV V"),
+        case("Cmaj7 Am7 Dm7 G7", "C", "Imaj7 vi7 ii7 V7"),
+        case("C D7 G7 C", "C", "I V7/V V7 I"),
+        case("G E7 Am D7 G", "G", "I V7/ii ii V7 I")
+    )]
+    fn test_analyze(chord_seq: ChordSequence, key: Note, numerals: &str) {
+        assert_eq!(chord_seq.analyze(key).join(" "), numerals);
+    }
+
+    #[rstest(
+        chord_seq,
+        capo,
+        shapes,
+        case("Bb Eb F", 3, "G C D"),
+        case("Eb Bb Cm Ab", 3, "C G Am F"),
+        case("E A B7", 2, "D G A7"),
+        case("C F G", 0, "C F G")
+    )]
+    fn test_suggest_capo(chord_seq: ChordSequence, capo: FretID, shapes: ChordSequence) {
+        let config = VoicingConfig::default();
+        let difficulty = |chord_seq: &ChordSequence, config| -> u32 {
+            let config = VoicingConfig {
+                sort_by_difficulty: true,
+                ..config
+            };
+            chord_seq
+                .chords()
+                .map(|chord| chord.voicings_capped(config, 1)[0].difficulty())
+                .sum()
+        };
+
+        let (suggested_capo, suggested_shapes) = chord_seq.suggest_capo(config).unwrap();
+        assert_eq!(suggested_capo, capo);
+        assert_eq!(suggested_shapes, shapes);
+
+        // The shapes played with the capo are easier than the original chords.
+        let shapes_config = VoicingConfig {
+            max_fret: config.max_fret - capo,
+            ..config
+        };
+        if capo > 0 {
+            assert!(difficulty(&shapes, shapes_config) < difficulty(&chord_seq, config));
+        }
+    }
+
+    #[test]
+    fn test_suggest_capo_impossible() {
+        let chord_seq = ChordSequence::from_str("Cm C6").unwrap();
+        // Cm cannot be played using open strings only.
+        let config = VoicingConfig {
+            max_fret: 0,
+            ..Default::default()
+        };
+        assert_eq!(chord_seq.suggest_capo(config), None);
+    }
+
+    #[rstest(
+        chord_seq,
+        chords,
+        annotations,
+        case("C Am", &["C", "Am"], &[None, None]),
+        case("C(DUDU) Am", &["C", "Am"], &[Some("DUDU"), None]),
+        case("| C(D-DU) | G7(DU) x2 |", &["C", "G7", "G7"], &[Some("D-DU"), Some("DU"), Some("DU")]),
+        case("C(no5)(DUDU) %", &["C(no5)", "C(no5)"], &[Some("DUDU"), Some("DUDU")]),
+        case("C/G(DUDU)", &["C/G"], &[Some("DUDU")]),
+        case("C(normal) Am(no3)", &["C", "Am(no3)"], &[Some("normal"), None]),
+        case("C(no5)(nothing)", &["C(no5)"], &[Some("nothing")])
+    )]
+    fn test_annotations(chord_seq: ChordSequence, chords: &[&str], annotations: &[Option<&str>]) {
+        let chords: Vec<Chord> = chords.iter().map(|c| Chord::from_str(c).unwrap()).collect();
+        assert_eq!(chord_seq.chords().cloned().collect::<Vec<_>>(), chords);
+        assert_eq!(chord_seq.annotations().collect::<Vec<_>>(), annotations);
+    }
+
+    #[rstest(
+        s,
+        case(""),
+        case("C Am F G"),
+        case("C(DUDU) Am F(D-DU) G7"),
+        case("C(no3)(DDUUDU) Bb/D")
+    )]
+    fn test_display_round_trip(s: &str) {
+        let chord_seq = ChordSequence::from_str(s).unwrap();
+        assert_eq!(chord_seq.to_string(), s);
+        assert_eq!(
+            ChordSequence::from_str(&chord_seq.to_string()).unwrap(),
+            chord_seq
+        );
+    }
+
+    #[test]
+    fn test_annotations_transpose() {
+        let chord_seq = ChordSequence::from_str("C(DUDU) Am F(DU)").unwrap();
+        assert_eq!(chord_seq.transpose(2).to_string(), "D(DUDU) Bm G(DU)");
+    }
+
+    #[test]
+    fn test_annotations_ignored_by_voicings() {
+        let chord_seq = ChordSequence::from_str("C(DUDU) Am(DDU)").unwrap();
+        let plain = ChordSequence::from_str("C Am").unwrap();
+        let config = VoicingConfig::default();
+
+        for (chord, plain_chord) in chord_seq.chords().zip(plain.chords()) {
+            assert_eq!(chord, plain_chord);
+            assert_eq!(
+                chord.voicings(config).collect_vec(),
+                plain_chord.voicings(config).collect_vec()
+            );
+        }
+    }
+
+    #[rstest(
+        chord_seq,
+        key,
+        scale_type,
+        diatonic,
+        case("", "C", ScaleType::Major, vec![]),
+        case("C Am F G", "C", ScaleType::Major, vec![true, true, true, true]),
+        case("C E7 Am", "C", ScaleType::Major, vec![true, false, true]),
+        case("Cmaj7 Ab7 G7", "C", ScaleType::Major, vec![true, false, true]),
+        case("Am Dm E7 Am", "A", ScaleType::NaturalMinor, vec![true, true, true, true]),
+        case("Am D F E", "A", ScaleType::NaturalMinor, vec![true, false, true, true])
+    )]
+    fn test_diatonic_chords(
+        chord_seq: ChordSequence,
+        key: Note,
+        scale_type: ScaleType,
+        diatonic: Vec<bool>,
+    ) {
+        let key = Scale::new(key, scale_type);
+        assert_eq!(chord_seq.diatonic_chords(key), diatonic);
+    }
 }
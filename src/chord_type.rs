@@ -1,5 +1,16 @@
-use std::{cmp::min, convert::TryFrom, fmt, str::FromStr};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::min, convert::TryFrom, fmt, str::FromStr};
 
+use itertools::Itertools;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::{Chord, Tuning, VoicingConfig};
 use crate::{Interval, PitchClass, PITCH_CLASS_COUNT, STRING_COUNT};
 
 /// The type of the chord depending on the intervals it contains.
@@ -15,6 +26,7 @@ use crate::{Interval, PitchClass, PITCH_CLASS_COUNT, STRING_COUNT};
 /// * <https://ukulele-chords.com>
 /// * <https://ukulelehelper.com>
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ChordType {
     Major,
     MajorSeventh,
@@ -35,6 +47,12 @@ pub enum ChordType {
     DominantSeventhSharpNinth,
     DominantSeventhFlatFifth,
     //DominantSeventhSharpFifth,
+    DominantSeventhSharpEleventh,
+    /// The altered dominant chord contains the root, the major third and the
+    /// minor seventh together with all the altered fifths and ninths
+    /// (b5, #5, b9 and #9). Only the #9 is required, the other alterations
+    /// are optional.
+    AlteredDominantSeventh,
     SuspendedFourth,
     SuspendedSecond,
     DominantSeventhSuspendedFourth,
@@ -49,12 +67,25 @@ pub enum ChordType {
     Diminished,
     DiminishedSeventh,
     HalfDiminishedSeventh,
+    /// The power chord. As chord types are made up of pitch classes, there is no
+    /// separate type for the power chord with the root doubled an octave higher:
+    /// the voicings of `Fifth` already double the root or the fifth on the remaining strings.
     Fifth,
     Augmented,
     AugmentedSeventh,
+    /// Without its (optional) fifth, the dominant seventh flat thirteenth chord
+    /// consists of the same pitches as the augmented seventh chord. It is listed
+    /// afterwards so that such pitches are named as an augmented seventh chord.
+    DominantSeventhFlatThirteenth,
     AugmentedMajorSeventh,
     AddedNinth,
     AddedFourth,
+    /// Consists of the same pitch classes as `AddedFourth`. It is listed afterwards
+    /// so that such pitches are named as an added fourth chord.
+    AddedEleventh,
+    /// Consists of the same pitch classes as `MajorSixth`. It is listed afterwards
+    /// so that such pitches are named as a major sixth chord.
+    AddedThirteenth,
 }
 
 impl ChordType {
@@ -81,6 +112,8 @@ impl ChordType {
             DominantSeventhSharpNinth,
             DominantSeventhFlatFifth,
             //DominantSeventhSharpFifth,
+            DominantSeventhSharpEleventh,
+            AlteredDominantSeventh,
             SuspendedFourth,
             SuspendedSecond,
             DominantSeventhSuspendedFourth,
@@ -98,92 +131,229 @@ impl ChordType {
             Fifth,
             Augmented,
             AugmentedSeventh,
+            DominantSeventhFlatThirteenth,
             AugmentedMajorSeventh,
             AddedNinth,
             AddedFourth,
+            AddedEleventh,
+            AddedThirteenth,
         ]
         .iter()
         .copied()
     }
 
+    /// Iterator over the values of the ChordType enum ordered by stack height
+    /// (see `stack_height`): triads first, then sixth chords, seventh chords
+    /// and finally ninth, eleventh and thirteenth chords. Chord types of the
+    /// same stack height keep their order from `values`.
+    pub fn values_by_stack_height() -> impl Iterator<Item = Self> {
+        Self::values().sorted_by_key(|chord_type| chord_type.stack_height())
+    }
+
     /// Return an iterator over the chord type's intervals.
     pub fn intervals(&self) -> impl Iterator<Item = Interval> + '_ {
-        use ChordType::*;
+        use Interval::*;
 
-        let interval_names = match self {
-            Major => vec!["P1", "M3", "P5"],
-            MajorSeventh => vec!["P1", "M3", "P5", "M7"],
-            MajorNinth => vec!["P1", "M3", "P5", "M7", "M9"],
-            MajorEleventh => vec!["P1", "M3", "P5", "M7", "M9", "P11"],
-            MajorThirteenth => vec!["P1", "M3", "P5", "M7", "M9", "P11", "M13"],
-            MajorSixth => vec!["P1", "M3", "P5", "M6"],
-            SixthNinth => vec!["P1", "M3", "P5", "M6", "M9"],
-            DominantSeventh => vec!["P1", "M3", "P5", "m7"],
-            DominantNinth => vec!["P1", "M3", "P5", "m7", "M9"],
-            DominantEleventh => vec!["P1", "M3", "P5", "m7", "M9", "P11"],
-            DominantThirteenth => vec!["P1", "M3", "P5", "m7", "M9", "P11", "M13"],
-            DominantSeventhFlatNinth => vec!["P1", "M3", "P5", "m7", "m9"],
-            DominantSeventhSharpNinth => vec!["P1", "M3", "P5", "m7", "A9"],
-            DominantSeventhFlatFifth => vec!["P1", "M3", "d5", "m7"],
-            //DominantSeventhSharpFifth => vec!["P1", "M3", "A5", "m7"],
-            SuspendedFourth => vec!["P1", "P4", "P5"],
-            SuspendedSecond => vec!["P1", "M2", "P5"],
-            DominantSeventhSuspendedFourth => vec!["P1", "P4", "P5", "m7"],
-            DominantSeventhSuspendedSecond => vec!["P1", "M2", "P5", "m7"],
-            Minor => vec!["P1", "m3", "P5"],
-            MinorSeventh => vec!["P1", "m3", "P5", "m7"],
-            MinorMajorSeventh => vec!["P1", "m3", "P5", "M7"],
-            MinorSixth => vec!["P1", "m3", "P5", "M6"],
-            MinorNinth => vec!["P1", "m3", "P5", "m7", "M9"],
-            MinorEleventh => vec!["P1", "m3", "P5", "m7", "M9", "P11"],
-            MinorThirteenth => vec!["P1", "m3", "P5", "m7", "M9", "P11", "M13"],
-            Diminished => vec!["P1", "m3", "d5"],
-            DiminishedSeventh => vec!["P1", "m3", "d5", "d7"],
-            HalfDiminishedSeventh => vec!["P1", "m3", "d5", "m7"],
-            Fifth => vec!["P1", "P5"],
-            Augmented => vec!["P1", "M3", "A5"],
-            AugmentedSeventh => vec!["P1", "M3", "A5", "m7"],
-            AugmentedMajorSeventh => vec!["P1", "M3", "A5", "M7"],
-            AddedNinth => vec!["P1", "M3", "P5", "M9"],
-            AddedFourth => vec!["P1", "M3", "P4", "P5"],
+        let intervals: &'static [Interval] = match self {
+            Self::Major => &[PerfectUnison, MajorThird, PerfectFifth],
+            Self::MajorSeventh => &[PerfectUnison, MajorThird, PerfectFifth, MajorSeventh],
+            Self::MajorNinth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MajorSeventh,
+                MajorNinth,
+            ],
+            Self::MajorEleventh => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MajorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+            ],
+            Self::MajorThirteenth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MajorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+                MajorThirteenth,
+            ],
+            Self::MajorSixth => &[PerfectUnison, MajorThird, PerfectFifth, MajorSixth],
+            Self::SixthNinth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MajorSixth,
+                MajorNinth,
+            ],
+            Self::DominantSeventh => &[PerfectUnison, MajorThird, PerfectFifth, MinorSeventh],
+            Self::DominantNinth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+            ],
+            Self::DominantEleventh => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+            ],
+            Self::DominantThirteenth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+                MajorThirteenth,
+            ],
+            Self::DominantSeventhFlatNinth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MinorNinth,
+            ],
+            Self::DominantSeventhSharpNinth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                AugmentedNinth,
+            ],
+            Self::DominantSeventhFlatFifth => {
+                &[PerfectUnison, MajorThird, DiminishedFifth, MinorSeventh]
+            }
+            //Self::DominantSeventhSharpFifth => &[PerfectUnison, MajorThird, AugmentedFifth, MinorSeventh],
+            Self::DominantSeventhSharpEleventh => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                AugmentedEleventh,
+            ],
+            Self::DominantSeventhFlatThirteenth => &[
+                PerfectUnison,
+                MajorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MinorThirteenth,
+            ],
+            Self::AlteredDominantSeventh => &[
+                PerfectUnison,
+                MajorThird,
+                DiminishedFifth,
+                AugmentedFifth,
+                MinorSeventh,
+                MinorNinth,
+                AugmentedNinth,
+            ],
+            Self::SuspendedFourth => &[PerfectUnison, PerfectFourth, PerfectFifth],
+            Self::SuspendedSecond => &[PerfectUnison, MajorSecond, PerfectFifth],
+            Self::DominantSeventhSuspendedFourth => {
+                &[PerfectUnison, PerfectFourth, PerfectFifth, MinorSeventh]
+            }
+            Self::DominantSeventhSuspendedSecond => {
+                &[PerfectUnison, MajorSecond, PerfectFifth, MinorSeventh]
+            }
+            Self::Minor => &[PerfectUnison, MinorThird, PerfectFifth],
+            Self::MinorSeventh => &[PerfectUnison, MinorThird, PerfectFifth, MinorSeventh],
+            Self::MinorMajorSeventh => &[PerfectUnison, MinorThird, PerfectFifth, MajorSeventh],
+            Self::MinorSixth => &[PerfectUnison, MinorThird, PerfectFifth, MajorSixth],
+            Self::MinorNinth => &[
+                PerfectUnison,
+                MinorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+            ],
+            Self::MinorEleventh => &[
+                PerfectUnison,
+                MinorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+            ],
+            Self::MinorThirteenth => &[
+                PerfectUnison,
+                MinorThird,
+                PerfectFifth,
+                MinorSeventh,
+                MajorNinth,
+                PerfectEleventh,
+                MajorThirteenth,
+            ],
+            Self::Diminished => &[PerfectUnison, MinorThird, DiminishedFifth],
+            Self::DiminishedSeventh => &[
+                PerfectUnison,
+                MinorThird,
+                DiminishedFifth,
+                DiminishedSeventh,
+            ],
+            Self::HalfDiminishedSeventh => {
+                &[PerfectUnison, MinorThird, DiminishedFifth, MinorSeventh]
+            }
+            Self::Fifth => &[PerfectUnison, PerfectFifth],
+            Self::Augmented => &[PerfectUnison, MajorThird, AugmentedFifth],
+            Self::AugmentedSeventh => &[PerfectUnison, MajorThird, AugmentedFifth, MinorSeventh],
+            Self::AugmentedMajorSeventh => {
+                &[PerfectUnison, MajorThird, AugmentedFifth, MajorSeventh]
+            }
+            Self::AddedNinth => &[PerfectUnison, MajorThird, PerfectFifth, MajorNinth],
+            Self::AddedFourth => &[PerfectUnison, MajorThird, PerfectFourth, PerfectFifth],
+            Self::AddedEleventh => &[PerfectUnison, MajorThird, PerfectFifth, PerfectEleventh],
+            Self::AddedThirteenth => &[PerfectUnison, MajorThird, PerfectFifth, MajorThirteenth],
         };
 
-        interval_names
-            .into_iter()
-            .map(|s| Interval::from_str(s).unwrap())
+        intervals.iter().copied()
     }
 
     /// Return an iterator over the chord type's optional intervals.
     pub fn optional_intervals(&self) -> impl Iterator<Item = Interval> + '_ {
-        use ChordType::*;
+        use Interval::*;
 
-        let interval_names = match self {
-            MajorSeventh
-            | MajorNinth
-            | MajorSixth
-            | SixthNinth
-            | DominantSeventh
-            | DominantNinth
-            | DominantSeventhFlatNinth
-            | DominantSeventhSharpNinth
-            | SuspendedFourth
-            | SuspendedSecond
-            | DominantSeventhSuspendedFourth
-            | DominantSeventhSuspendedSecond
-            | MinorSeventh
-            | MinorMajorSeventh
-            | MinorSixth
-            | MinorNinth
-            | AddedNinth
-            | AddedFourth => vec!["P5"],
-            MajorEleventh | DominantEleventh | MinorEleventh => vec!["P5", "M9"],
-            MajorThirteenth | DominantThirteenth | MinorThirteenth => vec!["P5", "M9", "P11"],
-            _ => vec![],
+        let intervals: &'static [Interval] = match self {
+            Self::MajorSeventh
+            | Self::MajorNinth
+            | Self::MajorSixth
+            | Self::SixthNinth
+            | Self::DominantSeventh
+            | Self::DominantNinth
+            | Self::DominantSeventhFlatNinth
+            | Self::DominantSeventhSharpNinth
+            | Self::DominantSeventhSharpEleventh
+            | Self::DominantSeventhFlatThirteenth
+            | Self::SuspendedFourth
+            | Self::SuspendedSecond
+            | Self::DominantSeventhSuspendedFourth
+            | Self::DominantSeventhSuspendedSecond
+            | Self::MinorSeventh
+            | Self::MinorMajorSeventh
+            | Self::MinorSixth
+            | Self::MinorNinth
+            | Self::AddedNinth
+            | Self::AddedFourth
+            | Self::AddedEleventh
+            | Self::AddedThirteenth => &[PerfectFifth],
+            Self::MajorEleventh | Self::DominantEleventh | Self::MinorEleventh => {
+                &[PerfectFifth, MajorNinth]
+            }
+            Self::MajorThirteenth | Self::DominantThirteenth | Self::MinorThirteenth => {
+                &[PerfectFifth, MajorNinth, PerfectEleventh]
+            }
+            Self::AlteredDominantSeventh => &[DiminishedFifth, AugmentedFifth, MinorNinth],
+            _ => &[],
         };
 
-        interval_names
-            .into_iter()
-            .map(|s| Interval::from_str(s).unwrap())
+        intervals.iter().copied()
     }
 
     /// Return an iterator over the chord type's required intervals.
@@ -192,6 +362,101 @@ impl ChordType {
             .filter(move |&i1| self.optional_intervals().all(|i2| i2 != i1))
     }
 
+    /// Return `true` if the chord type contains `interval`.
+    fn has(&self, interval: Interval) -> bool {
+        self.intervals().any(|i| i == interval)
+    }
+
+    /// Return `true` if the chord type is built on a major triad and has
+    /// no minor seventh, e.g. `Major`, `MajorSeventh` or `AddedNinth`.
+    pub fn is_major(&self) -> bool {
+        self.has(Interval::MajorThird)
+            && self.has(Interval::PerfectFifth)
+            && !self.has(Interval::MinorSeventh)
+    }
+
+    /// Return `true` if the chord type is built on a minor triad,
+    /// e.g. `Minor`, `MinorSeventh` or `MinorMajorSeventh`.
+    pub fn is_minor(&self) -> bool {
+        self.has(Interval::MinorThird) && self.has(Interval::PerfectFifth)
+    }
+
+    /// Return `true` if the chord type has a major third and a minor seventh,
+    /// e.g. `DominantSeventh`, `DominantNinth` or `AugmentedSeventh`.
+    pub fn is_dominant(&self) -> bool {
+        self.has(Interval::MajorThird) && self.has(Interval::MinorSeventh)
+    }
+
+    /// Return `true` if the chord type is built on a diminished triad,
+    /// e.g. `Diminished`, `DiminishedSeventh` or `HalfDiminishedSeventh`.
+    pub fn is_diminished(&self) -> bool {
+        self.has(Interval::MinorThird) && self.has(Interval::DiminishedFifth)
+    }
+
+    /// Return `true` if the chord type contains a seventh of any kind.
+    pub fn has_seventh(&self) -> bool {
+        self.intervals().any(|i| i.to_number() == 7)
+    }
+
+    /// Return the highest degree stacked on top of the seventh of the chord
+    /// type, i.e. 7, 9, 11 or 13 (e.g. 9 for `MajorNinth` or `DominantSeventhFlatNinth`).
+    ///
+    /// Return `None` if the chord type has no seventh, e.g. for triads, sixth
+    /// chords and added tone chords such as `AddedNinth`.
+    pub fn extension_degree(&self) -> Option<u8> {
+        match self.has_seventh() {
+            true => self.intervals().map(|i| i.to_number()).max(),
+            false => None,
+        }
+    }
+
+    /// Return the number of the highest interval of the chord type, i.e. how far
+    /// the chord is stacked up: 5 for triads (as well as power chords and added
+    /// fourth chords), 6 for sixth chords, 7 for seventh chords and 9, 11 or 13
+    /// for extended chords (including added tone chords such as `AddedNinth` and
+    /// altered chords such as `DominantSeventhFlatNinth`).
+    pub fn stack_height(&self) -> u8 {
+        self.intervals().map(|i| i.to_number()).max().unwrap_or(1)
+    }
+
+    /// Return the name of the group of chord types with the same stack height
+    /// (see `stack_height`), e.g. "Seventh chords".
+    pub fn group_name(&self) -> &'static str {
+        match self.stack_height() {
+            5 => "Triads, power chords and added 4th chords",
+            6 => "Sixth chords",
+            7 => "Seventh chords",
+            9 => "Ninth chords",
+            11 => "Eleventh chords",
+            13 => "Thirteenth chords",
+            _ => unreachable!(),
+        }
+    }
+
+    /// Return a representative difficulty of playing chords of this type,
+    /// i.e. the average difficulty (see `Voicing::difficulty`) of the easiest
+    /// voicing of the chord on each of the twelve roots.
+    ///
+    /// Roots on which the chord cannot be played with the given tuning are skipped.
+    /// Return `None` if the chord cannot be played on any root.
+    #[cfg(feature = "std")]
+    pub fn difficulty(self, tuning: Tuning) -> Option<f64> {
+        let config = VoicingConfig {
+            tuning,
+            ..Default::default()
+        };
+
+        let difficulties: Vec<u32> = (0..PITCH_CLASS_COUNT)
+            .map(|pc| Chord::new(PitchClass::from(pc).into(), self))
+            .filter_map(|chord| chord.best_voicing(config).map(|v| v.difficulty()))
+            .collect();
+
+        match difficulties.len() {
+            0 => None,
+            n => Some(difficulties.iter().sum::<u32>() as f64 / n as f64),
+        }
+    }
+
     /// Return an iterator over the symbols that can be used to denote a chord type.
     pub fn symbols(self) -> impl Iterator<Item = &'static str> + 'static {
         use ChordType::*;
@@ -212,6 +477,9 @@ impl ChordType {
             DominantSeventhSharpNinth => vec!["7#9"],
             DominantSeventhFlatFifth => vec!["7b5", "7dim5"],
             //DominantSeventhSharpFifth => "7#5",
+            DominantSeventhSharpEleventh => vec!["7#11"],
+            DominantSeventhFlatThirteenth => vec!["7b13"],
+            AlteredDominantSeventh => vec!["7alt", "alt"],
             SuspendedFourth => vec!["sus4", "sus"],
             SuspendedSecond => vec!["sus2"],
             DominantSeventhSuspendedFourth => vec!["7sus4", "7sus"],
@@ -232,6 +500,8 @@ impl ChordType {
             AugmentedMajorSeventh => vec!["augMaj7", "+M7"],
             AddedNinth => vec!["add9", "add2"],
             AddedFourth => vec!["add4"],
+            AddedEleventh => vec!["add11"],
+            AddedThirteenth => vec!["add13"],
         };
 
         symbols.into_iter()
@@ -240,6 +510,85 @@ impl ChordType {
     pub fn to_symbol(self) -> String {
         self.symbols().next().unwrap().to_string()
     }
+
+    /// Return the number of required intervals of the chord type that are missing
+    /// from the pitch classes (the first of which is taken as the root).
+    ///
+    /// Return `None` if the pitch classes do not fit the chord type at all, i.e.
+    /// if they contain a note that is not part of the chord or if there are
+    /// fewer notes than needed to identify the chord.
+    pub fn missing_intervals(&self, pitches: &[PitchClass]) -> Option<usize> {
+        // Subtract the root note's pitch class from all pitch classes to get the
+        // difference in semitones.
+        let mut pitch_diffs: Vec<_> = pitches.iter().map(|pc| *pc - pitches[0]).collect();
+
+        pitch_diffs.sort_unstable();
+
+        let to_semitones = |i: Interval| {
+            let s = i.to_semitones();
+            if s >= PITCH_CLASS_COUNT {
+                s - PITCH_CLASS_COUNT
+            } else {
+                s
+            }
+        };
+
+        // If a chord has less required intervals than we have strings, add optional intervals
+        // until all strings are used.
+        let min_len = min(self.intervals().count(), STRING_COUNT);
+
+        if pitch_diffs.len() < min_len {
+            return None;
+        }
+
+        let req_sems: Vec<_> = self.required_intervals().map(to_semitones).collect();
+        let missing = req_sems.iter().filter(|s| !pitch_diffs.contains(s)).count();
+
+        // The remaining semitones must all correspond to optional intervals from the chord.
+        let opt_sems: Vec<_> = self.optional_intervals().map(to_semitones).collect();
+
+        let mut opt = pitch_diffs.iter().filter(|s| !req_sems.contains(s));
+
+        match opt.all(|s| opt_sems.contains(s)) {
+            true => Some(missing),
+            false => None,
+        }
+    }
+
+    /// Return the chord type consisting of exactly the given intervals above
+    /// the root (in any order), e.g. `DominantSeventh` for `P1 M3 P5 m7`.
+    /// This is the inverse of `intervals`.
+    ///
+    /// Return `None` if no chord type matches the intervals.
+    pub fn from_intervals(intervals: &[Interval]) -> Option<Self> {
+        let mut intervals = intervals.to_vec();
+        intervals.sort();
+        intervals.dedup();
+
+        Self::values().find(|chord_type| {
+            chord_type.intervals().count() == intervals.len()
+                && chord_type.intervals().all(|i| intervals.contains(&i))
+        })
+    }
+
+    /// Return all chord types that the pitch classes (the first of which is taken
+    /// as the root) may represent, ranked by the number of missing required intervals.
+    ///
+    /// As only four strings sound, extended chords can often not be played
+    /// completely, e.g. `C B D A` is a Cmaj13 chord lacking its third.
+    /// Exact matches come first, chord types with the same number of missing
+    /// intervals are listed in the usual order.
+    pub fn candidates(pitches: &[PitchClass]) -> Vec<Self> {
+        Self::values()
+            .filter_map(|chord_type| {
+                chord_type
+                    .missing_intervals(pitches)
+                    .map(|missing| (missing, chord_type))
+            })
+            .sorted_by_key(|(missing, _)| *missing)
+            .map(|(_, chord_type)| chord_type)
+            .collect()
+    }
 }
 
 impl fmt::Display for ChordType {
@@ -262,6 +611,9 @@ impl fmt::Display for ChordType {
             DominantSeventhSharpNinth => "dominant 7th sharp 9th",
             DominantSeventhFlatFifth => "dominant 7th flat 5th",
             //DominantSeventhSharpFifth => "dominant 7th sharp 5th",
+            DominantSeventhSharpEleventh => "dominant 7th sharp 11th",
+            DominantSeventhFlatThirteenth => "dominant 7th flat 13th",
+            AlteredDominantSeventh => "altered dominant 7th",
             SuspendedFourth => "suspended 4th",
             SuspendedSecond => "suspended 2nd",
             DominantSeventhSuspendedFourth => "dominant 7th suspended 4th",
@@ -282,6 +634,8 @@ impl fmt::Display for ChordType {
             AugmentedMajorSeventh => "augmented major 7th",
             AddedNinth => "added 9th",
             AddedFourth => "added 4th",
+            AddedEleventh => "added 11th",
+            AddedThirteenth => "added 13th",
         };
 
         write!(f, "{s}")
@@ -294,66 +648,46 @@ pub struct NoValidChordTypeError;
 impl FromStr for ChordType {
     type Err = NoValidChordTypeError;
 
+    /// Parse a chord type symbol such as `m7` or `maj7`.
+    ///
+    /// If no symbol matches exactly, the capitalization is ignored (e.g. `MAJ7`
+    /// or `Sus4`) as long as this does not make the symbol ambiguous. `M7` and
+    /// `m7`, for example, keep denoting different chord types.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::values()
-            .find(|ct| ct.symbols().any(|sym| sym == s))
-            .ok_or(NoValidChordTypeError)
+        if let Some(chord_type) = Self::values().find(|ct| ct.symbols().any(|sym| sym == s)) {
+            return Ok(chord_type);
+        }
+
+        let mut matches =
+            Self::values().filter(|ct| ct.symbols().any(|sym| sym.eq_ignore_ascii_case(s)));
+
+        match (matches.next(), matches.next()) {
+            (Some(chord_type), None) => Ok(chord_type),
+            _ => Err(NoValidChordTypeError),
+        }
     }
 }
 
-#[derive(Debug, thiserror::Error)]
-#[error("no matching chord type found")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NoMatchingChordTypeFoundError;
 
+impl fmt::Display for NoMatchingChordTypeFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no matching chord type found")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoMatchingChordTypeFoundError {}
+
 impl TryFrom<&[PitchClass]> for ChordType {
     type Error = NoMatchingChordTypeFoundError;
 
     /// Determine the chord type from a list of pitch classes representing a chord.
     fn try_from(pitches: &[PitchClass]) -> Result<Self, Self::Error> {
-        // Subtract the root note's pitch class from all pitch classes to get the
-        // difference in semitones.
-        let mut pitch_diffs: Vec<_> = pitches.iter().map(|pc| *pc - pitches[0]).collect();
-
-        pitch_diffs.sort_unstable();
-
-        let to_semitones = |i: Interval| {
-            let s = i.to_semitones();
-            if s >= PITCH_CLASS_COUNT {
-                s - PITCH_CLASS_COUNT
-            } else {
-                s
-            }
-        };
-
-        for chord_type in Self::values() {
-            // If a chord has less required intervals than we have strings, add optional intervals
-            // until all strings are used.
-            let min_len = min(chord_type.intervals().count(), STRING_COUNT);
-
-            if pitch_diffs.len() < min_len {
-                continue;
-            }
-
-            // All the required intervals need to be there.
-            let req_sems: Vec<_> = chord_type.required_intervals().map(to_semitones).collect();
-
-            let req = pitch_diffs.iter().filter(|s| req_sems.contains(s));
-
-            if req.count() != req_sems.len() {
-                continue;
-            }
-
-            // The remaining semitones must all correspond to optional intervals from the chord.
-            let opt_sems: Vec<_> = chord_type.optional_intervals().map(to_semitones).collect();
-
-            let mut opt = pitch_diffs.iter().filter(|s| !req_sems.contains(s));
-
-            if opt.all(|s| opt_sems.contains(s)) {
-                return Ok(chord_type);
-            }
-        }
-
-        Err(NoMatchingChordTypeFoundError)
+        Self::values()
+            .find(|chord_type| chord_type.missing_intervals(pitches) == Some(0))
+            .ok_or(NoMatchingChordTypeFoundError)
     }
 }
 
@@ -392,6 +726,11 @@ mod tests {
         case(vec![C, E, G, ASharp, DSharp], DominantSeventhSharpNinth),
         case(vec![C, E, ASharp, DSharp], DominantSeventhSharpNinth),
         case(vec![C, E, FSharp, ASharp], DominantSeventhFlatFifth),
+        case(vec![C, E, G, ASharp, FSharp], DominantSeventhSharpEleventh),
+        case(vec![C, E, ASharp, FSharp, G], DominantSeventhSharpEleventh),
+        case(vec![C, E, G, ASharp, GSharp], DominantSeventhFlatThirteenth),
+        case(vec![C, E, ASharp, DSharp, GSharp], AlteredDominantSeventh),
+        case(vec![C, E, FSharp, ASharp, CSharp, DSharp], AlteredDominantSeventh),
         //case(vec![C, E, GSharp, ASharp], DominantSeventhSharpFifth),
         case(vec![C, F, G], SuspendedFourth),
         case(vec![C, D, G], SuspendedSecond),
@@ -417,6 +756,8 @@ mod tests {
         case(vec![C, E, G, D], AddedNinth),
         case(vec![C, D, E, G], AddedNinth),
         case(vec![C, E, F, G], AddedFourth),
+        case(vec![C, E, G, F], AddedFourth),
+        case(vec![C, E, G, A], MajorSixth),
         // Test some chords with other root notes.
         case(vec![D, FSharp, A], Major),
         case(vec![D, F, A], Minor),
@@ -439,6 +780,64 @@ mod tests {
         assert!(ChordType::try_from(&pitches[..]).is_err());
     }
 
+    #[rstest(
+        pitches, chord_types,
+        case(vec![C, E, G, B], vec![MajorSeventh, MajorNinth, MajorEleventh, MajorThirteenth]),
+        // Cmaj9 without its third.
+        case(vec![C, G, B, D], vec![MajorNinth, MajorEleventh, MajorThirteenth]),
+        // Cmaj13 without its third.
+        case(vec![C, B, D, A], vec![MajorThirteenth]),
+        case(vec![C, CSharp, D], vec![]),
+        // The added 11th and 13th share their pitch classes with the added 4th
+        // and the major 6th, which are preferred.
+        case(
+            vec![C, E, G, F],
+            vec![AddedFourth, AddedEleventh, MajorEleventh, DominantEleventh, MajorThirteenth, DominantThirteenth]
+        ),
+        case(
+            vec![C, E, G, A],
+            vec![MajorSixth, AddedThirteenth, MajorThirteenth, SixthNinth, DominantThirteenth]
+        ),
+    )]
+    fn test_candidates(pitches: Vec<PitchClass>, chord_types: Vec<ChordType>) {
+        assert_eq!(ChordType::candidates(&pitches), chord_types);
+    }
+
+    #[rstest(
+        intervals, chord_type,
+        case(vec!["P1", "M3", "P5"], Some(Major)),
+        case(vec!["P1", "M3", "P5", "m7"], Some(DominantSeventh)),
+        case(vec!["m7", "P5", "M3", "P1"], Some(DominantSeventh)),
+        case(vec!["P1", "M3", "P5", "m7", "P5"], Some(DominantSeventh)),
+        case(vec!["P1", "m3", "d5", "d7"], Some(DiminishedSeventh)),
+        case(vec!["P1", "M3", "A5"], Some(Augmented)),
+        // A minor sixth is not an augmented fifth.
+        case(vec!["P1", "M3", "m6"], None),
+        // Unlike pitch classes, intervals tell the added 11th and 13th apart.
+        case(vec!["P1", "M3", "P5", "P4"], Some(AddedFourth)),
+        case(vec!["P1", "M3", "P5", "P11"], Some(AddedEleventh)),
+        case(vec!["P1", "M3", "P5", "M6"], Some(MajorSixth)),
+        case(vec!["P1", "M3", "P5", "M13"], Some(AddedThirteenth)),
+        case(vec!["P1", "M3"], None),
+        case(vec!["P1", "m2", "M2"], None),
+        case(vec![], None),
+    )]
+    fn test_from_intervals(intervals: Vec<&str>, chord_type: Option<ChordType>) {
+        let intervals: Vec<Interval> = intervals
+            .iter()
+            .map(|s| Interval::from_str(s).unwrap())
+            .collect();
+        assert_eq!(ChordType::from_intervals(&intervals), chord_type);
+    }
+
+    #[test]
+    fn test_from_intervals_inverse() {
+        for chord_type in ChordType::values() {
+            let intervals: Vec<Interval> = chord_type.intervals().collect();
+            assert_eq!(ChordType::from_intervals(&intervals), Some(chord_type));
+        }
+    }
+
     #[rstest(
         chord_type, intervals,
         case(Major, vec!["P1", "M3", "P5"]),
@@ -454,4 +853,208 @@ mod tests {
 
         assert_eq!(req_ints, exp_ints);
     }
+
+    #[rstest(
+        chord_type,
+        is_major,
+        is_minor,
+        is_dominant,
+        is_diminished,
+        case(Major, true, false, false, false),
+        case(MajorSeventh, true, false, false, false),
+        case(MajorNinth, true, false, false, false),
+        case(MajorSixth, true, false, false, false),
+        case(SixthNinth, true, false, false, false),
+        case(AddedNinth, true, false, false, false),
+        case(DominantSeventh, false, false, true, false),
+        case(DominantThirteenth, false, false, true, false),
+        case(DominantSeventhFlatFifth, false, false, true, false),
+        case(AlteredDominantSeventh, false, false, true, false),
+        case(AugmentedSeventh, false, false, true, false),
+        case(Minor, false, true, false, false),
+        case(MinorSeventh, false, true, false, false),
+        case(MinorMajorSeventh, false, true, false, false),
+        case(MinorSixth, false, true, false, false),
+        case(MinorThirteenth, false, true, false, false),
+        case(Diminished, false, false, false, true),
+        case(DiminishedSeventh, false, false, false, true),
+        case(HalfDiminishedSeventh, false, false, false, true),
+        case(SuspendedFourth, false, false, false, false),
+        case(DominantSeventhSuspendedFourth, false, false, false, false),
+        case(Fifth, false, false, false, false),
+        case(Augmented, false, false, false, false),
+        case(AugmentedMajorSeventh, false, false, false, false)
+    )]
+    fn test_quality(
+        chord_type: ChordType,
+        is_major: bool,
+        is_minor: bool,
+        is_dominant: bool,
+        is_diminished: bool,
+    ) {
+        assert_eq!(chord_type.is_major(), is_major);
+        assert_eq!(chord_type.is_minor(), is_minor);
+        assert_eq!(chord_type.is_dominant(), is_dominant);
+        assert_eq!(chord_type.is_diminished(), is_diminished);
+    }
+
+    #[test]
+    fn test_quality_exclusive() {
+        for chord_type in ChordType::values() {
+            let qualities = [
+                chord_type.is_major(),
+                chord_type.is_minor(),
+                chord_type.is_dominant(),
+                chord_type.is_diminished(),
+            ];
+            assert!(qualities.iter().filter(|&&q| q).count() <= 1);
+        }
+    }
+
+    #[rstest(
+        chord_type,
+        has_seventh,
+        extension_degree,
+        case(Major, false, None),
+        case(MajorSixth, false, None),
+        case(SixthNinth, false, None),
+        case(AddedNinth, false, None),
+        case(AddedThirteenth, false, None),
+        case(Diminished, false, None),
+        case(MajorSeventh, true, Some(7)),
+        case(DominantSeventh, true, Some(7)),
+        case(DiminishedSeventh, true, Some(7)),
+        case(HalfDiminishedSeventh, true, Some(7)),
+        case(DominantSeventhSuspendedSecond, true, Some(7)),
+        case(MajorNinth, true, Some(9)),
+        case(MinorNinth, true, Some(9)),
+        case(DominantSeventhFlatNinth, true, Some(9)),
+        case(AlteredDominantSeventh, true, Some(9)),
+        case(DominantEleventh, true, Some(11)),
+        case(DominantSeventhSharpEleventh, true, Some(11)),
+        case(MajorThirteenth, true, Some(13)),
+        case(DominantSeventhFlatThirteenth, true, Some(13))
+    )]
+    fn test_extension_degree(
+        chord_type: ChordType,
+        has_seventh: bool,
+        extension_degree: Option<u8>,
+    ) {
+        assert_eq!(chord_type.has_seventh(), has_seventh);
+        assert_eq!(chord_type.extension_degree(), extension_degree);
+    }
+
+    #[rstest(
+        s,
+        chord_type,
+        // Exact matches keep the distinction between M (major) and m (minor).
+        case("M7", MajorSeventh),
+        case("m7", MinorSeventh),
+        case("mM7", MinorMajorSeventh),
+        // Otherwise, the capitalization is ignored.
+        case("MAJ7", MajorSeventh),
+        case("Maj7", MajorSeventh),
+        case("SUS4", SuspendedFourth),
+        case("Dim7", DiminishedSeventh),
+        case("MMAJ7", MinorMajorSeventh)
+    )]
+    fn test_from_str_capitalization(s: &str, chord_type: ChordType) {
+        assert_eq!(ChordType::from_str(s).unwrap(), chord_type);
+    }
+
+    #[rstest(s, case("x"), case("MAJ8"), case("mm"))]
+    fn test_from_str_fail(s: &str) {
+        assert!(ChordType::from_str(s).is_err());
+    }
+
+    #[rstest(
+        chord_type,
+        stack_height,
+        case(ChordType::Major, 5),
+        case(ChordType::Fifth, 5),
+        case(ChordType::SuspendedSecond, 5),
+        case(ChordType::AddedFourth, 5),
+        case(ChordType::MinorSixth, 6),
+        case(ChordType::SixthNinth, 9),
+        case(ChordType::DominantSeventh, 7),
+        case(ChordType::DominantSeventhFlatFifth, 7),
+        case(ChordType::DiminishedSeventh, 7),
+        case(ChordType::MajorNinth, 9),
+        case(ChordType::AddedNinth, 9),
+        case(ChordType::AlteredDominantSeventh, 9),
+        case(ChordType::DominantSeventhSharpEleventh, 11),
+        case(ChordType::MinorThirteenth, 13),
+        case(ChordType::DominantSeventhFlatThirteenth, 13)
+    )]
+    fn test_stack_height(chord_type: ChordType, stack_height: u8) {
+        assert_eq!(chord_type.stack_height(), stack_height);
+    }
+
+    #[rstest(
+        chord_type,
+        group_name,
+        case(Major, "Triads, power chords and added 4th chords"),
+        case(Fifth, "Triads, power chords and added 4th chords"),
+        case(MinorSixth, "Sixth chords"),
+        case(HalfDiminishedSeventh, "Seventh chords"),
+        case(AddedNinth, "Ninth chords"),
+        case(DominantEleventh, "Eleventh chords"),
+        case(MajorThirteenth, "Thirteenth chords")
+    )]
+    fn test_group_name(chord_type: ChordType, group_name: &str) {
+        assert_eq!(chord_type.group_name(), group_name);
+    }
+
+    #[test]
+    fn test_group_name_all() {
+        // Every chord type belongs to one of the groups.
+        for chord_type in ChordType::values() {
+            chord_type.group_name();
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_difficulty() {
+        let major = Major.difficulty(Tuning::C).unwrap();
+        let thirteenth = DominantThirteenth.difficulty(Tuning::C).unwrap();
+        assert!(major > 0.0);
+        assert!(major < thirteenth);
+    }
+
+    #[test]
+    fn test_values_by_stack_height() {
+        let chord_types = ChordType::values_by_stack_height().collect_vec();
+        assert_eq!(chord_types.len(), ChordType::values().count());
+
+        // Triads precede sevenths, which precede ninths.
+        let position = |chord_type| chord_types.iter().position(|&c| c == chord_type).unwrap();
+        for triad in [ChordType::Major, ChordType::Minor, ChordType::Augmented] {
+            assert!(position(triad) < position(ChordType::DominantSeventh));
+        }
+        assert!(position(ChordType::MajorSixth) < position(ChordType::MajorSeventh));
+        assert!(position(ChordType::HalfDiminishedSeventh) < position(ChordType::DominantNinth));
+        assert!(position(ChordType::DominantNinth) < position(ChordType::MinorEleventh));
+
+        assert!(chord_types
+            .iter()
+            .tuple_windows()
+            .all(|(c1, c2)| c1.stack_height() <= c2.stack_height()));
+        assert!(chord_types
+            .iter()
+            .all(|c| [5, 6, 7, 9, 11, 13].contains(&c.stack_height())));
+
+        // Within a group, the order of `values` is kept.
+        assert_eq!(chord_types[0], ChordType::Major);
+        assert_eq!(
+            chord_types
+                .iter()
+                .filter(|c| c.stack_height() == 7)
+                .copied()
+                .collect_vec(),
+            ChordType::values()
+                .filter(|c| c.stack_height() == 7)
+                .collect_vec()
+        );
+    }
 }
@@ -1,29 +1,99 @@
-use std::{iter::Sum, ops::Add};
+use std::{fmt, iter::Sum, ops::Add};
+
+use crate::{FretID, Voicing};
+
+/// Coefficients used to combine the different aspects of moving from one
+/// voicing to another into a single cost when searching for the best voice leading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DistanceWeights {
+    /// Weight of the number of frets the fingers move along the strings.
+    pub fret_movement: u32,
+    /// Weight of the fingering distance, i.e. how far the fingers move
+    /// (including changes of strings).
+    pub fingering: u32,
+    /// Weight of the height of the target voicing on the neck
+    /// (its highest fret).
+    pub position: u32,
+}
+
+/// By default, only the movement along the strings is taken into account.
+impl Default for DistanceWeights {
+    fn default() -> Self {
+        Self {
+            fret_movement: 1,
+            fingering: 0,
+            position: 0,
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 /// The distance between two voicings combining semitone distance
 /// and fingering distance as a tuple.
-pub struct Distance(u8, u8);
+///
+/// Distances are compared by their weighted cost first. Ties are broken
+/// by semitone distance and then by fingering distance.
+pub struct Distance(u32, u8, u8);
 
 impl Distance {
     pub fn new(semitone_distance: u8, fingering_distance: u8) -> Self {
-        Self(semitone_distance, fingering_distance)
+        Self::weighted(
+            semitone_distance,
+            fingering_distance,
+            0,
+            DistanceWeights::default(),
+        )
     }
 
-    pub fn semitone_distance(&self) -> u8 {
+    /// Create a distance whose cost is computed from the given `weights`.
+    /// `position` is the highest fret of the voicing that is moved to.
+    pub fn weighted(
+        semitone_distance: u8,
+        fingering_distance: u8,
+        position: FretID,
+        weights: DistanceWeights,
+    ) -> Self {
+        let cost = weights.fret_movement * semitone_distance as u32
+            + weights.fingering * fingering_distance as u32
+            + weights.position * position as u32;
+
+        Self(cost, semitone_distance, fingering_distance)
+    }
+
+    /// Compute the distance of moving from voicing `from` to voicing `to`
+    /// with the default weights.
+    pub fn between(from: &Voicing, to: &Voicing) -> Self {
+        from.distance(to)
+    }
+
+    pub fn cost(&self) -> u32 {
         self.0
     }
 
-    pub fn fingering_distance(&self) -> u8 {
+    pub fn semitone_distance(&self) -> u8 {
         self.1
     }
+
+    pub fn fingering_distance(&self) -> u8 {
+        self.2
+    }
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "semitone distance: {}, fingering distance: {}",
+            self.1, self.2
+        )
+    }
 }
 
 impl Add for Distance {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        Self(self.0 + other.0, self.1 + other.1)
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
     }
 }
 
@@ -32,6 +102,64 @@ impl<'a> Sum<&'a Self> for Distance {
     where
         I: Iterator<Item = &'a Self>,
     {
-        iter.fold(Self(0, 0), |a, b| Self(a.0 + b.0, a.1 + b.1))
+        iter.fold(Self(0, 0, 0), |a, b| Self(a.0 + b.0, a.1 + b.1, a.2 + b.2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{Tuning, STRING_COUNT};
+
+    #[rstest(
+        frets1, frets2, semitones, fingering,
+        case([0, 0, 0, 3], [0, 0, 0, 3], 0, 0),
+        case([0, 0, 0, 3], [0, 0, 0, 2], 1, 2),
+        case([0, 0, 0, 3], [0, 2, 3, 2], 6, 3),
+    )]
+    fn test_between(
+        frets1: [FretID; STRING_COUNT],
+        frets2: [FretID; STRING_COUNT],
+        semitones: u8,
+        fingering: u8,
+    ) {
+        let voicing1 = Voicing::new(frets1, Tuning::C);
+        let voicing2 = Voicing::new(frets2, Tuning::C);
+        let distance = Distance::between(&voicing1, &voicing2);
+        assert_eq!(distance, Distance::new(semitones, fingering));
+        assert_eq!(distance.cost(), semitones as u32);
+    }
+
+    #[rstest(
+        semitones,
+        fingering,
+        position,
+        weights,
+        cost,
+        case(3, 2, 5, DistanceWeights::default(), 3),
+        case(3, 2, 5, DistanceWeights { fret_movement: 0, fingering: 1, position: 0 }, 2),
+        case(3, 2, 5, DistanceWeights { fret_movement: 1, fingering: 1, position: 2 }, 15),
+    )]
+    fn test_weighted(
+        semitones: u8,
+        fingering: u8,
+        position: FretID,
+        weights: DistanceWeights,
+        cost: u32,
+    ) {
+        let distance = Distance::weighted(semitones, fingering, position, weights);
+        assert_eq!(distance.cost(), cost);
+        assert_eq!(distance.semitone_distance(), semitones);
+        assert_eq!(distance.fingering_distance(), fingering);
+    }
+
+    #[test]
+    fn test_default_order() {
+        // With the default weights, distances are ordered by semitone distance
+        // first and by fingering distance second.
+        assert!(Distance::new(3, 9) < Distance::new(4, 0));
+        assert!(Distance::new(3, 1) < Distance::new(3, 2));
     }
 }
@@ -0,0 +1,412 @@
+//! Export of chord voicings to file formats used by other music software.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::{Chord, ChordChart, Note, Octave, Voicing};
+
+/// Split a note into the components used by MusicXML to describe a pitch:
+/// the step (the note name without accidentals), the alteration in semitones
+/// and the octave.
+fn pitch(note: Note) -> (char, i8, Octave) {
+    let name = note.to_string();
+    let mut chars = name.chars();
+
+    let step = chars.next().unwrap();
+    let alter = chars
+        .map(|c| match c {
+            '#' => 1,
+            _ => -1,
+        })
+        .sum();
+
+    (step, alter, note.octave())
+}
+
+/// Serialize a sequence of chords with their voicings into a minimal MusicXML
+/// document (`<score-partwise>`).
+///
+/// Each chord is written into its own measure as a whole-note chord with one
+/// note per string. The chord symbol is added as a harmony element and each
+/// note is annotated with the string and the fret it is played on.
+pub fn to_musicxml<'a>(chords: impl IntoIterator<Item = (&'a Chord, &'a Voicing)>) -> String {
+    let mut xml = String::new();
+
+    xml.push_str(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#,
+        "\n",
+        r#"<!DOCTYPE score-partwise PUBLIC "-//Recordare//DTD MusicXML 4.0 Partwise//EN" "http://www.musicxml.org/dtds/partwise.dtd">"#,
+        "\n",
+        r#"<score-partwise version="4.0">"#,
+        "\n",
+        "  <part-list>\n",
+        r#"    <score-part id="P1">"#,
+        "\n",
+        "      <part-name>Ukulele</part-name>\n",
+        "    </score-part>\n",
+        "  </part-list>\n",
+        r#"  <part id="P1">"#,
+        "\n",
+    ));
+
+    for (i, (chord, voicing)) in chords.into_iter().enumerate() {
+        let _ = writeln!(xml, r#"    <measure number="{}">"#, i + 1);
+
+        if i == 0 {
+            xml.push_str(concat!(
+                "      <attributes>\n",
+                "        <divisions>1</divisions>\n",
+                "        <time>\n",
+                "          <beats>4</beats>\n",
+                "          <beat-type>4</beat-type>\n",
+                "        </time>\n",
+                "        <clef>\n",
+                "          <sign>G</sign>\n",
+                "          <line>2</line>\n",
+                "        </clef>\n",
+                "      </attributes>\n",
+            ));
+        }
+
+        // Chord symbol.
+        let (root_step, root_alter, _) = pitch(chord.root);
+        let symbol = chord.chord_type.to_symbol();
+        let _ = writeln!(xml, "      <harmony>");
+        let _ = writeln!(xml, "        <root>");
+        let _ = writeln!(xml, "          <root-step>{root_step}</root-step>");
+        if root_alter != 0 {
+            let _ = writeln!(xml, "          <root-alter>{root_alter}</root-alter>");
+        }
+        let _ = writeln!(xml, "        </root>");
+        let _ = writeln!(xml, r#"        <kind text="{symbol}">other</kind>"#);
+        if let Some(bass) = chord.bass {
+            let (bass_step, bass_alter, _) = pitch(bass);
+            let _ = writeln!(xml, "        <bass>");
+            let _ = writeln!(xml, "          <bass-step>{bass_step}</bass-step>");
+            if bass_alter != 0 {
+                let _ = writeln!(xml, "          <bass-alter>{bass_alter}</bass-alter>");
+            }
+            let _ = writeln!(xml, "        </bass>");
+        }
+        let _ = writeln!(xml, "      </harmony>");
+
+        let string_count = voicing.strings().len();
+
+        // Muted strings are left out.
+        let sounding_strings = voicing
+            .strings()
+            .enumerate()
+            .filter_map(|(i, uke_string)| uke_string.map(|us| (i, us)));
+
+        for (j, (i, (_root, fret, note))) in sounding_strings.enumerate() {
+            let (step, alter, octave) = pitch(*note);
+
+            let _ = writeln!(xml, "      <note>");
+            // All notes but the first one are part of the same chord.
+            if j > 0 {
+                let _ = writeln!(xml, "        <chord/>");
+            }
+            let _ = writeln!(xml, "        <pitch>");
+            let _ = writeln!(xml, "          <step>{step}</step>");
+            if alter != 0 {
+                let _ = writeln!(xml, "          <alter>{alter}</alter>");
+            }
+            let _ = writeln!(xml, "          <octave>{octave}</octave>");
+            let _ = writeln!(xml, "        </pitch>");
+            let _ = writeln!(xml, "        <duration>4</duration>");
+            let _ = writeln!(xml, "        <type>whole</type>");
+            let _ = writeln!(xml, "        <notations>");
+            let _ = writeln!(xml, "          <technical>");
+            // In MusicXML, strings are numbered starting from the highest one.
+            let _ = writeln!(xml, "            <string>{}</string>", string_count - i);
+            let _ = writeln!(xml, "            <fret>{fret}</fret>");
+            let _ = writeln!(xml, "          </technical>");
+            let _ = writeln!(xml, "        </notations>");
+            let _ = writeln!(xml, "      </note>");
+        }
+
+        let _ = writeln!(xml, "    </measure>");
+    }
+
+    xml.push_str("  </part>\n");
+    xml.push_str("</score-partwise>\n");
+
+    xml
+}
+
+/// Serialize a sequence of chords with their voicings into ChordPro chord
+/// definitions, one `{define: ...}` line per chord.
+///
+/// The frets are given relative to the base fret and in the string order of the
+/// tuning, open strings are marked by `0` and muted strings by `x`. The base
+/// fret and the fingers are the ones shown in the chord chart of the voicing.
+/// Repeated definitions are left out.
+pub fn to_chordpro<'a>(chords: impl IntoIterator<Item = (&'a Chord, &'a Voicing)>) -> String {
+    let mut chordpro = String::new();
+
+    let definitions = chords
+        .into_iter()
+        .map(|(chord, voicing)| {
+            let chart = ChordChart::new(chord, voicing.clone(), voicing.get_span());
+            let base_fret = chart.get_base_fret();
+
+            let frets = voicing
+                .fret_pattern()
+                .map(|fret| match fret {
+                    None => "x".to_string(),
+                    Some(0) => "0".to_string(),
+                    Some(fret) => (fret - base_fret + 1).to_string(),
+                })
+                .join(" ");
+            let fingers = chart.fingers().iter().join(" ");
+
+            format!(
+                "{{define: {} base-fret {base_fret} frets {frets} fingers {fingers}}}",
+                chord.name()
+            )
+        })
+        .unique();
+
+    for definition in definitions {
+        let _ = writeln!(chordpro, "{definition}");
+    }
+
+    chordpro
+}
+
+/// Serialize a sequence of chords with their voicings into ASCII tablature.
+///
+/// There is one line per string (with the highest string on top as in the
+/// chord charts) and one column per chord. The frets of each voicing are stacked
+/// vertically with the chord names above them, followed by their annotations
+/// in parentheses if any (e.g. `C(DUDU)`). Open strings are marked by `0`
+/// and muted strings by `x`. Each column is as wide as its widest entry.
+pub fn to_tab<'a>(
+    chords: impl IntoIterator<Item = (&'a Chord, Option<&'a str>, &'a Voicing)>,
+) -> String {
+    let mut roots: Vec<String> = vec![];
+
+    let columns: Vec<(String, Vec<String>)> = chords
+        .into_iter()
+        .map(|(chord, annotation, voicing)| {
+            if roots.is_empty() {
+                roots = voicing.roots().map(|root| root.to_string()).collect();
+            }
+
+            let frets = voicing
+                .fret_pattern()
+                .map(|fret| match fret {
+                    None => "x".to_string(),
+                    Some(fret) => fret.to_string(),
+                })
+                .collect();
+
+            let name = match annotation {
+                Some(annotation) => format!("{}({annotation})", chord.name()),
+                None => chord.name(),
+            };
+
+            (name, frets)
+        })
+        .collect();
+
+    let root_width = roots.iter().map(|root| root.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|(name, frets)| frets.iter().chain([name]).map(|s| s.len()).max().unwrap())
+        .collect();
+
+    let mut tab = String::new();
+
+    if columns.is_empty() {
+        return tab;
+    }
+
+    // Chord names.
+    let names = columns
+        .iter()
+        .zip(&widths)
+        .map(|((name, _frets), width)| format!("{name:width$}"))
+        .join("  ");
+    let _ = writeln!(tab, "{:root_width$}  {}", "", names.trim_end());
+
+    // One line per string, starting with the highest one.
+    for (i, root) in roots.iter().enumerate().rev() {
+        let frets = columns
+            .iter()
+            .zip(&widths)
+            .map(|((_name, frets), width)| format!("{:-<width$}", frets[i]))
+            .join("--");
+        let _ = writeln!(tab, "{root:root_width$}|-{frets}-|");
+    }
+
+    tab
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{FretID, Tuning, VoicingConfig};
+
+    #[rstest(
+        note,
+        octave,
+        step,
+        alter,
+        case("C", 4, 'C', 0),
+        case("C#", 4, 'C', 1),
+        case("Eb", 3, 'E', -1),
+        case("Bb", 4, 'B', -1),
+    )]
+    fn test_pitch(note: Note, octave: Octave, step: char, alter: i8) {
+        assert_eq!(pitch(note.with_octave(octave)), (step, alter, octave));
+    }
+
+    #[test]
+    fn test_to_musicxml() {
+        let chord = Chord::from_str("Bb").unwrap();
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        let xml = to_musicxml(vec![(&chord, &voicing)]);
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.ends_with("</score-partwise>\n"));
+        assert_eq!(xml.matches("<note>").count(), 4);
+        assert_eq!(xml.matches("<chord/>").count(), 3);
+        // Bb is spelled as a flat note.
+        assert!(xml.contains("<root-step>B</root-step>"));
+        assert!(xml.contains("<root-alter>-1</root-alter>"));
+        assert!(!xml.contains("<alter>1</alter>"));
+        // The strings are numbered from the A string (1) to the G string (4).
+        assert!(xml.contains("<string>1</string>\n            <fret>1</fret>"));
+        assert!(xml.contains("<string>4</string>\n            <fret>3</fret>"));
+    }
+
+    #[rstest(
+        chord,
+        min_fret,
+        definition,
+        case("C", 0, "{define: C base-fret 1 frets 0 0 0 3 fingers 0 0 0 3}"),
+        case("Bb", 0, "{define: Bb base-fret 1 frets 3 2 1 1 fingers 3 2 1 1}"),
+        case("C/E", 0, "{define: C/E base-fret 1 frets 0 4 0 3 fingers 0 2 0 1}"),
+        case("C", 5, "{define: C base-fret 5 frets 1 3 4 3 fingers 1 2 4 3}")
+    )]
+    fn test_to_chordpro(chord: Chord, min_fret: FretID, definition: &str) {
+        let config = VoicingConfig {
+            min_fret,
+            ..Default::default()
+        };
+        let voicing = chord.voicings(config).next().unwrap();
+
+        assert_eq!(
+            to_chordpro(vec![(&chord, &voicing)]),
+            format!("{definition}\n")
+        );
+    }
+
+    #[rstest(chord, case("C"), case("Bb"), case("D7"), case("F#m"), case("B"))]
+    fn test_to_chordpro_fingers_match_chart(chord: Chord) {
+        for voicing in chord.voicings(VoicingConfig::default()).take(5) {
+            let chart = ChordChart::new(&chord, voicing.clone(), voicing.get_span());
+            let chordpro = to_chordpro(vec![(&chord, &voicing)]);
+            let fingers = chordpro
+                .trim_end()
+                .trim_end_matches('}')
+                .split(" fingers ")
+                .nth(1);
+
+            assert_eq!(fingers, Some(chart.fingers().iter().join(" ").as_str()));
+            assert!(chordpro.contains(&format!("base-fret {} ", chart.get_base_fret())));
+        }
+    }
+
+    #[test]
+    fn test_to_chordpro_repeated_chord() {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = chord.voicings(VoicingConfig::default()).next().unwrap();
+        let chordpro = to_chordpro(vec![(&chord, &voicing), (&chord, &voicing)]);
+
+        assert_eq!(chordpro.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_to_tab() {
+        let chords = ["C", "Am", "F", "G"].map(|s| Chord::from_str(s).unwrap());
+        let voicings = chords
+            .iter()
+            .map(|chord| chord.voicings(VoicingConfig::default()).next().unwrap())
+            .collect_vec();
+        let tab = to_tab(
+            chords
+                .iter()
+                .zip(&voicings)
+                .map(|(chord, voicing)| (chord, None, voicing)),
+        );
+
+        let expected = indoc!(
+            "
+               C  Am  F  G
+            A|-3--0---0--2-|
+            E|-0--0---1--3-|
+            C|-0--0---0--2-|
+            G|-0--2---2--0-|
+        "
+        );
+        assert_eq!(tab, expected);
+    }
+
+    #[test]
+    fn test_to_tab_two_digit_frets() {
+        let chords = ["C", "D", "Dm"].map(|s| Chord::from_str(s).unwrap());
+        let voicings = [
+            Voicing::new([0, 0, 0, 3], Tuning::C),
+            Voicing::new([14, 14, 14, 12], Tuning::C),
+            Voicing::new([2, 2, 1, 0], Tuning::C),
+        ];
+        let tab = to_tab(
+            chords
+                .iter()
+                .zip(&voicings)
+                .map(|(chord, voicing)| (chord, None, voicing)),
+        );
+
+        // Four strings and one line with the chord names.
+        assert_eq!(tab.lines().count(), 5);
+        // All the string lines have the same length and the columns are aligned.
+        let lines = tab.lines().skip(1).collect_vec();
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        assert_eq!(lines[0], "A|-3--12--0--|");
+        assert_eq!(lines[3], "G|-0--14--2--|");
+        assert_eq!(tab.lines().next().unwrap(), "   C  D   Dm");
+    }
+
+    #[test]
+    fn test_to_tab_annotations() {
+        let chords = ["C", "Am"].map(|s| Chord::from_str(s).unwrap());
+        let voicings = [
+            Voicing::new([0, 0, 0, 3], Tuning::C),
+            Voicing::new([2, 0, 0, 0], Tuning::C),
+        ];
+        let annotations = [Some("DUDU"), None];
+        let tab = to_tab(
+            chords
+                .iter()
+                .zip(annotations)
+                .zip(&voicings)
+                .map(|((chord, annotation), voicing)| (chord, annotation, voicing)),
+        );
+
+        assert_eq!(tab.lines().next().unwrap(), "   C(DUDU)  Am");
+        assert_eq!(tab.lines().nth(1).unwrap(), "A|-3--------0--|");
+    }
+
+    #[test]
+    fn test_to_tab_empty() {
+        assert_eq!(to_tab(vec![]), "");
+    }
+}
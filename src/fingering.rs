@@ -1,6 +1,12 @@
 use std::cmp::{max, min};
 
-use crate::{FingerPosition, Voicing, FINGER_COUNT};
+use itertools::Itertools;
+
+use crate::{FingerPosition, FretID, Voicing, FINGER_COUNT};
+
+/// Maximal number of frets that can be covered by the fingers of one hand,
+/// allowing for a stretch of one fret beyond one finger per fret.
+const MAX_FINGER_SPAN: usize = FINGER_COUNT + 1;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Fingering {
@@ -8,6 +14,44 @@ pub struct Fingering {
 }
 
 impl Fingering {
+    /// Return an iterator over the fingers that are placed on the fretboard
+    /// (numbered from 1 to `FINGER_COUNT`, starting with the index finger)
+    /// together with their positions.
+    ///
+    /// For a barre, only the position of the upmost string covered by the
+    /// finger is returned.
+    pub fn placed_fingers(&self) -> impl Iterator<Item = (u8, FingerPosition)> + '_ {
+        self.finger_positions
+            .iter()
+            .enumerate()
+            .filter(|(_i, &position)| position != (0, 0))
+            .map(|(i, &position)| (i as u8 + 1, position))
+    }
+
+    /// Return `true` if `voicing` can physically be played with one hand.
+    ///
+    /// A voicing is considered unplayable if its frets are spread over more
+    /// than `MAX_FINGER_SPAN` frets or if it cannot be played without crossing
+    /// fingers, i.e. if the fingering (see `Voicing::fingers_on_strings`) does
+    /// not place the fingers on increasing frets in the order of the fingers.
+    /// The latter happens when more fingers than `FINGER_COUNT` are needed.
+    pub fn is_feasible(voicing: &Voicing) -> bool {
+        if voicing.get_span() as usize > MAX_FINGER_SPAN {
+            return false;
+        }
+
+        let pressed: Vec<(FretID, u8)> = voicing
+            .fret_pattern()
+            .zip(voicing.fingers_on_strings())
+            .filter_map(|(fret, finger)| fret.filter(|&f| f > 0).map(|f| (f, finger)))
+            .collect();
+
+        pressed
+            .into_iter()
+            .tuple_combinations()
+            .all(|((f1, finger1), (f2, finger2))| f1 == f2 || f1.cmp(&f2) == finger1.cmp(&finger2))
+    }
+
     /// Compute the distance between two fingerings, inspired by
     /// http://www.petecorey.com/blog/2018/08/27/computing-fingering-distance-with-dr-levenshtein/
     pub fn distance(&self, other: Self) -> u8 {
@@ -40,14 +84,14 @@ impl From<[FingerPosition; FINGER_COUNT]> for Fingering {
     }
 }
 
-impl From<Voicing> for Fingering {
-    fn from(voicing: Voicing) -> Self {
+impl From<&Voicing> for Fingering {
+    fn from(voicing: &Voicing) -> Self {
         let mut finger_positions = [(0, 0); FINGER_COUNT];
 
         let fingers_on_strings = voicing.fingers_on_strings();
 
-        for ((i, fret_id), finger) in voicing.frets().enumerate().zip(&fingers_on_strings) {
-            if finger > &0 {
+        for ((i, fret_id), finger) in voicing.fret_pattern().enumerate().zip(&fingers_on_strings) {
+            if let (Some(fret_id), true) = (fret_id, finger > &0) {
                 let index = (finger - 1) as usize;
                 let string_id = (i + 1) as u8;
 
@@ -67,7 +111,7 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
-    use crate::{FretID, Tuning, STRING_COUNT};
+    use crate::{FretPattern, Tuning, STRING_COUNT};
 
     #[rstest(
         frets, finger_positions,
@@ -80,11 +124,53 @@ mod tests {
         finger_positions: [FingerPosition; FINGER_COUNT],
     ) {
         let voicing = Voicing::new(frets, Tuning::C);
-        let fingering1 = Fingering::from(voicing);
+        let fingering1 = Fingering::from(&voicing);
         let fingering2 = Fingering::from(finger_positions);
         assert_eq!(fingering1, fingering2);
     }
 
+    #[rstest(
+        frets, placed_fingers,
+        case([0, 0, 0, 0], vec![]),
+        // Open C: a single finger on the A string.
+        case([0, 0, 0, 3], vec![(3, (4, 3))]),
+        case([2, 2, 2, 0], vec![(1, (1, 2)), (2, (2, 2)), (3, (3, 2))]),
+        // Barre chords: the index finger covers several strings.
+        case([2, 2, 2, 2], vec![(1, (1, 2))]),
+        case([3, 2, 1, 1], vec![(1, (3, 1)), (2, (2, 2)), (3, (1, 3))]),
+    )]
+    fn test_placed_fingers(
+        frets: [FretID; STRING_COUNT],
+        placed_fingers: Vec<(u8, FingerPosition)>,
+    ) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        let fingering = voicing.fingering();
+        assert_eq!(
+            fingering.placed_fingers().collect::<Vec<_>>(),
+            placed_fingers
+        );
+    }
+
+    #[rstest(
+        fret_pattern,
+        tuning,
+        is_feasible,
+        case("0000", Tuning::C, true),
+        case("0003", Tuning::C, true),
+        case("3211", Tuning::C, true),
+        case("1234", Tuning::C, true),
+        case("3015", Tuning::C, true),
+        case("x232", Tuning::C, true),
+        case("1357", Tuning::C, false),
+        case("0 0 1 7", Tuning::C, false),
+        case("123456", Tuning::Guitalele, false),
+        case("111234", Tuning::Guitalele, true)
+    )]
+    fn test_is_feasible(fret_pattern: FretPattern, tuning: Tuning, is_feasible: bool) {
+        let voicing = Voicing::new(fret_pattern, tuning);
+        assert_eq!(Fingering::is_feasible(&voicing), is_feasible);
+    }
+
     #[rstest(
         finger_positions1, finger_positions2, distance,
         case([(0, 0), (0, 0), (0, 0), (0, 0)], [(0, 0), (0, 0), (0, 0), (0, 0)], 0),
@@ -1,28 +1,99 @@
-use std::{convert::TryInto, slice::Iter, str::FromStr};
+use std::{fmt, iter::FromIterator, slice::Iter, str::FromStr};
 
-use crate::{FretID, STRING_COUNT};
+use itertools::Itertools;
+
+use crate::{FretID, Tuning};
 
 /// Custom error for strings that cannot be parsed into a fret pattern.
 #[derive(Debug, thiserror::Error)]
-#[error("fret pattern has wrong format (should be something like 1234 or '7 8 9 10')")]
+#[error("fret pattern has wrong format (should be something like 1234, x232 or '7 8 9 10')")]
 pub struct ParseFretPatternError;
 
+/// Custom error for fret patterns whose number of frets differs from
+/// the number of strings of the tuning they are played with.
+#[derive(Debug, thiserror::Error)]
+#[error("the fret pattern has {fret_count} frets but tuning {tuning} has {string_count} strings")]
+pub struct FretCountMismatchError {
+    tuning: Tuning,
+    fret_count: usize,
+    string_count: usize,
+}
+
 /// A pattern of frets to press down for playing a chord.
-/// Each index of the array corresponds to a ukulele string.
-#[derive(Debug, Copy, Clone)]
+/// Each index corresponds to a string of the instrument,
+/// muted strings are represented by `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FretPattern {
-    frets: [FretID; STRING_COUNT],
+    frets: Vec<Option<FretID>>,
 }
 
 impl FretPattern {
-    pub fn iter(&self) -> Iter<'_, FretID> {
+    pub fn iter(&self) -> Iter<'_, Option<FretID>> {
         self.frets.iter()
     }
+
+    /// Return the number of strings the pattern is made for.
+    pub fn len(&self) -> usize {
+        self.frets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frets.is_empty()
+    }
+
+    /// Check that the pattern has one fret for each string of `tuning`.
+    pub fn check_tuning(&self, tuning: Tuning) -> Result<(), FretCountMismatchError> {
+        let fret_count = self.len();
+        let string_count = tuning.string_count();
+
+        match fret_count == string_count {
+            true => Ok(()),
+            false => Err(FretCountMismatchError {
+                tuning,
+                fret_count,
+                string_count,
+            }),
+        }
+    }
+}
+
+impl<const N: usize> From<[FretID; N]> for FretPattern {
+    fn from(frets: [FretID; N]) -> Self {
+        Self {
+            frets: frets.iter().copied().map(Some).collect(),
+        }
+    }
+}
+
+impl<const N: usize> From<[Option<FretID>; N]> for FretPattern {
+    fn from(frets: [Option<FretID>; N]) -> Self {
+        Self {
+            frets: frets.to_vec(),
+        }
+    }
+}
+
+impl FromIterator<Option<FretID>> for FretPattern {
+    fn from_iter<I: IntoIterator<Item = Option<FretID>>>(iter: I) -> Self {
+        Self {
+            frets: iter.into_iter().collect(),
+        }
+    }
 }
 
-impl From<[FretID; STRING_COUNT]> for FretPattern {
-    fn from(frets: [FretID; STRING_COUNT]) -> Self {
-        Self { frets }
+/// Fret patterns are displayed in the compact form (e.g. `x232`) if all frets
+/// are single digits and separated by spaces otherwise (e.g. `7 8 9 10`),
+/// so that they can be parsed again by `from_str`.
+impl fmt::Display for FretPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frets = self
+            .iter()
+            .map(|fret| fret.map_or("x".to_string(), |fret| fret.to_string()));
+
+        match self.iter().flatten().all(|&fret| fret < 10) {
+            true => write!(f, "{}", frets.format("")),
+            false => write!(f, "{}", frets.format(" ")),
+        }
     }
 }
 
@@ -37,14 +108,19 @@ impl FromStr for FretPattern {
             false => s.chars().map(|c| c.to_string()).collect(),
         };
 
-        // Parse out numbers in the pattern.
-        let fret_res: Result<Vec<FretID>, _> = split.iter().map(|s| s.parse()).collect();
+        // Parse out numbers in the pattern. Muted strings are marked by `x` or `-`.
+        let fret_res: Result<Vec<Option<FretID>>, _> = split
+            .iter()
+            .map(|s| match s.as_str() {
+                "x" | "X" | "-" => Ok(None),
+                _ => s.parse().map(Some),
+            })
+            .collect();
 
-        if let Ok(fret_vec) = fret_res {
-            // Check for the correct number of frets.
-            let res: Result<[FretID; STRING_COUNT], _> = fret_vec.try_into();
-            if let Ok(frets) = res {
-                return Ok(Self::from(frets));
+        if let Ok(frets) = fret_res {
+            // At least one string has to be played.
+            if frets.iter().any(Option::is_some) {
+                return Ok(Self { frets });
             }
         }
 
@@ -60,16 +136,84 @@ mod tests {
 
     #[rstest(
         fret_pattern, frets,
-        case("2220", [2, 2, 2, 0]),
-        case("2 2 2 0", [2, 2, 2, 0]),
-        case("7 8 9 10", [7, 8, 9, 10]),
+        case("2220", vec![Some(2), Some(2), Some(2), Some(0)]),
+        case("2 2 2 0", vec![Some(2), Some(2), Some(2), Some(0)]),
+        case("7 8 9 10", vec![Some(7), Some(8), Some(9), Some(10)]),
+        case("x232", vec![None, Some(2), Some(3), Some(2)]),
+        case("-232", vec![None, Some(2), Some(3), Some(2)]),
+        case("x 7 8 x", vec![None, Some(7), Some(8), None]),
+        // The number of strings depends on the tuning.
+        case("222", vec![Some(2), Some(2), Some(2)]),
+        case("x32010", vec![None, Some(3), Some(2), Some(0), Some(1), Some(0)]),
     )]
-    fn test_from_str(fret_pattern: FretPattern, frets: [FretID; STRING_COUNT]) {
+    fn test_from_str(fret_pattern: FretPattern, frets: Vec<Option<FretID>>) {
         assert_eq!(fret_pattern.frets, frets);
     }
 
-    #[rstest(s, case(""), case("Cm"), case("222"), case("22201"))]
+    #[rstest(s, case(""), case("Cm"), case("xxxx"), case("x23y"))]
     fn test_from_str_fail(s: &str) {
         assert!(FretPattern::from_str(s).is_err());
     }
+
+    #[rstest(
+        s,
+        case("2220"),
+        case("x232"),
+        case("x32010"),
+        case("7 8 9 10"),
+        case("x 7 8 12"),
+        case("10 x 12 11")
+    )]
+    fn test_to_string_round_trip(s: &str) {
+        let fret_pattern = FretPattern::from_str(s).unwrap();
+        assert_eq!(fret_pattern.to_string(), s);
+        assert_eq!(
+            FretPattern::from_str(&fret_pattern.to_string()).unwrap(),
+            fret_pattern
+        );
+    }
+
+    #[rstest(
+        fret_pattern,
+        s,
+        case("2 2 2 0", "2220"),
+        case("-232", "x232"),
+        case("X 7 8 X", "x78x")
+    )]
+    fn test_to_string(fret_pattern: FretPattern, s: &str) {
+        assert_eq!(fret_pattern.to_string(), s);
+    }
+
+    #[rstest(
+        fret_pattern,
+        tuning,
+        case("2220", Tuning::C),
+        case("x232", Tuning::Baritone),
+        case("320003", Tuning::Guitalele)
+    )]
+    fn test_check_tuning(fret_pattern: FretPattern, tuning: Tuning) {
+        assert!(fret_pattern.check_tuning(tuning).is_ok());
+    }
+
+    #[rstest(
+        fret_pattern,
+        tuning,
+        error,
+        case(
+            "222",
+            Tuning::C,
+            "the fret pattern has 3 frets but tuning C has 4 strings"
+        ),
+        case(
+            "2220",
+            Tuning::Guitalele,
+            "the fret pattern has 4 frets but tuning guitalele has 6 strings"
+        )
+    )]
+    fn test_check_tuning_mismatch(fret_pattern: FretPattern, tuning: Tuning, error: &str) {
+        assert_eq!(
+            fret_pattern.check_tuning(tuning).unwrap_err().to_string(),
+            error
+        );
+    }
 }
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::{FingerPosition, FretID, Note, Spelling, Tuning};
+
+/// An overview of the notes that can be played on each string of the
+/// fretboard, from the nut up to a given fret.
+pub struct Fretboard {
+    tuning: Tuning,
+    /// The last fret to be shown.
+    max_fret: FretID,
+    /// If given, only the notes at these positions are shown.
+    positions: Option<Vec<FingerPosition>>,
+    spelling: Spelling,
+}
+
+impl Fretboard {
+    pub fn new(tuning: Tuning, max_fret: FretID) -> Self {
+        Self {
+            tuning,
+            max_fret,
+            positions: None,
+            spelling: Spelling::default(),
+        }
+    }
+
+    /// Only show the notes at the given positions (see `Tuning::positions_of`)
+    /// and leave all the other frets blank.
+    pub fn with_positions(self, positions: Vec<FingerPosition>) -> Self {
+        Self {
+            positions: Some(positions),
+            ..self
+        }
+    }
+
+    pub fn with_spelling(self, spelling: Spelling) -> Self {
+        Self { spelling, ..self }
+    }
+
+    fn is_shown(&self, string_id: usize, fret: FretID) -> bool {
+        match &self.positions {
+            Some(positions) => positions.contains(&((string_id + 1) as u8, fret)),
+            None => true,
+        }
+    }
+
+    /// Format a line that lists the notes to be played on a ukulele string.
+    fn format_line(&self, root: &str, root_width: usize, notes: &[String]) -> String {
+        let root_str = format!("{:width$}", root, width = root_width);
+
+        let frets: String = notes
+            .iter()
+            .enumerate()
+            .map(|(fret, note)| match fret {
+                // Mark the nut.
+                0 => format!(" {note:2} ||"),
+                _ => format!(" {note:2} |"),
+            })
+            .collect();
+
+        format!("{root_str}{frets}")
+    }
+}
+
+impl fmt::Display for Fretboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let root_width = self
+            .tuning
+            .roots()
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap();
+
+        // The header line showing the fret numbers has the same layout as
+        // the lines for the strings, just without the fret separators.
+        let fret_ids: Vec<String> = (0..=self.max_fret).map(|i| i.to_string()).collect();
+        let header = self
+            .format_line("", root_width, &fret_ids)
+            .replace('|', " ");
+        writeln!(f, "{}", header.trim_end())?;
+
+        // Show the strings in the same order as in a chord chart.
+        let roots: Vec<Note> = self.tuning.roots().collect();
+
+        for (string_id, root) in roots.iter().enumerate().rev() {
+            let notes: Vec<String> = (0..=self.max_fret)
+                .filter_map(|fret| {
+                    let note = self.tuning.note_at(string_id, fret)?;
+
+                    match self.is_shown(string_id, fret) {
+                        true => Some(note.respell(self.spelling).to_string()),
+                        false => Some(String::new()),
+                    }
+                })
+                .collect();
+
+            let line = self.format_line(&root.to_string(), root_width, &notes);
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::PitchClass;
+
+    #[rstest(
+        tuning, max_fret, fretboard,
+        case(
+            Tuning::C,
+            0,
+            indoc!("
+                  0
+                A A  ||
+                E E  ||
+                C C  ||
+                G G  ||
+            "),
+        ),
+        case(
+            Tuning::C,
+            3,
+            indoc!("
+                  0     1    2    3
+                A A  || A# | B  | C  |
+                E E  || F  | F# | G  |
+                C C  || C# | D  | D# |
+                G G  || G# | A  | A# |
+            "),
+        ),
+        case(
+            Tuning::D,
+            2,
+            indoc!("
+                   0     1    2
+                B  B  || C  | C# |
+                F# F# || G  | G# |
+                D  D  || D# | E  |
+                A  A  || A# | B  |
+            "),
+        ),
+    )]
+    fn test_to_string(tuning: Tuning, max_fret: FretID, fretboard: &str) {
+        assert_eq!(Fretboard::new(tuning, max_fret).to_string(), fretboard);
+    }
+
+    #[test]
+    fn test_with_positions() {
+        let positions = Tuning::C.positions_of(PitchClass::ASharp, 0, 3);
+        let fretboard = Fretboard::new(Tuning::C, 3)
+            .with_positions(positions)
+            .with_spelling(Spelling::Flats);
+
+        let expected = indoc!(
+            "
+              0     1    2    3
+            A    || Bb |    |    |
+            E    ||    |    |    |
+            C    ||    |    |    |
+            G    ||    |    | Bb |
+        "
+        );
+        assert_eq!(fretboard.to_string(), expected);
+    }
+}
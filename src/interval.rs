@@ -1,27 +1,48 @@
-use std::str::FromStr;
+use alloc::string::{String, ToString};
+use core::{cmp::Ordering, fmt, ops::Add, str::FromStr};
 
-use crate::{Semitones, StaffSteps};
+use crate::{Semitones, StaffSteps, PITCH_CLASS_COUNT};
+
+/// Number of diatonic steps in an octave (the interval numbers of an
+/// octave and a unison differ by seven).
+const OCTAVE_STEPS: StaffSteps = 7;
 
 /// Custom error for strings that cannot be parsed into intervals.
-#[derive(Debug, thiserror::Error)]
-#[error("could not parse interval name '{name}'")]
+#[derive(Debug)]
 pub struct ParseIntervalError {
     name: String,
 }
 
+impl fmt::Display for ParseIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse interval name '{}'", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIntervalError {}
+
 /// An interval is the difference between two notes.
 /// https://en.wikipedia.org/wiki/Interval_(music)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Interval {
     PerfectUnison,
+    AugmentedUnison,
+    MinorSecond,
     MajorSecond,
+    AugmentedSecond,
+    DiminishedThird,
     MinorThird,
     MajorThird,
+    DiminishedFourth,
     PerfectFourth,
+    AugmentedFourth,
     DiminishedFifth,
     PerfectFifth,
     AugmentedFifth,
+    MinorSixth,
     MajorSixth,
+    AugmentedSixth,
     DiminishedSeventh,
     MinorSeventh,
     MajorSeventh,
@@ -29,24 +50,104 @@ pub enum Interval {
     MajorNinth,
     AugmentedNinth,
     PerfectEleventh,
+    AugmentedEleventh,
+    MinorThirteenth,
     MajorThirteenth,
 }
 
 impl Interval {
+    /// Iterator over the values of the Interval enum.
+    ///
+    /// Unfortunately, we have to list them all and make sure to update
+    /// this list if a value is added or removed.
+    pub fn values() -> impl Iterator<Item = Self> {
+        use Interval::*;
+
+        [
+            PerfectUnison,
+            AugmentedUnison,
+            MinorSecond,
+            MajorSecond,
+            AugmentedSecond,
+            DiminishedThird,
+            MinorThird,
+            MajorThird,
+            DiminishedFourth,
+            PerfectFourth,
+            AugmentedFourth,
+            DiminishedFifth,
+            PerfectFifth,
+            AugmentedFifth,
+            MinorSixth,
+            MajorSixth,
+            AugmentedSixth,
+            DiminishedSeventh,
+            MinorSeventh,
+            MajorSeventh,
+            MinorNinth,
+            MajorNinth,
+            AugmentedNinth,
+            PerfectEleventh,
+            AugmentedEleventh,
+            MinorThirteenth,
+            MajorThirteenth,
+        ]
+        .iter()
+        .copied()
+    }
+
+    /// Return the interval with the given number that encompasses
+    /// the given number of semitones, e.g. `DiminishedFifth` for 5 and 6.
+    ///
+    /// Return `None` if there is no such interval.
+    fn from_number_and_semitones(number: StaffSteps, semitones: Semitones) -> Option<Self> {
+        Self::values().find(|i| i.to_number() == number && i.to_semitones() == semitones)
+    }
+
+    /// Return the inversion of the interval, i.e. the interval that
+    /// complements it to an octave, e.g. a minor sixth for a major third.
+    /// Compound intervals (such as ninths) are reduced to simple intervals
+    /// first, and the unison is its own inversion.
+    ///
+    /// Return `None` for the augmented unison, whose inversion
+    /// (a diminished octave) cannot be represented.
+    pub fn invert(self) -> Option<Self> {
+        let (number, semitones) = match self.to_number() {
+            n if n > OCTAVE_STEPS => (n - OCTAVE_STEPS, self.to_semitones() - PITCH_CLASS_COUNT),
+            n => (n, self.to_semitones()),
+        };
+
+        let number = match OCTAVE_STEPS + 2 - number {
+            n if n > OCTAVE_STEPS => n - OCTAVE_STEPS,
+            n => n,
+        };
+        let semitones = (PITCH_CLASS_COUNT - semitones) % PITCH_CLASS_COUNT;
+
+        Self::from_number_and_semitones(number, semitones)
+    }
+
     /// Return the number of semitones that the interval encompasses.
     pub fn to_semitones(self) -> Semitones {
         use Interval::*;
 
         match self {
             PerfectUnison => 0,
+            AugmentedUnison => 1,
+            MinorSecond => 1,
             MajorSecond => 2,
+            AugmentedSecond => 3,
+            DiminishedThird => 2,
             MinorThird => 3,
             MajorThird => 4,
+            DiminishedFourth => 4,
             PerfectFourth => 5,
+            AugmentedFourth => 6,
             DiminishedFifth => 6,
             PerfectFifth => 7,
             AugmentedFifth => 8,
+            MinorSixth => 8,
             MajorSixth => 9,
+            AugmentedSixth => 10,
             DiminishedSeventh => 9,
             MinorSeventh => 10,
             MajorSeventh => 11,
@@ -54,6 +155,8 @@ impl Interval {
             MajorNinth => 14,
             AugmentedNinth => 15,
             PerfectEleventh => 17,
+            AugmentedEleventh => 18,
+            MinorThirteenth => 20,
             MajorThirteenth => 21,
         }
     }
@@ -65,14 +168,22 @@ impl Interval {
 
         match self {
             PerfectUnison => 1,
+            AugmentedUnison => 1,
+            MinorSecond => 2,
             MajorSecond => 2,
+            AugmentedSecond => 2,
+            DiminishedThird => 3,
             MinorThird => 3,
             MajorThird => 3,
+            DiminishedFourth => 4,
             PerfectFourth => 4,
+            AugmentedFourth => 4,
             DiminishedFifth => 5,
             PerfectFifth => 5,
             AugmentedFifth => 5,
+            MinorSixth => 6,
             MajorSixth => 6,
+            AugmentedSixth => 6,
             DiminishedSeventh => 7,
             MinorSeventh => 7,
             MajorSeventh => 7,
@@ -80,9 +191,113 @@ impl Interval {
             MajorNinth => 9,
             AugmentedNinth => 9,
             PerfectEleventh => 11,
+            AugmentedEleventh => 11,
+            MinorThirteenth => 13,
             MajorThirteenth => 13,
         }
     }
+
+    /// Return the short name of the interval as used in chord formulas,
+    /// e.g. `b3` for the minor third or `#9` for the augmented ninth.
+    pub fn short(self) -> &'static str {
+        use Interval::*;
+
+        match self {
+            PerfectUnison => "1",
+            AugmentedUnison => "#1",
+            MinorSecond => "b2",
+            MajorSecond => "2",
+            AugmentedSecond => "#2",
+            DiminishedThird => "bb3",
+            MinorThird => "b3",
+            MajorThird => "3",
+            DiminishedFourth => "b4",
+            PerfectFourth => "4",
+            AugmentedFourth => "#4",
+            DiminishedFifth => "b5",
+            PerfectFifth => "5",
+            AugmentedFifth => "#5",
+            MinorSixth => "b6",
+            MajorSixth => "6",
+            AugmentedSixth => "#6",
+            DiminishedSeventh => "bb7",
+            MinorSeventh => "b7",
+            MajorSeventh => "7",
+            MinorNinth => "b9",
+            MajorNinth => "9",
+            AugmentedNinth => "#9",
+            PerfectEleventh => "11",
+            AugmentedEleventh => "#11",
+            MinorThirteenth => "b13",
+            MajorThirteenth => "13",
+        }
+    }
+
+    /// Return the full name of the interval, e.g. `minor third`.
+    pub fn name(self) -> &'static str {
+        use Interval::*;
+
+        match self {
+            PerfectUnison => "perfect unison",
+            AugmentedUnison => "augmented unison",
+            MinorSecond => "minor second",
+            MajorSecond => "major second",
+            AugmentedSecond => "augmented second",
+            DiminishedThird => "diminished third",
+            MinorThird => "minor third",
+            MajorThird => "major third",
+            DiminishedFourth => "diminished fourth",
+            PerfectFourth => "perfect fourth",
+            AugmentedFourth => "augmented fourth",
+            DiminishedFifth => "diminished fifth",
+            PerfectFifth => "perfect fifth",
+            AugmentedFifth => "augmented fifth",
+            MinorSixth => "minor sixth",
+            MajorSixth => "major sixth",
+            AugmentedSixth => "augmented sixth",
+            DiminishedSeventh => "diminished seventh",
+            MinorSeventh => "minor seventh",
+            MajorSeventh => "major seventh",
+            MinorNinth => "minor ninth",
+            MajorNinth => "major ninth",
+            AugmentedNinth => "augmented ninth",
+            PerfectEleventh => "perfect eleventh",
+            AugmentedEleventh => "augmented eleventh",
+            MinorThirteenth => "minor thirteenth",
+            MajorThirteenth => "major thirteenth",
+        }
+    }
+}
+
+impl Add for Interval {
+    type Output = Option<Self>;
+
+    /// Stack two intervals on top of each other, e.g. two minor thirds
+    /// make up a diminished fifth.
+    ///
+    /// Return `None` if the resulting interval cannot be represented
+    /// (e.g. an octave or an interval larger than a major thirteenth).
+    fn add(self, other: Self) -> Option<Self> {
+        let number = self.to_number() + other.to_number() - 1;
+        let semitones = self.to_semitones() + other.to_semitones();
+
+        Self::from_number_and_semitones(number, semitones)
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    /// Compare intervals by their size in semitones. Intervals of the same
+    /// size (such as the augmented fourth and the diminished fifth) are
+    /// ordered by their number.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.to_semitones(), self.to_number()).cmp(&(other.to_semitones(), other.to_number()))
+    }
 }
 
 impl FromStr for Interval {
@@ -95,14 +310,22 @@ impl FromStr for Interval {
 
         let interval = match s {
             "P1" => PerfectUnison,
+            "A1" => AugmentedUnison,
+            "m2" => MinorSecond,
             "M2" => MajorSecond,
+            "A2" => AugmentedSecond,
+            "d3" => DiminishedThird,
             "m3" => MinorThird,
             "M3" => MajorThird,
+            "d4" => DiminishedFourth,
             "P4" => PerfectFourth,
+            "A4" => AugmentedFourth,
             "d5" => DiminishedFifth,
             "P5" => PerfectFifth,
             "A5" => AugmentedFifth,
+            "m6" => MinorSixth,
             "M6" => MajorSixth,
+            "A6" => AugmentedSixth,
             "d7" => DiminishedSeventh,
             "m7" => MinorSeventh,
             "M7" => MajorSeventh,
@@ -110,6 +333,8 @@ impl FromStr for Interval {
             "M9" => MajorNinth,
             "A9" => AugmentedNinth,
             "P11" => PerfectEleventh,
+            "A11" => AugmentedEleventh,
+            "m13" => MinorThirteenth,
             "M13" => MajorThirteenth,
             _ => return Err(ParseIntervalError { name }),
         };
@@ -117,3 +342,200 @@ impl FromStr for Interval {
         Ok(interval)
     }
 }
+
+impl From<Semitones> for Interval {
+    /// Convert a number of semitones into the corresponding simple interval,
+    /// i.e. an interval spanning at most one octave.
+    ///
+    /// Values > 11 are placed in the range between 0 and 11 first.
+    /// If several intervals consist of the same number of semitones (e.g. the
+    /// major sixth and the diminished seventh), the more common one is used.
+    fn from(n: Semitones) -> Self {
+        use Interval::*;
+
+        match n % PITCH_CLASS_COUNT {
+            0 => PerfectUnison,
+            1 => MinorSecond,
+            2 => MajorSecond,
+            3 => MinorThird,
+            4 => MajorThird,
+            5 => PerfectFourth,
+            6 => DiminishedFifth,
+            7 => PerfectFifth,
+            8 => MinorSixth,
+            9 => MajorSixth,
+            10 => MinorSeventh,
+            11 => MajorSeventh,
+            // Because of the modulo, the value will always be in the correct range.
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        n,
+        interval,
+        case(0, "P1"),
+        case(1, "m2"),
+        case(2, "M2"),
+        case(3, "m3"),
+        case(4, "M3"),
+        case(5, "P4"),
+        case(6, "d5"),
+        case(7, "P5"),
+        case(8, "m6"),
+        case(9, "M6"),
+        case(10, "m7"),
+        case(11, "M7"),
+        case(12, "P1"),
+        case(15, "m3")
+    )]
+    fn test_from_semitones(n: Semitones, interval: Interval) {
+        assert_eq!(Interval::from(n), interval);
+    }
+
+    #[rstest(
+        interval,
+        short,
+        case("P1", "1"),
+        case("A2", "#2"),
+        case("m3", "b3"),
+        case("A4", "#4"),
+        case("M3", "3"),
+        case("d5", "b5"),
+        case("A5", "#5"),
+        case("d7", "bb7"),
+        case("m7", "b7"),
+        case("M7", "7"),
+        case("A9", "#9"),
+        case("A11", "#11"),
+        case("M13", "13")
+    )]
+    fn test_short(interval: Interval, short: &str) {
+        assert_eq!(interval.short(), short);
+    }
+
+    #[rstest(
+        interval,
+        name,
+        case("P1", "perfect unison"),
+        case("A2", "augmented second"),
+        case("m3", "minor third"),
+        case("A4", "augmented fourth"),
+        case("d5", "diminished fifth"),
+        case("M13", "major thirteenth")
+    )]
+    fn test_name(interval: Interval, name: &str) {
+        assert_eq!(interval.name(), name);
+    }
+
+    #[test]
+    fn test_values() {
+        let values: Vec<Interval> = Interval::values().collect();
+        assert_eq!(values.len(), 27);
+
+        // Every interval is listed exactly once.
+        for (i, interval) in values.iter().enumerate() {
+            assert!(!values[i + 1..].contains(interval));
+        }
+    }
+
+    #[rstest(
+        interval,
+        inversion,
+        case("P1", "P1"),
+        case("m2", "M7"),
+        case("M2", "m7"),
+        case("A2", "d7"),
+        case("d3", "A6"),
+        case("m3", "M6"),
+        case("M3", "m6"),
+        case("P4", "P5"),
+        case("A4", "d5"),
+        case("d5", "A4"),
+        case("P5", "P4"),
+        case("A5", "d4"),
+        case("m6", "M3"),
+        case("d7", "A2"),
+        case("m7", "M2"),
+        case("M7", "m2"),
+        case("m9", "M7"),
+        case("A9", "d7"),
+        case("P11", "P5"),
+        case("A11", "d5"),
+        case("M13", "m3")
+    )]
+    fn test_invert(interval: Interval, inversion: Interval) {
+        assert_eq!(interval.invert(), Some(inversion));
+    }
+
+    #[test]
+    fn test_invert_augmented_unison() {
+        assert_eq!(Interval::AugmentedUnison.invert(), None);
+    }
+
+    #[test]
+    fn test_invert_twice() {
+        for interval in Interval::values().filter(|i| i.to_number() <= OCTAVE_STEPS) {
+            if let Some(inversion) = interval.invert() {
+                assert_eq!(inversion.invert(), Some(interval));
+            }
+        }
+    }
+
+    #[rstest(
+        interval1,
+        interval2,
+        sum,
+        case("m3", "m3", Some("d5")),
+        case("M3", "m3", Some("P5")),
+        case("m3", "M3", Some("P5")),
+        case("M3", "M3", Some("A5")),
+        case("P5", "m3", Some("m7")),
+        case("P5", "M3", Some("M7")),
+        case("m3", "d5", Some("d7")),
+        case("P1", "M6", Some("M6")),
+        case("M7", "M2", None),
+        case("P5", "P5", Some("M9")),
+        case("M7", "P5", Some("A11")),
+        case("P5", "P4", None),
+        case("M13", "M2", None)
+    )]
+    fn test_add(interval1: Interval, interval2: Interval, sum: Option<&str>) {
+        let sum = sum.map(|s| Interval::from_str(s).unwrap());
+        assert_eq!(interval1 + interval2, sum);
+    }
+
+    #[rstest(
+        interval1,
+        interval2,
+        case("P1", "m2"),
+        case("m3", "M3"),
+        case("A4", "d5"),
+        case("M6", "d7"),
+        case("M7", "m9"),
+        case("P5", "M13")
+    )]
+    fn test_cmp(interval1: Interval, interval2: Interval) {
+        assert!(interval1 < interval2);
+        assert!(interval2 > interval1);
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        let mut intervals: Vec<Interval> = Interval::values().collect();
+        intervals.sort();
+
+        assert!(intervals
+            .windows(2)
+            .all(|w| w[0].to_semitones() <= w[1].to_semitones()));
+    }
+}
@@ -1,36 +1,81 @@
 #![allow(clippy::upper_case_acronyms)]
+// The music theory primitives only need `alloc`, everything else is gated
+// behind the default `std` feature (see Cargo.toml).
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+// The test harness (and `rstest`) needs `std` even if the library does not.
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+#[cfg(feature = "std")]
 pub mod chord;
+#[cfg(feature = "std")]
 pub mod chord_chart;
+#[cfg(feature = "std")]
 pub mod chord_sequence;
 pub mod chord_type;
+#[cfg(feature = "std")]
 pub mod distance;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "std")]
 pub mod fingering;
+#[cfg(feature = "std")]
 pub mod fret_pattern;
+#[cfg(feature = "std")]
+pub mod fretboard;
 pub mod interval;
 pub mod note;
 pub mod pitch_class;
+#[cfg(feature = "std")]
+pub mod pitch_range;
+#[cfg(feature = "std")]
+pub mod scale;
 pub mod staff_position;
+#[cfg(feature = "std")]
 pub mod tuning;
+#[cfg(feature = "std")]
 pub mod voicing;
+#[cfg(feature = "std")]
+pub mod voicing_builder;
+#[cfg(feature = "std")]
 pub mod voicing_graph;
 
+#[cfg(feature = "std")]
 pub use chord::Chord;
-pub use chord_chart::ChordChart;
+#[cfg(feature = "std")]
+pub use chord_chart::{ChartStyle, ChordChart};
+#[cfg(feature = "std")]
 pub use chord_sequence::ChordSequence;
 pub use chord_type::{ChordType, NoMatchingChordTypeFoundError};
-pub use distance::Distance;
+#[cfg(feature = "std")]
+pub use distance::{Distance, DistanceWeights};
+#[cfg(feature = "std")]
 pub use fingering::Fingering;
-pub use fret_pattern::FretPattern;
+#[cfg(feature = "std")]
+pub use fret_pattern::{FretCountMismatchError, FretPattern};
+#[cfg(feature = "std")]
+pub use fretboard::Fretboard;
 pub use interval::Interval;
-pub use note::Note;
+pub use note::{Note, Spelling};
 pub use pitch_class::PitchClass;
+#[cfg(feature = "std")]
+pub use pitch_range::{ParsePitchRangeError, PitchRange};
+#[cfg(feature = "std")]
+pub use scale::{ParseKeyError, Scale, ScaleType};
 pub use staff_position::StaffPosition;
-pub use tuning::Tuning;
-pub use voicing::Voicing;
-pub use voicing_graph::VoicingGraph;
+#[cfg(feature = "std")]
+pub use tuning::{CustomTuning, ParseTuningError, StringCountMismatchError, Tuning};
+#[cfg(feature = "std")]
+pub use voicing::{ToneAnalysis, Voicing};
+#[cfg(feature = "std")]
+pub use voicing_builder::{VoicingBuilder, VoicingBuilderError};
+#[cfg(feature = "std")]
+pub use voicing_graph::{InvalidStartVoicingError, VoicingGraph};
 
-/// Number of strings on our string instrument.
+/// Number of strings on a standard ukulele. Use `Tuning::string_count`
+/// to get the number of strings for a given tuning.
 pub const STRING_COUNT: usize = 4;
 
 /// Number of fingers on our left hand to be used for pressing down strings.
@@ -54,6 +99,10 @@ pub type Semitones = u8;
 /// to another.
 pub type StaffSteps = u8;
 
+/// The octave of a note in scientific pitch notation, e.g. 4 for the
+/// middle C (C4).
+pub type Octave = u8;
+
 /// The position of a finger on a certain string in a certain fret.
 /// For example, (3, 4) depicts the fourth fret on the third string.
 pub type FingerPosition = (u8, u8);
@@ -63,14 +112,43 @@ pub type FingerPosition = (u8, u8);
 /// the note that is played if this fret is pressed down.
 pub type UkeString = (Note, FretID, Note);
 
+#[cfg(feature = "std")]
 #[derive(Clone, Copy)]
 pub struct VoicingConfig {
     pub tuning: Tuning,
     pub min_fret: FretID,
     pub max_fret: FretID,
     pub max_span: Semitones,
+    /// Order the voicings by their difficulty (see `Voicing::difficulty`)
+    /// instead of by their position on the fretboard.
+    pub sort_by_difficulty: bool,
+    /// Skip the voicings that are physically impossible to play
+    /// (see `Fingering::is_feasible`).
+    pub only_playable: bool,
+    /// Skip the voicings in which a note sounds lower than this note
+    /// (compared by pitch, including the octave).
+    pub lowest_note: Option<Note>,
+    /// Skip the voicings in which a note sounds higher than this note
+    /// (compared by pitch, including the octave).
+    pub highest_note: Option<Note>,
+    /// Number of a string that is always played open as a drone (counted from 1,
+    /// the leftmost string in the tuning's notation, e.g. G in GCEA), even if
+    /// `min_fret` is greater than 0.
+    /// Unless `drone_chord_tone` is set, the open string does not need to be
+    /// a chord tone.
+    pub drone: Option<usize>,
+    /// Only allow drones whose open string is a tone of the chord.
+    pub drone_chord_tone: bool,
+    /// Only keep root-position voicings, i.e. voicings whose lowest note
+    /// (see `Voicing::bass`) is the root of the chord. For slash chords,
+    /// the bass note has to be the lowest note instead.
+    pub root_in_bass: bool,
+    /// Skip the voicings with more open strings than this, e.g. `Some(0)`
+    /// to only keep closed voicings, i.e. movable shapes.
+    pub max_open_strings: Option<usize>,
 }
 
+#[cfg(feature = "std")]
 impl Default for VoicingConfig {
     fn default() -> Self {
         Self {
@@ -78,6 +156,37 @@ impl Default for VoicingConfig {
             min_fret: 0,
             max_fret: 12,
             max_span: 4,
+            sort_by_difficulty: false,
+            only_playable: false,
+            lowest_note: None,
+            highest_note: None,
+            drone: None,
+            drone_chord_tone: false,
+            root_in_bass: false,
+            max_open_strings: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::{convert::TryFrom, str::FromStr};
+
+    use super::*;
+
+    /// The music theory primitives work without the `std` feature
+    /// (run with `cargo test --no-default-features --lib`).
+    #[test]
+    fn test_core_primitives() {
+        let root = Note::from_str("Eb").unwrap();
+        let third = root + Interval::MajorThird;
+        let fifth = root + Interval::from_str("P5").unwrap();
+        assert_eq!(third.to_string(), "G");
+        assert_eq!(fifth.to_string(), "Bb");
+
+        let pitches = [root.pitch_class, third.pitch_class, fifth.pitch_class];
+        assert_eq!(ChordType::try_from(&pitches[..]), Ok(ChordType::Major));
+        assert_eq!(root.midi_number(), 63);
+    }
+}
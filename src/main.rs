@@ -1,15 +1,28 @@
-use clap::Parser;
+use std::{collections::HashSet, ffi::OsStr, iter, str::FromStr};
+
+use clap::{
+    builder::{PossibleValue, StringValueParser, TypedValueParser},
+    error::ErrorKind,
+    CommandFactory, Parser, ValueEnum,
+};
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use serde::Serialize;
 use ukebox::{
-    Chord, ChordChart, ChordSequence, ChordType, FretID, FretPattern, Semitones, Tuning, Voicing,
-    VoicingConfig, VoicingGraph,
+    chord_chart::side_by_side,
+    export::{to_chordpro, to_musicxml, to_tab},
+    ChartStyle, Chord, ChordChart, ChordSequence, ChordType, CustomTuning, Distance,
+    DistanceWeights, FretID, FretPattern, Fretboard, Note, PitchRange, Scale, ScaleType, Semitones,
+    Spelling, Tuning, Voicing, VoicingConfig, VoicingGraph,
 };
 
 /// Maximal possible fret ID.
 /// According to Wikipedia, the biggest ukulele type (baritone) has 21 frets.
 const MAX_FRET_ID: FretID = 21;
 
+/// Number of spaces between chord charts printed next to each other.
+const CHART_GAP: usize = 4;
+
 /// Maximal span of frets.
 /// Playing a chord that spans more than 5 frets seems anatomically impossible to me.
 const MAX_SPAN: Semitones = 5;
@@ -23,11 +36,204 @@ lazy_static! {
     static ref MAX_SPAN_STR: String = DEFAULT_CONFIG.max_span.to_string();
 }
 
+/// Output format of chord charts.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ChartFormat {
+    Text,
+    Svg,
+}
+
+/// Order in which the voicings of a chord are printed.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum VoicingOrder {
+    /// By their position on the fretboard, lowest first
+    Position,
+    /// By their difficulty, easiest first
+    Difficulty,
+    /// By the number of frets they span, smallest first
+    Span,
+}
+
+/// Quality of chord types to be listed by the `chords` subcommand.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ChordQuality {
+    Major,
+    Minor,
+    Dominant,
+    Diminished,
+    /// Chord types containing a seventh of any kind
+    Seventh,
+}
+
+impl ChordQuality {
+    fn matches(self, chord_type: ChordType) -> bool {
+        match self {
+            Self::Major => chord_type.is_major(),
+            Self::Minor => chord_type.is_minor(),
+            Self::Dominant => chord_type.is_dominant(),
+            Self::Diminished => chord_type.is_diminished(),
+            Self::Seventh => chord_type.has_seventh(),
+        }
+    }
+}
+
+/// Parser for `--tuning` accepting the names of the tunings (which are listed
+/// as possible values in the help) as well as the notes of custom tunings.
+#[derive(Clone)]
+struct TuningParser;
+
+impl TypedValueParser for TuningParser {
+    type Value = Tuning;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = StringValueParser::new().parse_ref(cmd, arg, value)?;
+        <Tuning as FromStr>::from_str(&value)
+            .map_err(|e| cmd.clone().error(ErrorKind::ValueValidation, e))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Tuning::value_variants()
+                .iter()
+                .filter_map(|tuning| tuning.to_possible_value()),
+        ))
+    }
+}
+
+/// Output format of the results of the subcommands.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// JSON representation of a chord voicing.
+#[derive(Serialize)]
+struct VoicingOutput {
+    tuning: String,
+    #[serde(flatten)]
+    voicing: Voicing,
+    span: FretID,
+    position: FretID,
+}
+
+impl VoicingOutput {
+    fn new(voicing: Voicing, tuning: Tuning) -> Self {
+        Self {
+            tuning: tuning.to_string(),
+            span: voicing.get_span(),
+            position: voicing.get_min_fret(),
+            voicing,
+        }
+    }
+}
+
+/// JSON representation of the result of the `chart` subcommand.
+#[derive(Serialize)]
+struct ChartOutput {
+    chord: Chord,
+    voicings: Vec<VoicingOutput>,
+}
+
+/// JSON representation of the result of the `chart` subcommand with `--count`.
+#[derive(Serialize)]
+struct ChartCountOutput {
+    chord: Chord,
+    count: usize,
+}
+
+/// Output format of the voice leading.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum VoiceLeadFormat {
+    Text,
+    Json,
+    #[value(name = "musicxml")]
+    MusicXml,
+    #[value(name = "chordpro")]
+    ChordPro,
+    /// ASCII tablature with one line per string
+    Tab,
+}
+
+/// JSON representation of the distance between two voicings.
+#[derive(Serialize)]
+struct DistanceOutput {
+    semitone_distance: u8,
+    fingering_distance: u8,
+}
+
+impl From<Distance> for DistanceOutput {
+    fn from(dist: Distance) -> Self {
+        Self {
+            semitone_distance: dist.semitone_distance(),
+            fingering_distance: dist.fingering_distance(),
+        }
+    }
+}
+
+/// JSON representation of a single step of a voice leading.
+#[derive(Serialize)]
+struct VoiceLeadOutput {
+    chord: Chord,
+    voicing: VoicingOutput,
+    /// Roman numeral of the chord relative to the root of the first chord.
+    roman_numeral: String,
+    distance_from_previous: Option<DistanceOutput>,
+}
+
+/// JSON representation of a supported chord type.
+#[derive(Serialize)]
+struct ChordTypeOutput {
+    chord_type: ChordType,
+    name: String,
+    symbols: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    intervals: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    difficulty: Option<f64>,
+}
+
+/// Exit with an error if the number of frets in `fret_pattern` does not match
+/// the number of strings of `tuning`.
+fn check_fret_pattern(fret_pattern: &FretPattern, tuning: Tuning) {
+    if let Err(err) = fret_pattern.check_tuning(tuning) {
+        Ukebox::command()
+            .error(ErrorKind::ValueValidation, err)
+            .exit();
+    }
+}
+
+/// Print `value` as a single line of JSON.
+fn print_json(value: &impl Serialize) {
+    println!("{}", serde_json::to_string(value).unwrap());
+}
+
 #[derive(Parser)]
 struct Ukebox {
-    /// Type of tuning to be used
-    #[arg(short, long, global = true, value_name = "TUNING", default_value = &**TUNING_STR, value_enum)]
+    /// Type of tuning to be used or the notes of the open strings, e.g. "A D F# B" or DGBE
+    #[arg(short, long, global = true, value_name = "TUNING", default_value = &**TUNING_STR, value_parser = TuningParser)]
     tuning: Tuning,
+    /// Notes of the open strings replacing those of <TUNING>, e.g. "A D F# B" or "G3 C4 E4 A4"
+    #[arg(long, global = true, value_name = "NOTES")]
+    custom_tuning: Option<CustomTuning>,
+    /// Output format (JSON is supported by chart, name, voice-lead and chords)
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_enum
+    )]
+    output: OutputFormat,
+    /// Mirror the chord charts for left-handed players
+    #[arg(long, global = true)]
+    left_handed: bool,
     #[command(subcommand)]
     cmd: Subcommand,
 }
@@ -35,7 +241,21 @@ struct Ukebox {
 #[derive(Parser)]
 enum Subcommand {
     /// List all supported chord types and symbols
-    Chords {},
+    Chords {
+        /// List only the chord types of the given quality
+        #[arg(long, value_name = "QUALITY", value_enum)]
+        filter: Option<ChordQuality>,
+        /// Root note used for the example chords
+        #[arg(long, value_name = "NOTE", default_value = "C")]
+        root: Note,
+        /// Show how difficult the chord types are to play, i.e. the average
+        /// difficulty of their easiest voicings on all roots (the lower, the easier)
+        #[arg(long)]
+        difficulty: bool,
+        /// Print the interval formula of each chord type (e.g. 1 3 5 b7)
+        #[arg(long)]
+        print_intervals: bool,
+    },
     /// Chord chart lookup
     ///
     /// Enter note names as capital letters A - G.
@@ -48,6 +268,53 @@ enum Subcommand {
         /// Print out all voicings of <chord> that fulfill the given conditions
         #[arg(short, long)]
         all: bool,
+        /// Order of the voicings (without --all, the first voicing in this order is printed)
+        #[arg(long, value_name = "ORDER", default_value = "position", value_enum)]
+        sort_by: VoicingOrder,
+        /// Maximal number of voicings to print out when using --all
+        #[arg(long, value_name = "N")]
+        max_results: Option<usize>,
+        /// Skip voicings with the same shape as a previous one, i.e. voicings that
+        /// only differ in the octave in which some strings are played (when using --all)
+        #[arg(long)]
+        no_duplicate_shapes: bool,
+        /// Number of chord charts to print next to each other (when using --all)
+        #[arg(long, value_name = "N", default_value = "1", value_parser = clap::value_parser!(u8).range(1..))]
+        per_row: u8,
+        /// Output format of the chord charts (SVG output holds a single chart and cannot be used with --all)
+        #[arg(long, value_name = "FORMAT", default_value = "text", value_enum)]
+        format: ChartFormat,
+        /// Characters used to draw the strings and frets of the chord charts
+        #[arg(long, value_name = "STYLE", default_value = "ascii", value_enum)]
+        style: ChartStyle,
+        /// Print the MIDI note numbers of the strings instead of a chord chart
+        #[arg(long, conflicts_with = "format")]
+        midi: bool,
+        /// Print the finger to be placed on each string (0 for open and x for muted strings)
+        /// instead of a chord chart
+        #[arg(long, conflicts_with_all = ["format", "midi"])]
+        fingers: bool,
+        /// Print only the number of voicings of <chord> that fulfill the given conditions
+        #[arg(long, conflicts_with_all = ["format", "midi", "fingers"])]
+        count: bool,
+        /// Do not print the names of the notes played on the strings
+        #[arg(long)]
+        no_notes: bool,
+        /// Print the open-string notes of the tuning above the chart
+        #[arg(long)]
+        show_tuning: bool,
+        /// Show only the frets needed to play the voicing instead of <max-span> frets
+        #[arg(long)]
+        window: bool,
+        /// Label each note with its interval from the root of the chord (e.g. R, 3, 5, b7)
+        #[arg(long)]
+        intervals: bool,
+        /// Fret at which a capo is placed (the chart shows the shape relative to the capo)
+        #[arg(long, value_name = "FRET_ID", default_value = "0", value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        capo: FretID,
+        /// Spelling of notes that can be sharp or flat (by default, it is derived from the chord)
+        #[arg(long, value_name = "SPELLING", default_value = "key", value_enum)]
+        spell: Spelling,
         #[command(flatten)]
         voicing_opts: VoicingOpts,
         /// Name of the chord to be shown
@@ -57,13 +324,164 @@ enum Subcommand {
     /// Chord name lookup
     Name {
         /// A compact chart representing the finger positions of the chord to be looked up
-        #[arg(value_name = "FRET_PATTERN")]
+        /// (use x or - for muted strings)
+        #[arg(value_name = "FRET_PATTERN", allow_hyphen_values = true)]
         fret_pattern: FretPattern,
+        /// Print the note of each string and its interval from the root of the chord
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Show how the fingers move when changing from one chord voicing to another
+    Diff {
+        /// Fret pattern of the voicing to start from (use x or - for muted strings)
+        #[arg(value_name = "FROM", allow_hyphen_values = true)]
+        from: FretPattern,
+        /// Fret pattern of the voicing to move to (use x or - for muted strings)
+        #[arg(value_name = "TO", allow_hyphen_values = true)]
+        to: FretPattern,
     },
     /// Voice leading for a sequence of chords
     VoiceLead {
         #[command(flatten)]
         voicing_opts: VoicingOpts,
+        /// Merge consecutive repetitions of the same chord into a single chord
+        #[arg(long)]
+        merge_repeats: bool,
+        /// Output format of the voice leading
+        #[arg(long, value_name = "FORMAT", default_value = "text", value_enum)]
+        format: VoiceLeadFormat,
+        /// Number of alternative voice leadings to print, starting with the best one
+        /// (only used for the text format)
+        #[arg(long, value_name = "K", default_value = "1", value_parser = clap::value_parser!(u8).range(1..))]
+        num_paths: u8,
+        /// Print the distances between consecutive voicings and the total distance
+        /// (only used for the text format)
+        #[arg(long)]
+        show_cost: bool,
+        /// Weight of the movement of the fingers along the strings when searching for the best voice leading
+        #[arg(long, value_name = "WEIGHT", default_value = "1")]
+        fret_weight: u32,
+        /// Weight of the fingering distance (including changes of strings)
+        #[arg(long, value_name = "WEIGHT", default_value = "0")]
+        fingering_weight: u32,
+        /// Weight of the position of the voicings on the neck (use it to stay in a low position)
+        #[arg(long, value_name = "WEIGHT", default_value = "0")]
+        position_weight: u32,
+        /// Key of the chord sequence, e.g. C for C major or Am for A minor (used for
+        /// --nashville, --check-key and for the Roman numerals, by default the root
+        /// of the first chord)
+        #[arg(long, value_name = "KEY", value_parser = Scale::from_key)]
+        key: Option<Scale>,
+        /// Interpret the chord sequence in Nashville number notation (e.g. "1 6m 4 5"),
+        /// the numbers being scale degrees of the major scale on the tonic of --key
+        #[arg(long, requires = "key")]
+        nashville: bool,
+        /// Warn about the chords that are not diatonic to --key
+        #[arg(long, requires = "key")]
+        check_key: bool,
+        /// Fret pattern of the voicing of the first chord (use x or - for muted strings),
+        /// only the voicings of the other chords are searched for
+        #[arg(long, value_name = "FRET_PATTERN", allow_hyphen_values = true)]
+        start: Option<FretPattern>,
+        /// Chord sequence
+        #[arg(value_name = "CHORD_SEQUENCE")]
+        chord_seq: String,
+    },
+    /// List the chords that can be played (almost) only with open strings
+    OpenChords {
+        /// Maximal number of strings to be pressed down
+        #[arg(long, value_name = "COUNT", default_value = "0")]
+        max_pressed: u8,
+        /// Maximal fret at which to press down a string
+        #[arg(long, value_name = "FRET_ID", default_value = "3", value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        max_fret: FretID,
+    },
+    /// List all the chords that can be played within a range of frets, easiest first
+    Suggest {
+        /// Minimal fret at which to press down a string
+        #[arg(long, value_name = "FRET_ID", default_value = &**MIN_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        min_fret: FretID,
+        /// Maximal fret at which to press down a string
+        #[arg(long, value_name = "FRET_ID", default_value = "3", value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        max_fret: FretID,
+        /// Maximal span between the first and the last fret pressed down
+        #[arg(long, value_name = "FRET_COUNT", default_value = &**MAX_SPAN_STR, value_parser = clap::value_parser!(Semitones).range(0..=MAX_SPAN as i64))]
+        max_span: Semitones,
+        /// Root note of a string that must be played open (can be given several times)
+        #[arg(long, value_name = "NOTE")]
+        open: Vec<Note>,
+    },
+    /// Notes of a scale and their positions on the fretboard
+    Scale {
+        /// Minimal fret from which to show the notes of the scale
+        #[arg(long, value_name = "FRET_ID", default_value = &**MIN_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        min_fret: FretID,
+        /// Maximal fret up to which to show the notes of the scale
+        #[arg(long, value_name = "FRET_ID", default_value = &**MAX_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        max_fret: FretID,
+        /// Root note of the scale
+        #[arg(value_name = "ROOT")]
+        root: Note,
+        /// Type of the scale
+        #[arg(value_name = "SCALE_TYPE", value_enum)]
+        scale_type: ScaleType,
+    },
+    /// Overview of the notes on the fretboard
+    Fretboard {
+        /// Maximal fret up to which to show the notes
+        #[arg(long, value_name = "FRET_ID", default_value = &**MAX_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        max_fret: FretID,
+    },
+    /// Find all the positions on the fretboard at which a note is played
+    Find {
+        /// Minimal fret from which to search for the note
+        #[arg(long, value_name = "FRET_ID", default_value = &**MIN_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        min_fret: FretID,
+        /// Maximal fret up to which to search for the note
+        #[arg(long, value_name = "FRET_ID", default_value = &**MAX_FRET_STR, value_parser = clap::value_parser!(FretID).range(0..=MAX_FRET_ID as i64))]
+        max_fret: FretID,
+        /// Name of the note to be found
+        #[arg(value_name = "NOTE")]
+        note: Note,
+    },
+    /// Roman numeral analysis of a sequence of chords
+    Analyze {
+        /// Major key in which to analyze the chord sequence (by default the root of the first chord)
+        #[arg(long, value_name = "KEY")]
+        key: Option<Note>,
+        /// Chord sequence
+        #[arg(value_name = "CHORD_SEQUENCE")]
+        chord_seq: ChordSequence,
+    },
+    /// Relative minor chord of a major chord or relative major chord of a minor chord
+    Relative {
+        /// Name of the chord
+        #[arg(value_name = "CHORD")]
+        chord: Chord,
+    },
+    /// Suggest a capo position that makes a sequence of chords easier to play
+    /// and print the chord shapes to be played relative to the capo
+    Capo {
+        #[command(flatten)]
+        voicing_opts: VoicingOpts,
+        /// Chord sequence
+        #[arg(value_name = "CHORD_SEQUENCE")]
+        chord_seq: ChordSequence,
+    },
+    /// Transpose a sequence of chords and print the chord names
+    Transpose {
+        /// Number of semitones to add (e.g. 1, +1) or to subtract (e.g. -1)
+        #[arg(
+            long,
+            value_name = "SEMITONES",
+            allow_hyphen_values = true,
+            required_unless_present = "to",
+            conflicts_with = "to"
+        )]
+        by: Option<i8>,
+        /// Root that the first chord of the sequence is transposed to
+        #[arg(long, value_name = "ROOT")]
+        to: Option<Note>,
         /// Chord sequence
         #[arg(value_name = "CHORD_SEQUENCE")]
         chord_seq: ChordSequence,
@@ -89,69 +507,422 @@ pub struct VoicingOpts {
         default_value = "0"
     )]
     transpose: i8,
+    /// Only show voicings whose notes all sound within this range of pitches
+    /// (e.g. C4-A5, G4- or -C6)
+    #[arg(long, value_name = "LOWEST-HIGHEST", allow_hyphen_values = true)]
+    range: Option<PitchRange>,
+    /// Number of a string to be played open as a drone (counted from 1, the leftmost
+    /// string in the tuning's notation, e.g. G in GCEA)
+    #[arg(long, value_name = "STRING", value_parser = clap::value_parser!(u8).range(1..))]
+    drone: Option<u8>,
+    /// Only allow drones that are tones of the chord
+    #[arg(long, requires = "drone")]
+    drone_chord_tone: bool,
+    /// Only show voicings whose lowest note is the root of the chord
+    /// (or the bass note of a slash chord)
+    #[arg(long)]
+    root_in_bass: bool,
+    /// Maximal number of strings played open (0 to only show movable shapes)
+    #[arg(long, value_name = "STRING_COUNT")]
+    max_open_strings: Option<usize>,
+}
+
+impl VoicingOpts {
+    fn lowest_note(&self) -> Option<Note> {
+        self.range.and_then(|range| range.lowest)
+    }
+
+    fn highest_note(&self) -> Option<Note> {
+        self.range.and_then(|range| range.highest)
+    }
+
+    /// Return the drone string, exiting with an error if `tuning` has fewer strings.
+    fn drone(&self, tuning: Tuning) -> Option<usize> {
+        let drone = self.drone? as usize;
+        let string_count = tuning.string_count();
+
+        if drone > string_count {
+            Ukebox::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("the drone string {drone} does not exist in tuning {tuning} with {string_count} strings"),
+                )
+                .exit();
+        }
+
+        Some(drone)
+    }
 }
 
 fn main() {
     let args = Ukebox::parse();
-    let tuning = args.tuning;
+    let tuning = match args.custom_tuning {
+        Some(custom) => match args.tuning.with_custom(custom) {
+            Ok(tuning) => tuning,
+            Err(e) => Ukebox::command()
+                .error(ErrorKind::ValueValidation, e.to_string())
+                .exit(),
+        },
+        None => args.tuning,
+    };
+    let output = args.output;
+    let left_handed = args.left_handed;
 
     match args.cmd {
-        Subcommand::Chords {} => {
+        Subcommand::Chords {
+            filter,
+            difficulty,
+            print_intervals,
+            ..
+        } if output == OutputFormat::Json => {
+            let chord_types: Vec<ChordTypeOutput> = ChordType::values_by_stack_height()
+                .filter(|&chord_type| filter.map_or(true, |f| f.matches(chord_type)))
+                .map(|chord_type| ChordTypeOutput {
+                    chord_type,
+                    name: chord_type.to_string(),
+                    symbols: chord_type.symbols().map(String::from).collect(),
+                    intervals: match print_intervals {
+                        true => Some(
+                            chord_type
+                                .intervals()
+                                .map(|i| i.short().to_string())
+                                .collect(),
+                        ),
+                        false => None,
+                    },
+                    difficulty: match difficulty {
+                        true => chord_type.difficulty(tuning),
+                        false => None,
+                    },
+                })
+                .collect();
+            print_json(&chord_types);
+        }
+        Subcommand::Chords {
+            filter,
+            root,
+            difficulty,
+            print_intervals,
+        } => {
             println!("Supported chord types and symbols\n");
-            println!("The root note C is used as an example.\n");
+            println!("The root note {root} is used as an example.\n");
+
+            let chord_types = ChordType::values_by_stack_height()
+                .filter(|&chord_type| filter.map_or(true, |f| f.matches(chord_type)));
+            for (i, (group_name, group)) in chord_types
+                .chunk_by(|chord_type| chord_type.group_name())
+                .into_iter()
+                .enumerate()
+            {
+                if i > 0 {
+                    println!();
+                }
+                println!("{group_name}\n");
 
-            for chord_type in ChordType::values() {
-                let symbols = chord_type.symbols().map(|s| format!("C{s}")).join(", ");
-                println!("C {chord_type} - {symbols}");
+                for chord_type in group {
+                    let mut symbols = chord_type
+                        .symbols()
+                        .map(|s| format!("{root}{s}"))
+                        .join(", ");
+
+                    if print_intervals {
+                        let formula = chord_type.intervals().map(|i| i.short()).join(" ");
+                        symbols = format!("{symbols} - {formula}");
+                    }
+
+                    match difficulty {
+                        true => match chord_type.difficulty(tuning) {
+                            Some(score) => {
+                                println!("{root} {chord_type} - {symbols} (difficulty: {score:.1})")
+                            }
+                            None => println!("{root} {chord_type} - {symbols} (no voicing found)"),
+                        },
+                        false => println!("{root} {chord_type} - {symbols}"),
+                    }
+                }
             }
         }
         Subcommand::Chart {
             all,
+            sort_by,
+            max_results,
+            no_duplicate_shapes,
+            per_row,
+            format,
+            style,
+            midi,
+            fingers,
+            count,
+            no_notes,
+            show_tuning,
+            window,
+            intervals,
+            capo,
+            spell,
             voicing_opts,
             chord,
         } => {
-            let chord = chord.transpose(voicing_opts.transpose);
+            if capo > voicing_opts.max_fret {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        format!(
+                            "the capo (fret {capo}) cannot be placed beyond the maximal fret ({})",
+                            voicing_opts.max_fret
+                        ),
+                    )
+                    .exit();
+            }
+
+            if all && format == ChartFormat::Svg {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "the argument '--all' cannot be used with '--format svg'",
+                    )
+                    .exit();
+            }
+
+            let chord = chord.transpose(voicing_opts.transpose).respell(spell);
 
+            // With a capo, the shapes are searched relative to the capo, i.e. for
+            // the chord transposed down by `capo` semitones with the capo as the nut.
+            let shape_chord = chord.transpose(-(capo as i8));
             let config = VoicingConfig {
                 tuning,
-                min_fret: voicing_opts.min_fret,
-                max_fret: voicing_opts.max_fret,
+                min_fret: voicing_opts.min_fret.saturating_sub(capo),
+                max_fret: voicing_opts.max_fret - capo,
                 max_span: voicing_opts.max_span,
+                sort_by_difficulty: sort_by == VoicingOrder::Difficulty,
+                lowest_note: voicing_opts
+                    .lowest_note()
+                    .and_then(|note| note.checked_sub(capo)),
+                // Without a counterpart below the capo, no note is low enough.
+                highest_note: voicing_opts
+                    .highest_note()
+                    .map(|note| note.checked_sub(capo).unwrap_or_else(|| Note::from_midi(0))),
+                drone: voicing_opts.drone(tuning),
+                drone_chord_tone: voicing_opts.drone_chord_tone,
+                root_in_bass: voicing_opts.root_in_bass,
+                max_open_strings: voicing_opts.max_open_strings,
+                ..Default::default()
+            };
+
+            // Without --all, there is no need to determine all the voicings
+            // (unless they have to be sorted by their span or counted).
+            let mut voicings = match all || count || sort_by == VoicingOrder::Span {
+                true => shape_chord.voicings(config).collect(),
+                false => shape_chord.voicings_capped(config, 1),
+            };
+            if sort_by == VoicingOrder::Span {
+                // The sort is stable, so voicings with the same span stay ordered by position.
+                voicings.sort_by_key(|voicing| voicing.get_span());
+            }
+            let voicings: Vec<Voicing> = voicings
+                .into_iter()
+                .filter({
+                    let mut shapes = HashSet::new();
+                    move |voicing| !no_duplicate_shapes || shapes.insert(voicing.shape())
+                })
+                .map(|voicing| voicing.capo_shape(capo, &chord).respell(spell))
+                .collect();
+            let voicing_count = voicings.len();
+
+            if count {
+                match output {
+                    OutputFormat::Json => print_json(&ChartCountOutput {
+                        chord,
+                        count: voicing_count,
+                    }),
+                    _ => println!("{voicing_count}"),
+                }
+                return;
+            }
+
+            // Without --all, only the first voicing is printed.
+            let max_results = match all {
+                true => max_results.unwrap_or(voicing_count),
+                false => 1,
             };
+            // Number of charts that are actually printed.
+            let chart_count = voicing_count.min(max_results);
+
+            if output == OutputFormat::Json {
+                let voicings = voicings
+                    .into_iter()
+                    .take(max_results)
+                    .map(|voicing| VoicingOutput::new(voicing, tuning))
+                    .collect();
+                print_json(&ChartOutput { chord, voicings });
+                return;
+            }
 
-            let mut voicings = chord.voicings(config).peekable();
+            if midi {
+                for voicing in voicings.iter().take(max_results) {
+                    println!("{}", voicing.notes().map(|n| n.midi_number()).join(" "));
+                }
+                return;
+            }
+
+            if fingers {
+                for voicing in voicings.iter().take(max_results) {
+                    let fingers = voicing
+                        .fret_pattern()
+                        .zip(voicing.fingers_on_strings())
+                        .map(|(fret, finger)| match fret {
+                            Some(_) => finger.to_string(),
+                            None => "x".to_string(),
+                        });
+                    println!("{}", fingers.format(" "));
+                }
+                return;
+            }
 
-            if voicings.peek().is_none() {
-                println!("No matching chord voicing was found");
-            } else {
+            if voicings.is_empty() {
+                match format {
+                    // Keep the standard output free of anything but SVG.
+                    ChartFormat::Svg => eprintln!("No matching chord voicing was found"),
+                    ChartFormat::Text => println!("No matching chord voicing was found"),
+                }
+            } else if (!all || max_results == 0) && format == ChartFormat::Text {
                 println!("[{chord}]\n");
             }
 
-            for voicing in voicings {
-                let chart = ChordChart::new(voicing, voicing_opts.max_span);
-                println!("{chart}");
+            let mut blocks = vec![];
+            for (i, voicing) in voicings.into_iter().take(max_results).enumerate() {
+                let chart = ChordChart::new(&chord, voicing, voicing_opts.max_span)
+                    .with_left_handed(left_handed)
+                    .with_style(style)
+                    .with_window(window)
+                    .with_notes(!no_notes)
+                    .with_tuning(show_tuning)
+                    .with_intervals(intervals);
 
-                if !all {
-                    break;
+                match format {
+                    ChartFormat::Text if all && per_row > 1 => {
+                        let label = format!("[{chord}] ({}/{chart_count})", i + 1);
+                        blocks.push(
+                            vec![label, String::new()]
+                                .into_iter()
+                                .chain(chart.lines())
+                                .collect(),
+                        );
+                    }
+                    ChartFormat::Text => {
+                        // When printing all voicings, label each chart with the chord name
+                        // so that the charts are self-describing.
+                        if all {
+                            println!("[{chord}] ({}/{chart_count})\n", i + 1);
+                        }
+
+                        println!("{chart}");
+                    }
+                    ChartFormat::Svg => print!("{}", chart.to_svg()),
                 }
             }
+
+            for row in blocks.chunks(per_row as usize) {
+                println!("{}\n", side_by_side(row, CHART_GAP).join("\n"));
+            }
         }
-        Subcommand::Name { fret_pattern } => {
+        Subcommand::Name {
+            fret_pattern,
+            verbose,
+        } => {
+            check_fret_pattern(&fret_pattern, tuning);
+
             let voicing = Voicing::new(fret_pattern, tuning);
             let chords = voicing.get_chords();
 
+            if output == OutputFormat::Json {
+                print_json(&chords);
+                return;
+            }
+
             if chords.is_empty() {
                 println!("No matching chord was found");
             }
 
+            if verbose {
+                let explanations = chords
+                    .iter()
+                    .map(|chord| format!("{chord}\n{}", voicing.explain(chord)));
+                println!("{}", explanations.format("\n\n"));
+                return;
+            }
+
             for chord in chords {
                 println!("{chord}");
             }
         }
+        Subcommand::Diff { from, to } => {
+            check_fret_pattern(&from, tuning);
+            check_fret_pattern(&to, tuning);
+
+            let from = Voicing::new(from, tuning);
+            let to = Voicing::new(to, tuning);
+            let distance = Distance::between(&from, &to);
+
+            println!("{}", from.explain_diff(&to));
+            println!("{distance}, total cost: {}", distance.cost());
+        }
         Subcommand::VoiceLead {
             voicing_opts,
+            merge_repeats,
+            format,
+            num_paths,
+            show_cost,
+            fret_weight,
+            fingering_weight,
+            position_weight,
+            key,
+            nashville,
+            check_key,
+            start,
             chord_seq,
         } => {
+            let parsed = match (nashville, key) {
+                (true, Some(key)) => ChordSequence::from_nashville(&chord_seq, key.root),
+                _ => ChordSequence::from_str(&chord_seq),
+            };
+            let chord_seq = parsed.unwrap_or_else(|err| {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!("invalid value '{chord_seq}' for '<CHORD_SEQUENCE>': {err}"),
+                    )
+                    .exit()
+            });
+            if chord_seq.chords().len() == 0 {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        "the chord sequence is empty (it should contain at least one chord)",
+                    )
+                    .exit();
+            }
+
+            let chord_seq = match merge_repeats {
+                true => chord_seq.merge_repeats(),
+                false => chord_seq,
+            };
+
+            if let (true, Some(key)) = (check_key, key) {
+                let key_name = match key.scale_type {
+                    ScaleType::NaturalMinor => format!("{} minor", key.root),
+                    _ => format!("{} major", key.root),
+                };
+
+                for (chord, is_diatonic) in chord_seq.chords().zip(chord_seq.diatonic_chords(key)) {
+                    if !is_diatonic {
+                        eprintln!(
+                            "warning: chord {} is not diatonic to {key_name}",
+                            chord.name()
+                        );
+                    }
+                }
+            }
+
             let chord_seq = chord_seq.transpose(voicing_opts.transpose);
 
             let config = VoicingConfig {
@@ -159,21 +930,120 @@ fn main() {
                 min_fret: voicing_opts.min_fret,
                 max_fret: voicing_opts.max_fret,
                 max_span: voicing_opts.max_span,
+                lowest_note: voicing_opts.lowest_note(),
+                highest_note: voicing_opts.highest_note(),
+                drone: voicing_opts.drone(tuning),
+                drone_chord_tone: voicing_opts.drone_chord_tone,
+                root_in_bass: voicing_opts.root_in_bass,
+                max_open_strings: voicing_opts.max_open_strings,
+                ..Default::default()
             };
 
-            let mut voicing_graph = VoicingGraph::new(config);
-            voicing_graph.add(&chord_seq);
+            let weights = DistanceWeights {
+                fret_movement: fret_weight,
+                fingering: fingering_weight,
+                position: position_weight,
+            };
+
+            let mut voicing_graph = VoicingGraph::new(config, weights);
+            match start {
+                Some(start) => {
+                    check_fret_pattern(&start, tuning);
+
+                    let start = Voicing::new(start, tuning);
+                    if let Err(err) = voicing_graph.add_with_start(&chord_seq, start) {
+                        Ukebox::command()
+                            .error(ErrorKind::ValueValidation, err.to_string())
+                            .exit();
+                    }
+                }
+                None => voicing_graph.add(&chord_seq),
+            }
+
+            if output == OutputFormat::Json || format == VoiceLeadFormat::Json {
+                // Unless a key is given, use the root of the first chord as the reference
+                // for the Roman numerals.
+                let key = match key {
+                    Some(key) => Some(key.root.transpose(voicing_opts.transpose)),
+                    None => chord_seq.chords().next().map(|chord| chord.root),
+                };
+
+                let steps: Vec<VoiceLeadOutput> = voicing_graph
+                    .paths(1)
+                    .flat_map(|(path, _dist)| {
+                        let prev_voicings = iter::once(None).chain(path.iter().map(Some));
+
+                        chord_seq
+                            .chords()
+                            .zip(path.iter().zip(prev_voicings))
+                            .map(|(chord, (voicing, prev_voicing))| VoiceLeadOutput {
+                                chord: chord.clone(),
+                                voicing: VoicingOutput::new(voicing.clone(), tuning),
+                                roman_numeral: chord.roman_numeral(key.unwrap()),
+                                distance_from_previous: prev_voicing
+                                    .map(|prev| prev.distance(voicing).into()),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                print_json(&steps);
+                return;
+            }
 
             let mut path_found = false;
 
-            for (path, _dist) in voicing_graph.paths(1) {
-                for (chord, voicing) in chord_seq.chords().zip(path.iter()) {
-                    println!("[{chord}]\n");
-                    let chart = ChordChart::new(*voicing, voicing_opts.max_span);
-                    println!("{chart}");
+            let num_paths = match format {
+                VoiceLeadFormat::Text => num_paths as usize,
+                _ => 1,
+            };
+
+            for (i, (path, dist)) in voicing_graph.paths(num_paths).enumerate() {
+                // Label the voice leadings if there are several ones to compare.
+                if num_paths > 1 {
+                    if i > 0 {
+                        println!("---------------------------\n");
+                    }
+                    println!("Path {} ({dist})\n", i + 1);
+                }
+
+                match format {
+                    VoiceLeadFormat::MusicXml => {
+                        print!("{}", to_musicxml(chord_seq.chords().zip(path.iter())));
+                    }
+                    VoiceLeadFormat::ChordPro => {
+                        print!("{}", to_chordpro(chord_seq.chords().zip(path.iter())));
+                    }
+                    VoiceLeadFormat::Tab => {
+                        let chords = chord_seq.chords().zip(chord_seq.annotations());
+                        print!(
+                            "{}",
+                            to_tab(chords.zip(path.iter()).map(
+                                |((chord, annotation), voicing)| (chord, annotation, voicing)
+                            ))
+                        );
+                    }
+                    _ => {
+                        let prev_voicings = iter::once(None).chain(path.iter().map(Some));
+
+                        for (chord, (voicing, prev_voicing)) in
+                            chord_seq.chords().zip(path.iter().zip(prev_voicings))
+                        {
+                            if let (true, Some(prev)) = (show_cost, prev_voicing) {
+                                println!("-> ({})\n", prev.distance(voicing));
+                            }
+
+                            println!("[{chord}]\n");
+                            let chart =
+                                ChordChart::new(chord, voicing.clone(), voicing_opts.max_span)
+                                    .with_left_handed(left_handed);
+                            println!("{chart}");
+                        }
+
+                        if show_cost {
+                            println!("Total ({dist})\n");
+                        }
+                    }
                 }
-                //println!("{:?}\n", dist);
-                //println!("---------------------------\n");
 
                 path_found = true;
             }
@@ -182,5 +1052,180 @@ fn main() {
                 println!("No matching chord voicing sequence was found");
             }
         }
+        Subcommand::OpenChords {
+            max_pressed,
+            max_fret,
+        } => {
+            let string_count = tuning.string_count();
+
+            if max_pressed as usize > string_count {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!("cannot press down {max_pressed} strings with only {string_count} strings in tuning {tuning}"),
+                    )
+                    .exit();
+            }
+
+            let config = VoicingConfig {
+                tuning,
+                max_fret,
+                ..Default::default()
+            };
+            let chords = Chord::open_chords(config, max_pressed as usize);
+
+            if chords.is_empty() {
+                println!("No matching chord was found");
+            }
+
+            for chord in chords {
+                println!("{chord}");
+            }
+        }
+        Subcommand::Suggest {
+            min_fret,
+            max_fret,
+            max_span,
+            open,
+        } => {
+            let open_strings: Vec<usize> = tuning
+                .roots()
+                .positions(|root| open.contains(&root))
+                .collect();
+
+            let config = VoicingConfig {
+                tuning,
+                min_fret,
+                max_fret,
+                max_span,
+                ..Default::default()
+            };
+            let chords = Chord::playable_chords(config, &open_strings);
+
+            if chords.is_empty() {
+                println!("No matching chord was found");
+            }
+
+            for (chord, voicing) in chords {
+                let frets = voicing.frets().collect_vec();
+                let separator = match frets.iter().any(|&fret| fret > 9) {
+                    true => " ",
+                    false => "",
+                };
+                println!("{chord}: {}", frets.iter().join(separator));
+            }
+        }
+        Subcommand::Scale {
+            min_fret,
+            max_fret,
+            root,
+            scale_type,
+        } => {
+            let scale = Scale::new(root, scale_type);
+            let notes = scale.notes().join(" ");
+            println!("{scale}: {notes}\n");
+
+            let positions = scale.positions(tuning, min_fret, max_fret);
+            let roots: Vec<Note> = tuning.roots().collect();
+            let root_width = roots.iter().map(|n| n.to_string().len()).max().unwrap();
+
+            // Show the strings in the same order as in a chord chart.
+            for root in roots.iter().rev() {
+                let frets = positions
+                    .iter()
+                    .filter(|(r, _f, _n)| r.midi_number() == root.midi_number())
+                    .map(|(_r, f, n)| format!("{f}:{n}"))
+                    .join("  ");
+                let root_str = root.to_string();
+                println!("{root_str:root_width$}  {frets}");
+            }
+        }
+        Subcommand::Fretboard { max_fret } => {
+            let fretboard = Fretboard::new(tuning, max_fret);
+            print!("{fretboard}");
+        }
+        Subcommand::Find {
+            min_fret,
+            max_fret,
+            note,
+        } => {
+            let positions = tuning.positions_of(note.pitch_class, min_fret, max_fret);
+            let fretboard = Fretboard::new(tuning, max_fret)
+                .with_positions(positions)
+                .with_spelling(note.spelling());
+            print!("{fretboard}");
+        }
+        Subcommand::Analyze { key, chord_seq } => {
+            let key = key.or_else(|| chord_seq.chords().next().map(|chord| chord.root));
+            let numerals = key.map(|key| chord_seq.analyze(key)).unwrap_or_default();
+
+            println!("{}", numerals.join(" "));
+        }
+        Subcommand::Relative { chord } => {
+            let relative = chord.relative_minor().or_else(|| chord.relative_major());
+
+            match relative {
+                Some(relative) => println!("{}", relative.name()),
+                None => Ukebox::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!(
+                            "chord {} has neither a relative minor nor a relative major chord",
+                            chord.name()
+                        ),
+                    )
+                    .exit(),
+            }
+        }
+        Subcommand::Capo {
+            voicing_opts,
+            chord_seq,
+        } => {
+            if chord_seq.chords().len() == 0 {
+                Ukebox::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        "the chord sequence is empty (it should contain at least one chord)",
+                    )
+                    .exit();
+            }
+
+            let chord_seq = chord_seq.transpose(voicing_opts.transpose);
+
+            let config = VoicingConfig {
+                tuning,
+                min_fret: voicing_opts.min_fret,
+                max_fret: voicing_opts.max_fret,
+                max_span: voicing_opts.max_span,
+                lowest_note: voicing_opts.lowest_note(),
+                highest_note: voicing_opts.highest_note(),
+                drone: voicing_opts.drone(tuning),
+                drone_chord_tone: voicing_opts.drone_chord_tone,
+                root_in_bass: voicing_opts.root_in_bass,
+                max_open_strings: voicing_opts.max_open_strings,
+                ..Default::default()
+            };
+
+            match chord_seq.suggest_capo(config) {
+                Some((0, shapes)) => println!(
+                    "No capo needed: {}",
+                    shapes.chords().map(|chord| chord.name()).join(" ")
+                ),
+                Some((capo, shapes)) => println!(
+                    "Capo on fret {capo}: {}",
+                    shapes.chords().map(|chord| chord.name()).join(" ")
+                ),
+                None => println!("No matching capo position was found"),
+            }
+        }
+        Subcommand::Transpose { by, to, chord_seq } => {
+            // Either --by or --to is given.
+            let chord_seq = match to {
+                Some(root) => chord_seq.transpose_to(root),
+                None => chord_seq.transpose(by.unwrap_or_default()),
+            };
+
+            println!("{chord_seq}");
+        }
     }
 }
@@ -1,33 +1,184 @@
-use std::{
+use alloc::string::{String, ToString};
+use core::{
     fmt,
     ops::{Add, Sub},
     str::FromStr,
 };
 
-use crate::{Interval, PitchClass, Semitones, StaffPosition};
+#[cfg(feature = "std")]
+use clap::ValueEnum;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Interval, Octave, PitchClass, Semitones, StaffPosition, PITCH_CLASS_COUNT};
+
+/// The octave of notes for which no octave has been specified
+/// (the octave containing the middle C).
+const DEFAULT_OCTAVE: Octave = 4;
+
+/// Highest MIDI note number (G9).
+const MAX_MIDI_NUMBER: u16 = 127;
 
 /// Custom error for strings that cannot be parsed into notes.
-#[derive(Debug, thiserror::Error)]
-#[error("could not parse note name '{name}'")]
+#[derive(Debug)]
 pub struct ParseNoteError {
     name: String,
 }
 
+impl fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse note name '{}'", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseNoteError {}
+
+/// How notes that can be sharp or flat (e.g. C# and Db) are spelled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Spelling {
+    /// Spell black notes as sharps, e.g. F# rather than Gb.
+    Sharps,
+    /// Spell black notes as flats, e.g. Gb rather than F#.
+    Flats,
+    /// Keep the spelling derived from the key or the chord root.
+    #[default]
+    #[cfg_attr(feature = "std", value(name = "key"))]
+    FromKey,
+}
+
 /// A note such a C, C# and so on.
+///
+/// Each note is placed in an octave. The octave is determined by the pitch of
+/// the note rather than by its spelling, i.e. `B#3` and `C4` are the same note.
+/// The octave does not matter when comparing notes for equality (see below).
 #[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord)]
 pub struct Note {
     pub pitch_class: PitchClass,
     staff_position: StaffPosition,
+    octave: Octave,
 }
 
 impl Note {
+    /// Create a note in the octave containing the middle C.
     pub fn new(pitch_class: PitchClass, staff_position: StaffPosition) -> Self {
         Self {
             pitch_class,
             staff_position,
+            octave: DEFAULT_OCTAVE,
         }
     }
 
+    pub fn octave(&self) -> Octave {
+        self.octave
+    }
+
+    /// Return the staff position of the note, which determines its spelling,
+    /// e.g. `CPos` for C# and `DPos` for Db.
+    pub fn staff_position(&self) -> StaffPosition {
+        self.staff_position
+    }
+
+    /// Return `true` if both notes have the same pitch class regardless of
+    /// their spelling and octave, e.g. C# and Db.
+    ///
+    /// This is what `==` checks for notes. Use `is_spelled_like` to also
+    /// take the spelling into account.
+    pub fn is_enharmonic(&self, other: &Self) -> bool {
+        self.pitch_class == other.pitch_class
+    }
+
+    /// Return `true` if both notes have the same pitch class and the same
+    /// spelling (regardless of their octave), e.g. C# and C# but not C# and Db.
+    pub fn is_spelled_like(&self, other: &Self) -> bool {
+        self.is_enharmonic(other) && self.staff_position == other.staff_position
+    }
+
+    /// Return the same note placed in the given octave.
+    pub fn with_octave(self, octave: Octave) -> Self {
+        Self { octave, ..self }
+    }
+
+    /// Return the octave of the note that is `n` semitones higher
+    /// (or lower if `n` is negative) than the current note.
+    fn shift_octave(&self, n: i16) -> Octave {
+        let pitch_class_count = PITCH_CLASS_COUNT as i16;
+        let pitch = self.octave as i16 * pitch_class_count + self.pitch_class as i16 + n;
+
+        // There are no notes below C0.
+        (pitch.max(0) / pitch_class_count) as Octave
+    }
+
+    /// Parse a note with an optional octave, e.g. `G3` or `Bb`. Notes without
+    /// an octave are placed in the octave containing the middle C.
+    /// Notes above G9 (which have no MIDI note number) are rejected.
+    pub fn parse_with_octave(s: &str) -> Result<Self, ParseNoteError> {
+        let error = || ParseNoteError {
+            name: s.to_string(),
+        };
+
+        match s.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => {
+                let octave = s[i..].parse::<Octave>().map_err(|_| error())?;
+                let note = Self::from_str(&s[..i]).map_err(|_| error())?;
+
+                // Only accept notes that have a MIDI note number.
+                let midi_number =
+                    (octave as u16 + 1) * PITCH_CLASS_COUNT as u16 + note.pitch_class as u16;
+                if midi_number > MAX_MIDI_NUMBER {
+                    return Err(error());
+                }
+
+                Ok(note.with_octave(octave))
+            }
+            None => Self::from_str(s),
+        }
+    }
+
+    /// Return the MIDI note number of the note, e.g. 60 for the middle C (C4)
+    /// and 69 for A4.
+    pub fn midi_number(&self) -> u8 {
+        (self.octave + 1) * PITCH_CLASS_COUNT + self.pitch_class as u8
+    }
+
+    /// Return the note with the MIDI note number `note`, e.g. C4 for 60
+    /// (the middle C) or A4 for 69. Notes that can be sharp or flat are
+    /// spelled as sharps.
+    ///
+    /// As there are no notes below C0, the MIDI note numbers 0 to 11
+    /// (octave -1) are placed in octave 0.
+    pub fn from_midi(note: u8) -> Self {
+        let octave = (note / PITCH_CLASS_COUNT).saturating_sub(1);
+        Self::from(PitchClass::from_midi(note)).with_octave(octave)
+    }
+
+    /// Return the note `n` semitones lower than the current note, or `None`
+    /// if that note would be lower than C0.
+    pub fn checked_sub(self, n: Semitones) -> Option<Self> {
+        match self.midi_number().checked_sub(n) {
+            Some(midi_number) if midi_number >= PITCH_CLASS_COUNT => Some(self - n),
+            _ => None,
+        }
+    }
+
+    /// Return the note that is `semitones` higher (or lower if negative)
+    /// than the current note.
+    pub fn transpose(self, semitones: i8) -> Self {
+        match semitones {
+            s if s < 0 => self - semitones.unsigned_abs() as Semitones,
+            _ => self + semitones as Semitones,
+        }
+    }
+
+    /// Return the frequency of the note in Hz in equal temperament with
+    /// A4 tuned to `a4` Hz (usually 440.0).
+    #[cfg(feature = "std")]
+    pub fn frequency(&self, a4: f64) -> f64 {
+        let semitones_from_a4 = self.midi_number() as f64 - 69.0;
+        a4 * 2f64.powf(semitones_from_a4 / PITCH_CLASS_COUNT as f64)
+    }
+
     /// Return `true` if this note is a "white note", i.e. a note represented
     /// by a white key on the piano (i.e. the note is part of the C major scale).
     pub fn is_white_note(&self) -> bool {
@@ -35,10 +186,47 @@ impl Note {
 
         matches!(self.pitch_class, C | D | E | F | G | A | B)
     }
+
+    /// Return the spelling implied by the note's accidental, i.e. flats for
+    /// flat notes (e.g. Bb) and sharps for sharp notes (e.g. C#).
+    /// Natural notes keep the spelling derived from the key.
+    pub fn spelling(&self) -> Spelling {
+        match self.to_string().chars().nth(1) {
+            Some('b') => Spelling::Flats,
+            Some('#') => Spelling::Sharps,
+            _ => Spelling::FromKey,
+        }
+    }
+
+    /// Return the same note spelled according to `spelling`.
+    pub fn respell(self, spelling: Spelling) -> Self {
+        use PitchClass::*;
+        use StaffPosition::*;
+
+        let staff_position = match spelling {
+            Spelling::FromKey => return self,
+            Spelling::Sharps => Self::from(self.pitch_class).staff_position,
+            Spelling::Flats => match self.pitch_class {
+                C => CPos,
+                CSharp | D => DPos,
+                DSharp | E => EPos,
+                F => FPos,
+                FSharp | G => GPos,
+                GSharp | A => APos,
+                ASharp | B => BPos,
+            },
+        };
+
+        Self {
+            staff_position,
+            ..self
+        }
+    }
 }
 
 impl PartialEq for Note {
-    /// Treat two notes as equal if they have the same pitch class.
+    /// Treat two notes as equal if they have the same pitch class, i.e. if they
+    /// are enharmonic (see `is_enharmonic`). The spelling and the octave are ignored.
     /// For example, `B sharp`, `C` and `D double flat` should all match.
     fn eq(&self, other: &Self) -> bool {
         self.pitch_class == other.pitch_class
@@ -111,21 +299,21 @@ impl FromStr for Note {
 
         let (pitch_class, staff_position) = match s {
             "C" => (C, CPos),
-            "C#" => (CSharp, CPos),
-            "Db" => (CSharp, DPos),
+            "C#" | "C♯" => (CSharp, CPos),
+            "Db" | "D♭" => (CSharp, DPos),
             "D" => (D, DPos),
-            "D#" => (DSharp, DPos),
-            "Eb" => (DSharp, EPos),
+            "D#" | "D♯" => (DSharp, DPos),
+            "Eb" | "E♭" => (DSharp, EPos),
             "E" => (E, EPos),
             "F" => (F, FPos),
-            "F#" => (FSharp, FPos),
-            "Gb" => (FSharp, GPos),
+            "F#" | "F♯" => (FSharp, FPos),
+            "Gb" | "G♭" => (FSharp, GPos),
             "G" => (G, GPos),
-            "G#" => (GSharp, GPos),
-            "Ab" => (GSharp, APos),
+            "G#" | "G♯" => (GSharp, GPos),
+            "Ab" | "A♭" => (GSharp, APos),
             "A" => (A, APos),
-            "A#" => (ASharp, APos),
-            "Bb" => (ASharp, BPos),
+            "A#" | "A♯" => (ASharp, APos),
+            "Bb" | "B♭" => (ASharp, BPos),
             "B" => (B, BPos),
             _ => return Err(ParseNoteError { name }),
         };
@@ -155,14 +343,33 @@ impl From<PitchClass> for Note {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Note {
+    /// Serialize the note by its name, e.g. `"Eb"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Note {
+    /// Deserialize the note from its name, e.g. `"Eb"`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
 impl Add<Interval> for Note {
     type Output = Self;
 
     /// Get the next note when adding `interval` to the current note.
     fn add(self, interval: Interval) -> Self {
-        let pitch_class = self.pitch_class + interval.to_semitones();
+        let semitones = interval.to_semitones();
+        let pitch_class = self.pitch_class + semitones;
         let staff_position = self.staff_position + (interval.to_number() - 1);
-        Self::new(pitch_class, staff_position)
+        let octave = self.shift_octave(semitones as i16);
+        Self::new(pitch_class, staff_position).with_octave(octave)
     }
 }
 
@@ -171,16 +378,17 @@ impl Add<Semitones> for Note {
 
     fn add(self, n: Semitones) -> Self {
         let note = Self::from(self.pitch_class + n);
+        let octave = self.shift_octave(n as i16);
 
         // Make sure the staff position stays the same if the pitch class
         // stays the same (e.g. when adding 0 or 12 semitones).
         if note.pitch_class == self.pitch_class {
-            return Self::new(self.pitch_class, self.staff_position);
+            return self.with_octave(octave);
         }
 
         // Otherwise, the staff position will by default be chosen so that
         // sharp/flat notes turn out sharp (e.g. C + 1 = C#).
-        note
+        note.with_octave(octave)
     }
 }
 
@@ -189,11 +397,12 @@ impl Sub<Semitones> for Note {
 
     fn sub(self, n: Semitones) -> Self {
         let note = Self::from(self.pitch_class - n);
+        let octave = self.shift_octave(-(n as i16));
 
         // Make sure the staff position stays the same if the pitch class
         // stays the same (e.g. when subtracting 0 or 12 semitones).
         if note.pitch_class == self.pitch_class {
-            return Self::new(self.pitch_class, self.staff_position);
+            return self.with_octave(octave);
         }
 
         // Otherwise, make sure that the staff position will be chosen so that
@@ -203,12 +412,56 @@ impl Sub<Semitones> for Note {
             _ => note.staff_position + 1,
         };
 
-        Self::new(note.pitch_class, staff_position)
+        Self::new(note.pitch_class, staff_position).with_octave(octave)
+    }
+}
+
+impl Sub for Note {
+    type Output = Interval;
+
+    /// Get the (simple) interval from `other` up to the current note.
+    ///
+    /// The interval is spelled according to the staff positions of the notes,
+    /// e.g. C to Eb is a minor third whereas C to D# is an augmented second.
+    /// Octaves are ignored. For the rare combinations without a corresponding
+    /// `Interval` (such as a doubly augmented interval), the more common interval
+    /// with the same number of semitones is returned.
+    fn sub(self, other: Self) -> Interval {
+        use Interval::*;
+
+        let staff_steps = self.staff_position - other.staff_position;
+        let semitones = self.pitch_class - other.pitch_class;
+
+        match (staff_steps, semitones) {
+            (0, 0) => PerfectUnison,
+            (0, 1) => AugmentedUnison,
+            (1, 1) => MinorSecond,
+            (1, 2) => MajorSecond,
+            (1, 3) => AugmentedSecond,
+            (2, 2) => DiminishedThird,
+            (2, 3) => MinorThird,
+            (2, 4) => MajorThird,
+            (3, 4) => DiminishedFourth,
+            (3, 5) => PerfectFourth,
+            (3, 6) => AugmentedFourth,
+            (4, 6) => DiminishedFifth,
+            (4, 7) => PerfectFifth,
+            (4, 8) => AugmentedFifth,
+            (5, 8) => MinorSixth,
+            (5, 9) => MajorSixth,
+            (5, 10) => AugmentedSixth,
+            (6, 9) => DiminishedSeventh,
+            (6, 10) => MinorSeventh,
+            (6, 11) => MajorSeventh,
+            _ => Interval::from(semitones),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+
     use rstest::rstest;
     use Interval::*;
     use PitchClass::*;
@@ -240,6 +493,25 @@ mod tests {
         assert_eq!(format!("{note}"), s);
     }
 
+    #[rstest(
+        s,
+        name,
+        case("C♯", "C#"),
+        case("E♭", "Eb"),
+        case("F♯", "F#"),
+        case("B♭", "Bb")
+    )]
+    fn test_from_str_unicode_accidentals(s: &str, name: &str) {
+        let note = Note::from_str(s).unwrap();
+        assert_eq!(note.to_string(), name);
+        assert!(note.is_spelled_like(&Note::from_str(name).unwrap()));
+    }
+
+    #[rstest(s, case("c"), case("c#"), case("C♮"), case("♯C"), case("B♯"))]
+    fn test_from_str_fail(s: &str) {
+        assert!(Note::from_str(s).is_err());
+    }
+
     #[rstest(
         note,
         is_white_note,
@@ -285,6 +557,23 @@ mod tests {
         assert_eq!(Note::from(pitch_class), note);
     }
 
+    #[rstest(
+        note,
+        spelling,
+        respelled,
+        case("C#", Spelling::Flats, "Db"),
+        case("Db", Spelling::Sharps, "C#"),
+        case("F#", Spelling::Flats, "Gb"),
+        case("Gb", Spelling::FromKey, "Gb"),
+        case("D", Spelling::Flats, "D"),
+        case("D", Spelling::Sharps, "D"),
+        case("Bb", Spelling::Flats, "Bb"),
+        case("A#", Spelling::FromKey, "A#")
+    )]
+    fn test_respell(note: Note, spelling: Spelling, respelled: &str) {
+        assert_eq!(note.respell(spelling).to_string(), respelled);
+    }
+
     #[rstest(
         note1,
         interval,
@@ -339,4 +628,241 @@ mod tests {
     fn test_subtract_semitones(note1: Note, n: Semitones, note2: Note) {
         assert_eq!(note1 - n, note2);
     }
+
+    #[rstest(
+        note1,
+        note2,
+        interval,
+        case("C", "C", PerfectUnison),
+        case("C", "C#", AugmentedUnison),
+        case("C", "Db", MinorSecond),
+        case("C", "Eb", MinorThird),
+        case("C", "D#", AugmentedSecond),
+        case("C", "E", MajorThird),
+        case("C", "F#", AugmentedFourth),
+        case("C", "Gb", DiminishedFifth),
+        case("C", "G#", AugmentedFifth),
+        case("C", "Ab", MinorSixth),
+        case("C", "Bb", MinorSeventh),
+        case("A", "C", MinorThird),
+        case("G", "F", MinorSeventh),
+        case("Bb", "D", MajorThird),
+        case("F#", "A#", MajorThird)
+    )]
+    fn test_subtract_notes(note1: Note, note2: Note, interval: Interval) {
+        assert_eq!(note2 - note1, interval);
+    }
+
+    #[rstest(
+        note1,
+        octave1,
+        interval,
+        octave2,
+        case("C", 4, PerfectUnison, 4),
+        case("C", 4, MajorSeventh, 4),
+        case("C", 4, MajorNinth, 5),
+        case("A", 4, MinorThird, 5),
+        case("G", 3, PerfectFourth, 4),
+        case("B", 3, MinorSecond, 4)
+    )]
+    fn test_add_interval_octave(note1: Note, octave1: Octave, interval: Interval, octave2: Octave) {
+        assert_eq!((note1.with_octave(octave1) + interval).octave(), octave2);
+    }
+
+    #[rstest(
+        note1,
+        octave1,
+        n,
+        octave2,
+        case("C", 4, 0, 4),
+        case("C", 4, 11, 4),
+        case("C", 4, 12, 5),
+        case("A", 4, 3, 5),
+        case("G", 3, 24, 5)
+    )]
+    fn test_add_semitones_octave(note1: Note, octave1: Octave, n: Semitones, octave2: Octave) {
+        assert_eq!((note1.with_octave(octave1) + n).octave(), octave2);
+    }
+
+    #[rstest(
+        note1,
+        octave1,
+        n,
+        octave2,
+        case("C", 4, 0, 4),
+        case("C", 4, 1, 3),
+        case("C", 4, 12, 3),
+        case("A", 4, 9, 4),
+        case("A", 4, 10, 3),
+        case("C", 0, 1, 0)
+    )]
+    fn test_subtract_semitones_octave(note1: Note, octave1: Octave, n: Semitones, octave2: Octave) {
+        assert_eq!((note1.with_octave(octave1) - n).octave(), octave2);
+    }
+
+    #[rstest(
+        note,
+        octave,
+        midi_number,
+        case("C", 4, 60),
+        case("A", 4, 69),
+        case("C", 0, 12),
+        case("G", 3, 55),
+        case("Bb", 4, 70)
+    )]
+    fn test_midi_number(note: Note, octave: Octave, midi_number: u8) {
+        assert_eq!(note.with_octave(octave).midi_number(), midi_number);
+    }
+
+    #[cfg(feature = "std")]
+    #[rstest(
+        note,
+        octave,
+        a4,
+        frequency,
+        case("A", 4, 440.0, 440.0),
+        case("A", 3, 440.0, 220.0),
+        case("A", 5, 440.0, 880.0),
+        case("C", 4, 440.0, 261.626),
+        case("A", 4, 432.0, 432.0)
+    )]
+    fn test_frequency(note: Note, octave: Octave, a4: f64, frequency: f64) {
+        let f = note.with_octave(octave).frequency(a4);
+        assert!((f - frequency).abs() < 0.001);
+    }
+
+    #[rstest(
+        midi_number,
+        note,
+        octave,
+        case(60, "C", 4),
+        case(69, "A", 4),
+        case(12, "C", 0),
+        case(55, "G", 3),
+        case(70, "A#", 4),
+        case(127, "G", 9)
+    )]
+    fn test_from_midi(midi_number: u8, note: Note, octave: Octave) {
+        let from_midi = Note::from_midi(midi_number);
+        assert_eq!(from_midi.to_string(), note.to_string());
+        assert_eq!(from_midi.octave(), octave);
+        assert_eq!(from_midi.midi_number(), midi_number);
+    }
+
+    #[rstest(
+        note1,
+        octave1,
+        n,
+        note2,
+        octave2,
+        case("C", 4, 0, "C", 4),
+        case("E", 4, 5, "B", 3),
+        case("C", 1, 12, "C", 0),
+        case("D", 0, 2, "C", 0)
+    )]
+    fn test_checked_sub(note1: Note, octave1: Octave, n: Semitones, note2: Note, octave2: Octave) {
+        let note = note1.with_octave(octave1).checked_sub(n).unwrap();
+        assert_eq!(note, note2);
+        assert_eq!(note.octave(), octave2);
+    }
+
+    #[rstest(note, octave, n, case("C", 0, 1), case("D", 0, 3), case("C", 1, 13))]
+    fn test_checked_sub_below_c0(note: Note, octave: Octave, n: Semitones) {
+        assert_eq!(note.with_octave(octave).checked_sub(n), None);
+    }
+
+    #[rstest(
+        note1,
+        semitones,
+        note2,
+        case("C", 0, "C"),
+        case("C", 2, "D"),
+        case("D", -2, "C"),
+        case("Bb", -1, "A"),
+        case("B", 13, "C")
+    )]
+    fn test_transpose(note1: Note, semitones: i8, note2: Note) {
+        assert_eq!(note1.transpose(semitones), note2);
+    }
+
+    #[test]
+    fn test_from_midi_below_c0() {
+        let note = Note::from_midi(11);
+        assert_eq!(note.pitch_class, PitchClass::B);
+        assert_eq!(note.octave(), 0);
+    }
+
+    #[rstest(
+        note1,
+        note2,
+        case("C#", "Db"),
+        case("D#", "Eb"),
+        case("F#", "Gb"),
+        case("G#", "Ab"),
+        case("A#", "Bb")
+    )]
+    fn test_is_enharmonic(note1: Note, note2: Note) {
+        assert!(note1.is_enharmonic(&note2));
+        assert!(note2.is_enharmonic(&note1));
+        assert_eq!(note1, note2);
+
+        // The notes are written at different staff positions.
+        assert_ne!(note1.staff_position(), note2.staff_position());
+        assert!(!note1.is_spelled_like(&note2));
+    }
+
+    #[rstest(
+        note1,
+        note2,
+        case("C", "C#"),
+        case("Db", "D"),
+        case("E", "F"),
+        case("B", "C")
+    )]
+    fn test_is_not_enharmonic(note1: Note, note2: Note) {
+        assert!(!note1.is_enharmonic(&note2));
+        assert!(!note1.is_spelled_like(&note2));
+    }
+
+    #[rstest(note, case("C"), case("C#"), case("Db"), case("Bb"))]
+    fn test_is_spelled_like(note: Note) {
+        assert!(note.is_spelled_like(&note));
+        assert!(note.is_spelled_like(&note.with_octave(2)));
+        assert_eq!(
+            note.respell(Spelling::FromKey).staff_position(),
+            note.staff_position()
+        );
+    }
+
+    #[rstest(
+        s,
+        note,
+        octave,
+        case("C", "C", 4),
+        case("G3", "G", 3),
+        case("Bb5", "Bb", 5),
+        case("F#0", "F#", 0),
+        case("G9", "G", 9)
+    )]
+    fn test_parse_with_octave(s: &str, note: Note, octave: Octave) {
+        let parsed = Note::parse_with_octave(s).unwrap();
+        assert_eq!(parsed.to_string(), note.to_string());
+        assert_eq!(parsed.octave(), octave);
+    }
+
+    #[rstest(
+        s,
+        case(""),
+        case("H3"),
+        case("G-1"),
+        case("C300"),
+        case("3"),
+        case("G#9"),
+        case("C10"),
+        case("C30"),
+        case("B20")
+    )]
+    fn test_parse_with_octave_error(s: &str) {
+        assert!(Note::parse_with_octave(s).is_err());
+    }
 }
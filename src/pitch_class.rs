@@ -1,5 +1,5 @@
 #![allow(clippy::suspicious_arithmetic_impl)]
-use std::ops::{Add, Sub};
+use core::ops::{Add, Sub};
 
 use crate::{Semitones, PITCH_CLASS_COUNT};
 
@@ -12,7 +12,7 @@ use crate::{Semitones, PITCH_CLASS_COUNT};
 /// higher octave.
 /// For example, pitch class 12 is the same as pitch class 0 and corresponds
 /// to the pitch class of C.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PitchClass {
     C,
     CSharp,
@@ -28,6 +28,26 @@ pub enum PitchClass {
     B,
 }
 
+impl PitchClass {
+    /// Return the pitch class represented by the integer `n` (from 0 for C
+    /// to 11 for B) or `None` if `n` is greater than 11.
+    ///
+    /// Unlike the conversion from `Semitones`, values are not wrapped around
+    /// into the range of pitch classes.
+    pub fn new(n: u8) -> Option<Self> {
+        match n < PITCH_CLASS_COUNT {
+            true => Some(Self::from(n)),
+            false => None,
+        }
+    }
+
+    /// Return the pitch class of the MIDI note number `note`,
+    /// e.g. `C` for 60 (the middle C) or `A` for 69.
+    pub fn from_midi(note: u8) -> Self {
+        Self::from(note)
+    }
+}
+
 impl From<Semitones> for PitchClass {
     /// Convert an integer into a pitch class.
     ///
@@ -85,7 +105,7 @@ impl Sub for PitchClass {
     ///
     /// Examples:
     /// * D - C: both pitch classes are assumed to be in the same octave, D being
-    ///          higher than C. The difference is 2.
+    ///   higher than C. The difference is 2.
     /// * D - A: D is higher than A, the difference is 5.
     fn sub(self, other: Self) -> Semitones {
         let d = self as i8 - other as i8;
@@ -183,4 +203,34 @@ mod tests {
     fn test_sub_int(pc1: PitchClass, n: Semitones, pc2: PitchClass) {
         assert_eq!(pc1 - n, pc2);
     }
+
+    #[rstest(
+        note,
+        pitch_class,
+        case(0, C),
+        case(12, C),
+        case(21, A),
+        case(60, C),
+        case(61, CSharp),
+        case(69, A),
+        case(70, ASharp),
+        case(127, G)
+    )]
+    fn test_from_midi(note: u8, pitch_class: PitchClass) {
+        assert_eq!(PitchClass::from_midi(note), pitch_class);
+    }
+
+    #[rstest(
+        n,
+        pitch_class,
+        case(0, Some(C)),
+        case(4, Some(E)),
+        case(11, Some(B)),
+        case(12, None),
+        case(60, None),
+        case(255, None)
+    )]
+    fn test_new(n: u8, pitch_class: Option<PitchClass>) {
+        assert_eq!(PitchClass::new(n), pitch_class);
+    }
 }
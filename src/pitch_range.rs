@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use crate::Note;
+
+/// Custom error for strings that cannot be parsed into pitch ranges.
+#[derive(Debug, thiserror::Error)]
+pub enum ParsePitchRangeError {
+    #[error(
+        "could not parse pitch range '{name}' (should be something like 'C4-A5', 'G4-' or '-C6')"
+    )]
+    InvalidFormat { name: String },
+    #[error("the lowest note of pitch range '{name}' is higher than its highest note")]
+    Reversed { name: String },
+}
+
+/// Range of pitches within which all the notes of a voicing must sound,
+/// given as `<LOWEST>-<HIGHEST>` (e.g. `C4-A5`). Either bound may be omitted
+/// (e.g. `G4-` or `-C6`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PitchRange {
+    pub lowest: Option<Note>,
+    pub highest: Option<Note>,
+}
+
+impl FromStr for PitchRange {
+    type Err = ParsePitchRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePitchRangeError::InvalidFormat {
+            name: s.to_string(),
+        };
+
+        let (lowest, highest) = s.split_once('-').ok_or_else(error)?;
+        let parse_bound = |bound: &str| match bound {
+            "" => Ok(None),
+            _ => Note::parse_with_octave(bound)
+                .map(Some)
+                .map_err(|_| error()),
+        };
+        let (lowest, highest) = (parse_bound(lowest)?, parse_bound(highest)?);
+
+        match (lowest, highest) {
+            (None, None) => Err(error()),
+            (Some(low), Some(high)) if low.midi_number() > high.midi_number() => {
+                Err(ParsePitchRangeError::Reversed {
+                    name: s.to_string(),
+                })
+            }
+            _ => Ok(Self { lowest, highest }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        s,
+        lowest,
+        highest,
+        case("C4-A5", Some(60), Some(81)),
+        case("G4-", Some(67), None),
+        case("-C6", None, Some(84)),
+        case("C4-C4", Some(60), Some(60))
+    )]
+    fn test_from_str(s: &str, lowest: Option<u8>, highest: Option<u8>) {
+        let range = PitchRange::from_str(s).unwrap();
+        assert_eq!(range.lowest.map(|n| n.midi_number()), lowest);
+        assert_eq!(range.highest.map(|n| n.midi_number()), highest);
+    }
+
+    #[rstest(s, case(""), case("-"), case("C4"), case("C4-X5"), case("H4-"))]
+    fn test_from_str_invalid_format(s: &str) {
+        assert!(matches!(
+            PitchRange::from_str(s),
+            Err(ParsePitchRangeError::InvalidFormat { .. })
+        ));
+    }
+
+    #[rstest(s, case("A5-C4"), case("C#4-C4"))]
+    fn test_from_str_reversed(s: &str) {
+        assert_eq!(
+            PitchRange::from_str(s).unwrap_err().to_string(),
+            format!("the lowest note of pitch range '{s}' is higher than its highest note")
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::{fmt, str::FromStr};
+
+use clap::ValueEnum;
+
+use crate::{FretID, Interval, Note, Tuning, UkeString};
+
+/// Custom error for strings that cannot be parsed into keys.
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse key '{name}' (should be something like 'C' or 'Am')")]
+pub struct ParseKeyError {
+    name: String,
+}
+
+/// The type of a scale depending on the intervals it contains.
+/// https://en.wikipedia.org/wiki/Scale_(music)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScaleType {
+    #[value(alias = "ionian")]
+    Major,
+    /// The natural minor scale (aeolian mode).
+    #[value(name = "minor", alias = "natural-minor", alias = "aeolian")]
+    NaturalMinor,
+    HarmonicMinor,
+    /// The ascending melodic minor scale.
+    MelodicMinor,
+    MajorPentatonic,
+    MinorPentatonic,
+}
+
+impl ScaleType {
+    /// Return an iterator over the intervals (counted from the root)
+    /// that make up the scale.
+    pub fn intervals(self) -> impl Iterator<Item = Interval> {
+        use Interval::*;
+        use ScaleType::*;
+
+        let intervals = match self {
+            Major => vec![
+                PerfectUnison,
+                MajorSecond,
+                MajorThird,
+                PerfectFourth,
+                PerfectFifth,
+                MajorSixth,
+                MajorSeventh,
+            ],
+            NaturalMinor => vec![
+                PerfectUnison,
+                MajorSecond,
+                MinorThird,
+                PerfectFourth,
+                PerfectFifth,
+                MinorSixth,
+                MinorSeventh,
+            ],
+            HarmonicMinor => vec![
+                PerfectUnison,
+                MajorSecond,
+                MinorThird,
+                PerfectFourth,
+                PerfectFifth,
+                MinorSixth,
+                MajorSeventh,
+            ],
+            MelodicMinor => vec![
+                PerfectUnison,
+                MajorSecond,
+                MinorThird,
+                PerfectFourth,
+                PerfectFifth,
+                MajorSixth,
+                MajorSeventh,
+            ],
+            MajorPentatonic => vec![
+                PerfectUnison,
+                MajorSecond,
+                MajorThird,
+                PerfectFifth,
+                MajorSixth,
+            ],
+            MinorPentatonic => vec![
+                PerfectUnison,
+                MinorThird,
+                PerfectFourth,
+                PerfectFifth,
+                MinorSeventh,
+            ],
+        };
+
+        intervals.into_iter()
+    }
+}
+
+impl fmt::Display for ScaleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ScaleType::*;
+
+        let s = match self {
+            Major => "major",
+            NaturalMinor => "natural minor",
+            HarmonicMinor => "harmonic minor",
+            MelodicMinor => "melodic minor",
+            MajorPentatonic => "major pentatonic",
+            MinorPentatonic => "minor pentatonic",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// A scale such as C major, A minor pentatonic and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scale {
+    pub root: Note,
+    pub scale_type: ScaleType,
+}
+
+impl Scale {
+    pub fn new(root: Note, scale_type: ScaleType) -> Self {
+        Self { root, scale_type }
+    }
+
+    /// Return the scale of a key given by its tonic, followed by `m` for a minor
+    /// key (e.g. `C` or `Am`), i.e. the corresponding major or natural minor scale.
+    pub fn from_key(s: &str) -> Result<Self, ParseKeyError> {
+        let (tonic, scale_type) = match s.strip_suffix('m') {
+            Some(tonic) => (tonic, ScaleType::NaturalMinor),
+            None => (s, ScaleType::Major),
+        };
+
+        Note::from_str(tonic)
+            .map(|tonic| Self::new(tonic, scale_type))
+            .map_err(|_| ParseKeyError {
+                name: s.to_string(),
+            })
+    }
+
+    /// Return an iterator over the notes of the scale, starting at the root.
+    pub fn notes(&self) -> impl Iterator<Item = Note> + '_ {
+        self.scale_type.intervals().map(move |i| self.root + i)
+    }
+
+    /// Return `true` if `note` (or an enharmonic equivalent) is part of the scale.
+    pub fn contains(&self, note: Note) -> bool {
+        self.notes().any(|n| n == note)
+    }
+
+    /// Return all the positions on the fretboard between `min_fret` and
+    /// `max_fret` (inclusively) at which a note of the scale is played,
+    /// ordered by string and by fret.
+    ///
+    /// The notes are spelled as in the scale (e.g. Bb rather than A# in F major).
+    pub fn positions(&self, tuning: Tuning, min_fret: FretID, max_fret: FretID) -> Vec<UkeString> {
+        let notes: Vec<Note> = self.notes().collect();
+
+        tuning
+            .roots()
+            .flat_map(|root| {
+                let notes = &notes;
+
+                (min_fret..=max_fret).filter_map(move |fret| {
+                    let sounding = root + fret;
+
+                    notes
+                        .iter()
+                        .find(|&&n| n == sounding)
+                        .map(|n| (root, fret, n.with_octave(sounding.octave())))
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.root, self.scale_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        root,
+        scale_type,
+        notes,
+        case("C", ScaleType::Major, "C D E F G A B"),
+        case("F", ScaleType::Major, "F G A Bb C D E"),
+        case("D", ScaleType::Major, "D E F# G A B C#"),
+        case("A", ScaleType::NaturalMinor, "A B C D E F G"),
+        case("C", ScaleType::NaturalMinor, "C D Eb F G Ab Bb"),
+        case("A", ScaleType::HarmonicMinor, "A B C D E F G#"),
+        case("A", ScaleType::MelodicMinor, "A B C D E F# G#"),
+        case("C", ScaleType::MajorPentatonic, "C D E G A"),
+        case("A", ScaleType::MinorPentatonic, "A C D E G")
+    )]
+    fn test_notes(root: Note, scale_type: ScaleType, notes: &str) {
+        let scale = Scale::new(root, scale_type);
+        assert_eq!(scale.notes().map(|n| n.to_string()).join(" "), notes);
+    }
+
+    #[rstest(
+        s,
+        scale_type,
+        case("major", ScaleType::Major),
+        case("ionian", ScaleType::Major),
+        case("minor", ScaleType::NaturalMinor),
+        case("natural-minor", ScaleType::NaturalMinor),
+        case("harmonic-minor", ScaleType::HarmonicMinor),
+        case("melodic-minor", ScaleType::MelodicMinor),
+        case("major-pentatonic", ScaleType::MajorPentatonic),
+        case("minor-pentatonic", ScaleType::MinorPentatonic)
+    )]
+    fn test_from_str(s: &str, scale_type: ScaleType) {
+        assert_eq!(ScaleType::from_str(s, false).unwrap(), scale_type);
+    }
+
+    #[rstest(
+        s,
+        root,
+        scale_type,
+        case("C", "C", ScaleType::Major),
+        case("F#", "F#", ScaleType::Major),
+        case("Am", "A", ScaleType::NaturalMinor),
+        case("Bbm", "Bb", ScaleType::NaturalMinor)
+    )]
+    fn test_from_key(s: &str, root: Note, scale_type: ScaleType) {
+        let scale = Scale::from_key(s).unwrap();
+        assert_eq!(scale.root.to_string(), root.to_string());
+        assert_eq!(scale.scale_type, scale_type);
+    }
+
+    #[rstest(s, case(""), case("m"), case("H"), case("Amaj"), case("Cm7"))]
+    fn test_from_key_error(s: &str) {
+        assert_eq!(
+            Scale::from_key(s).unwrap_err().to_string(),
+            format!("could not parse key '{s}' (should be something like 'C' or 'Am')")
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let scale = Scale::new(Note::from_str("C").unwrap(), ScaleType::Major);
+        let positions = scale.positions(Tuning::C, 0, 3);
+
+        let frets: Vec<(String, FretID)> = positions
+            .iter()
+            .map(|(root, fret, _note)| (root.to_string(), *fret))
+            .collect();
+
+        let expected = [
+            ("G", 0),
+            ("G", 2),
+            ("C", 0),
+            ("C", 2),
+            ("E", 0),
+            ("E", 1),
+            ("E", 3),
+            ("A", 0),
+            ("A", 2),
+            ("A", 3),
+        ];
+        let expected: Vec<(String, FretID)> =
+            expected.iter().map(|(r, f)| (r.to_string(), *f)).collect();
+
+        assert_eq!(frets, expected);
+    }
+
+    #[rstest(min_fret, max_fret, case(0, 12), case(3, 7), case(5, 5), case(7, 3))]
+    fn test_positions_window(min_fret: FretID, max_fret: FretID) {
+        let scale = Scale::new(Note::from_str("A").unwrap(), ScaleType::MinorPentatonic);
+        let notes: Vec<Note> = scale.notes().collect();
+
+        for (root, fret, note) in scale.positions(Tuning::C, min_fret, max_fret) {
+            assert!(fret >= min_fret && fret <= max_fret);
+            assert!(notes.contains(&note));
+            assert_eq!(root + fret, note);
+        }
+    }
+
+    #[test]
+    fn test_positions_spelling() {
+        let scale = Scale::new(Note::from_str("F").unwrap(), ScaleType::Major);
+        let positions = scale.positions(Tuning::C, 1, 1);
+
+        // On the A string, the first fret is spelled Bb in F major.
+        let (_root, _fret, note) = positions.last().unwrap();
+        assert_eq!(note.to_string(), "Bb");
+    }
+
+    #[rstest(
+        root,
+        scale_type,
+        note,
+        contains,
+        case("C", ScaleType::Major, "E", true),
+        case("C", ScaleType::Major, "G#", false),
+        case("A", ScaleType::NaturalMinor, "G#", false),
+        case("A", ScaleType::HarmonicMinor, "G#", true),
+        case("A", ScaleType::HarmonicMinor, "Ab", true),
+        case("F", ScaleType::Major, "A#", true)
+    )]
+    fn test_contains(root: Note, scale_type: ScaleType, note: Note, contains: bool) {
+        let scale = Scale::new(root, scale_type);
+        assert_eq!(scale.contains(note), contains);
+    }
+}
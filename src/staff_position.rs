@@ -1,4 +1,4 @@
-use std::ops::Add;
+use core::ops::{Add, Sub};
 
 use crate::StaffSteps;
 
@@ -56,6 +56,16 @@ impl Add<StaffSteps> for StaffPosition {
     }
 }
 
+impl Sub for StaffPosition {
+    type Output = StaffSteps;
+
+    /// Get the number of staff positions from `other` up to the current one
+    /// (a value between 0 and 6).
+    fn sub(self, other: Self) -> StaffSteps {
+        (self as StaffSteps + STAFF_POSITION_COUNT - other as StaffSteps) % STAFF_POSITION_COUNT
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -96,4 +106,22 @@ mod tests {
     ) {
         assert_eq!(staff_position + n, result);
     }
+
+    #[rstest(
+        staff_position1,
+        staff_position2,
+        n,
+        case(CPos, CPos, 0),
+        case(CPos, DPos, 1),
+        case(CPos, BPos, 6),
+        case(BPos, CPos, 1),
+        case(GPos, FPos, 6)
+    )]
+    fn test_staff_position_sub(
+        staff_position1: StaffPosition,
+        staff_position2: StaffPosition,
+        n: StaffSteps,
+    ) {
+        assert_eq!(staff_position2 - staff_position1, n);
+    }
 }
@@ -1,42 +1,232 @@
-use std::{fmt, str::FromStr};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 use clap::ValueEnum;
+use itertools::{Either, Itertools};
 
-use crate::{Interval, Note, Semitones};
+use crate::{FingerPosition, FretID, Note, Octave, PitchClass};
 
-// Using clap's `value_enum` macro allows the specification of all Tuning
-// variants as `possible_values` for the CLI `--tuning` option.
+/// Maximal number of strings of an instrument with a custom tuning.
+const MAX_STRING_COUNT: usize = 6;
+
+/// Minimal number of strings of an instrument whose tuning is given by name
+/// or by notes (as many as a ukulele has).
+const MIN_STRING_COUNT: usize = 4;
+
+/// Custom error for strings that cannot be parsed into custom tunings.
+#[derive(Debug, thiserror::Error)]
+#[error("could not parse tuning '{name}' (should be something like 'A D F# B', 'DGBE' or 'G3 C4 E4 A4')")]
+pub struct ParseCustomTuningError {
+    name: String,
+}
+
+/// Custom error for strings that cannot be parsed into tunings.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseTuningError {
+    #[error(transparent)]
+    InvalidNotes(#[from] ParseCustomTuningError),
+    #[error("tuning '{name}' has {string_count} strings but at least {MIN_STRING_COUNT} strings are needed")]
+    TooFewStrings { name: String, string_count: usize },
+}
+
+/// Custom error for custom tunings whose number of strings differs from
+/// that of the tuning they replace.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "the custom tuning has {custom_count} strings but tuning {tuning} has {string_count} strings"
+)]
+pub struct StringCountMismatchError {
+    tuning: Tuning,
+    custom_count: usize,
+    string_count: usize,
+}
+
+/// An ad-hoc tuning given by the notes of the open strings, e.g. `A D F# B`.
+/// The notes may also be written without separators, e.g. `DGBE`.
+///
+/// Notes are placed in the octave containing the middle C unless an octave is
+/// given explicitly (e.g. `G3`).
+#[derive(Debug, Clone, Copy)]
+pub struct CustomTuning {
+    roots: [Note; MAX_STRING_COUNT],
+    string_count: usize,
+}
+
+impl CustomTuning {
+    pub fn roots(self) -> impl Iterator<Item = Note> + 'static {
+        IntoIterator::into_iter(self.roots).take(self.string_count)
+    }
+}
+
+// Notes only compare their pitch classes, but strings tuned an octave apart
+// make for different tunings.
+impl PartialEq for CustomTuning {
+    fn eq(&self, other: &Self) -> bool {
+        self.roots()
+            .map(|n| n.midi_number())
+            .eq(other.roots().map(|n| n.midi_number()))
+    }
+}
+
+impl Eq for CustomTuning {}
+
+impl Hash for CustomTuning {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.roots().for_each(|n| n.midi_number().hash(state));
+    }
+}
+
+impl fmt::Display for CustomTuning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.roots().join("-"))
+    }
+}
+
+impl FromStr for CustomTuning {
+    type Err = ParseCustomTuningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_string();
+
+        let roots: Vec<Note> = s
+            .split([' ', '-'])
+            .filter(|token| !token.is_empty())
+            .flat_map(split_notes)
+            .map(Note::parse_with_octave)
+            .collect::<Result<_, _>>()
+            .map_err(|_| ParseCustomTuningError { name: name.clone() })?;
+
+        if roots.is_empty() || roots.len() > MAX_STRING_COUNT {
+            return Err(ParseCustomTuningError { name });
+        }
+
+        let mut custom = Self {
+            roots: [roots[0]; MAX_STRING_COUNT],
+            string_count: roots.len(),
+        };
+        custom.roots[..roots.len()].copy_from_slice(&roots);
+
+        Ok(custom)
+    }
+}
+
+/// Split a token such as `DGBE` or `G3C4` into the names of its notes,
+/// each of which starts with an uppercase letter.
+fn split_notes(token: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = token
+        .char_indices()
+        .skip(1)
+        .filter(|(_i, c)| c.is_ascii_uppercase())
+        .map(|(i, _c)| i)
+        .collect();
+    starts.insert(0, 0);
+    starts.push(token.len());
+
+    starts.windows(2).map(|w| &token[w[0]..w[1]]).collect()
+}
+
+// Using clap's `value_enum` macro allows parsing the names of the Tuning
+// variants. `FromStr` additionally accepts the notes of custom tunings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
 #[clap(rename_all = "UPPER")]
 pub enum Tuning {
+    /// Standard tuning G-C-E-A (re-entrant).
+    #[value(alias = "standard")]
     C,
+    /// Standard tuning G-C-E-A with a low G string (linear).
+    #[value(name = "c-low-g")]
+    CLowG,
+    /// "English" tuning A-D-F#-B (re-entrant).
+    #[value(alias = "english")]
     D,
+    /// "Slack-key" tuning D-G-B-E (re-entrant).
+    #[value(alias = "slack-key")]
     G,
+    /// Baritone tuning D-G-B-E. The strings have the same names as in
+    /// G tuning but are tuned linearly, i.e. the D string is the lowest one.
+    #[value(name = "baritone")]
+    Baritone,
+    /// Guitalele tuning A-D-G-C-E-A (six strings). The four highest strings
+    /// are tuned like the C tuning with a low G string.
+    #[value(name = "guitalele")]
+    Guitalele,
+    /// A tuning given by arbitrary notes of the open strings.
+    #[value(skip)]
+    Custom(CustomTuning),
 }
 
 impl Tuning {
-    pub fn get_semitones(self) -> Semitones {
-        match self {
-            Self::C => 0,
-            Self::D => 2,
-            Self::G => 7,
-        }
+    /// Return an iterator over the notes of the open strings
+    /// (placed in the octaves in which they are played).
+    pub fn roots(self) -> impl Iterator<Item = Note> + 'static {
+        let roots: &[(&str, Octave)] = match self {
+            Self::Custom(custom) => return Either::Right(custom.roots()),
+            Self::C => &[("G", 4), ("C", 4), ("E", 4), ("A", 4)],
+            Self::CLowG => &[("G", 3), ("C", 4), ("E", 4), ("A", 4)],
+            Self::D => &[("A", 4), ("D", 4), ("F#", 4), ("B", 4)],
+            Self::G => &[("D", 4), ("G", 3), ("B", 3), ("E", 4)],
+            Self::Baritone => &[("D", 3), ("G", 3), ("B", 3), ("E", 4)],
+            Self::Guitalele => &[("A", 2), ("D", 3), ("G", 3), ("C", 4), ("E", 4), ("A", 4)],
+        };
+
+        Either::Left(
+            roots
+                .iter()
+                .map(|(name, octave)| Note::from_str(name).unwrap().with_octave(*octave)),
+        )
+    }
+
+    /// Return the number of strings of an instrument with this tuning.
+    pub fn string_count(self) -> usize {
+        self.roots().count()
     }
 
-    pub fn get_interval(self) -> Interval {
-        match self {
-            Self::C => Interval::PerfectUnison,
-            Self::D => Interval::MajorSecond,
-            Self::G => Interval::PerfectFifth,
+    /// Replace the notes of the open strings by those of `custom`, which must
+    /// have as many strings as the tuning.
+    pub fn with_custom(self, custom: CustomTuning) -> Result<Self, StringCountMismatchError> {
+        let custom_count = custom.roots().count();
+        let string_count = self.string_count();
+
+        match custom_count == string_count {
+            true => Ok(Self::Custom(custom)),
+            false => Err(StringCountMismatchError {
+                tuning: self,
+                custom_count,
+                string_count,
+            }),
         }
     }
 
-    pub fn roots(self) -> impl Iterator<Item = Note> + 'static {
-        let interval = self.get_interval();
+    /// Return the note that is played when pressing down the given fret
+    /// on the string with index `string_id` (0 being the leftmost string
+    /// in the tuning's notation, e.g. G in GCEA).
+    pub fn note_at(self, string_id: usize, fret: FretID) -> Option<Note> {
+        self.roots().nth(string_id).map(|root| root + fret)
+    }
 
-        ["G", "C", "E", "A"]
-            .iter()
-            .map(move |c| Note::from_str(c).unwrap() + interval)
+    /// Return all the positions between `min_fret` and `max_fret` (inclusively)
+    /// at which `pitch_class` is played, ordered by string and by fret.
+    ///
+    /// As for fingerings, the strings are counted from 1 (the leftmost string
+    /// in the tuning's notation). If the window spans more than an octave,
+    /// a string may contain several positions 12 frets apart.
+    pub fn positions_of(
+        self,
+        pitch_class: PitchClass,
+        min_fret: FretID,
+        max_fret: FretID,
+    ) -> Vec<FingerPosition> {
+        self.roots()
+            .enumerate()
+            .flat_map(|(i, root)| {
+                (min_fret..=max_fret)
+                    .filter(move |&fret| (root + fret).pitch_class == pitch_class)
+                    .map(move |fret| ((i + 1) as u8, fret))
+            })
+            .collect()
     }
 }
 
@@ -44,10 +234,246 @@ impl fmt::Display for Tuning {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Self::C => "C",
+            Self::CLowG => "c-low-g",
             Self::D => "D",
             Self::G => "G",
+            Self::Baritone => "baritone",
+            Self::Guitalele => "guitalele",
+            Self::Custom(custom) => return write!(f, "{custom}"),
         };
 
         write!(f, "{s}")
     }
 }
+
+/// Parse either the name of a tuning (e.g. `C` or `baritone`) or the notes
+/// of the open strings of a custom tuning (e.g. `A D F# B` or `DGBE`).
+/// Custom tunings need at least four strings.
+impl FromStr for Tuning {
+    type Err = ParseTuningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(tuning) = <Self as ValueEnum>::from_str(s, false) {
+            return Ok(tuning);
+        }
+
+        let custom = CustomTuning::from_str(s)?;
+        let string_count = custom.roots().count();
+
+        match string_count >= MIN_STRING_COUNT {
+            true => Ok(Self::Custom(custom)),
+            false => Err(ParseTuningError::TooFewStrings {
+                name: s.to_string(),
+                string_count,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        tuning,
+        roots,
+        octaves,
+        case(Tuning::C, vec!["G", "C", "E", "A"], vec![4, 4, 4, 4]),
+        case(Tuning::CLowG, vec!["G", "C", "E", "A"], vec![3, 4, 4, 4]),
+        case(Tuning::D, vec!["A", "D", "F#", "B"], vec![4, 4, 4, 4]),
+        case(Tuning::G, vec!["D", "G", "B", "E"], vec![4, 3, 3, 4]),
+        case(Tuning::Baritone, vec!["D", "G", "B", "E"], vec![3, 3, 3, 4]),
+        case(
+            Tuning::Guitalele,
+            vec!["A", "D", "G", "C", "E", "A"],
+            vec![2, 3, 3, 4, 4, 4]
+        )
+    )]
+    fn test_roots(tuning: Tuning, roots: Vec<&str>, octaves: Vec<Octave>) {
+        let roots: Vec<Note> = roots.iter().map(|s| Note::from_str(s).unwrap()).collect();
+        assert_eq!(tuning.roots().collect::<Vec<_>>(), roots);
+        assert_eq!(
+            tuning.roots().map(|n| n.octave()).collect::<Vec<_>>(),
+            octaves
+        );
+    }
+
+    #[rstest(
+        s,
+        tuning,
+        case("C", Tuning::C),
+        case("standard", Tuning::C),
+        case("c-low-g", Tuning::CLowG),
+        case("D", Tuning::D),
+        case("english", Tuning::D),
+        case("G", Tuning::G),
+        case("slack-key", Tuning::G),
+        case("baritone", Tuning::Baritone),
+        case("guitalele", Tuning::Guitalele)
+    )]
+    fn test_from_str(s: &str, tuning: Tuning) {
+        assert_eq!(<Tuning as ValueEnum>::from_str(s, false).unwrap(), tuning);
+        assert_eq!(<Tuning as FromStr>::from_str(s).unwrap(), tuning);
+    }
+
+    #[rstest(
+        tuning,
+        pitch_class,
+        min_fret,
+        max_fret,
+        positions,
+        case(Tuning::C, PitchClass::C, 0, 12, vec![(1, 5), (2, 0), (2, 12), (3, 8), (4, 3)]),
+        case(Tuning::C, PitchClass::C, 1, 11, vec![(1, 5), (3, 8), (4, 3)]),
+        case(Tuning::C, PitchClass::CSharp, 4, 4, vec![(4, 4)]),
+        case(Tuning::D, PitchClass::D, 0, 5, vec![(1, 5), (2, 0), (4, 3)]),
+        case(Tuning::C, PitchClass::C, 9, 10, vec![])
+    )]
+    fn test_positions_of(
+        tuning: Tuning,
+        pitch_class: PitchClass,
+        min_fret: FretID,
+        max_fret: FretID,
+        positions: Vec<FingerPosition>,
+    ) {
+        assert_eq!(
+            tuning.positions_of(pitch_class, min_fret, max_fret),
+            positions
+        );
+    }
+
+    #[rstest(
+        tuning,
+        case(Tuning::C),
+        case(Tuning::CLowG),
+        case(Tuning::D),
+        case(Tuning::G),
+        case(Tuning::Baritone),
+        case(Tuning::Guitalele)
+    )]
+    fn test_to_string_round_trip(tuning: Tuning) {
+        assert_eq!(
+            <Tuning as ValueEnum>::from_str(&tuning.to_string(), false).unwrap(),
+            tuning
+        );
+    }
+
+    #[rstest(
+        s,
+        roots,
+        midi_numbers,
+        case("A D F# B", vec!["A", "D", "F#", "B"], vec![69, 62, 66, 71]),
+        case("A-D-F#-B", vec!["A", "D", "F#", "B"], vec![69, 62, 66, 71]),
+        case("G3 C4 E4 A4", vec!["G", "C", "E", "A"], vec![55, 60, 64, 69]),
+        case("D G B E A D", vec!["D", "G", "B", "E", "A", "D"], vec![62, 67, 71, 64, 69, 62]),
+        case("DGBE", vec!["D", "G", "B", "E"], vec![62, 67, 71, 64]),
+        case("G3C4E4A4", vec!["G", "C", "E", "A"], vec![55, 60, 64, 69]),
+        case("F#BbDG", vec!["F#", "Bb", "D", "G"], vec![66, 70, 62, 67])
+    )]
+    fn test_custom_tuning_from_str(s: &str, roots: Vec<&str>, midi_numbers: Vec<u8>) {
+        let custom = CustomTuning::from_str(s).unwrap();
+        let tuning = Tuning::Custom(custom);
+
+        let roots: Vec<Note> = roots.iter().map(|s| Note::from_str(s).unwrap()).collect();
+        assert_eq!(tuning.roots().collect::<Vec<_>>(), roots);
+        assert_eq!(
+            tuning.roots().map(|n| n.midi_number()).collect::<Vec<_>>(),
+            midi_numbers
+        );
+        assert_eq!(tuning.string_count(), roots.len());
+    }
+
+    #[rstest(
+        s,
+        case(""),
+        case("A D X B"),
+        case("A D F# B A D F#"),
+        case("A4x D"),
+        case("ADXB"),
+        case("gcea")
+    )]
+    fn test_custom_tuning_from_str_fail(s: &str) {
+        assert!(CustomTuning::from_str(s).is_err());
+    }
+
+    #[test]
+    fn test_custom_tuning_eq() {
+        let custom = |s| Tuning::Custom(CustomTuning::from_str(s).unwrap());
+
+        assert_eq!(custom("G C E A"), custom("G4-C4-E4-A4"));
+        assert_ne!(custom("G C E A"), custom("G3 C E A"));
+        assert_eq!(custom("A D F# B").to_string(), "A-D-F#-B");
+    }
+
+    #[rstest(
+        tuning,
+        string_count,
+        case(Tuning::C, 4),
+        case(Tuning::Baritone, 4),
+        case(Tuning::Guitalele, 6)
+    )]
+    fn test_string_count(tuning: Tuning, string_count: usize) {
+        assert_eq!(tuning.string_count(), string_count);
+    }
+
+    #[rstest(
+        s,
+        roots,
+        case("DGBE", vec!["D", "G", "B", "E"]),
+        case("G C E A", vec!["G", "C", "E", "A"]),
+        case("A-D-F#-B", vec!["A", "D", "F#", "B"])
+    )]
+    fn test_from_str_custom(s: &str, roots: Vec<&str>) {
+        let tuning = <Tuning as FromStr>::from_str(s).unwrap();
+        let roots: Vec<Note> = roots.iter().map(|s| Note::from_str(s).unwrap()).collect();
+
+        assert!(matches!(tuning, Tuning::Custom(_)));
+        assert_eq!(tuning.roots().collect::<Vec<_>>(), roots);
+    }
+
+    #[rstest(s, case(""), case("X"), case("standard tuning"))]
+    fn test_from_str_fail(s: &str) {
+        assert!(<Tuning as FromStr>::from_str(s).is_err());
+    }
+
+    #[rstest(s, string_count, case("GCE", 3), case("G C", 2), case("A4", 1))]
+    fn test_from_str_too_few_strings(s: &str, string_count: usize) {
+        assert_eq!(
+            <Tuning as FromStr>::from_str(s).unwrap_err().to_string(),
+            format!("tuning '{s}' has {string_count} strings but at least 4 strings are needed")
+        );
+    }
+
+    #[rstest(
+        tuning,
+        s,
+        case(Tuning::C, "DGBE"),
+        case(Tuning::G, "G C E A"),
+        case(Tuning::Guitalele, "E A D G B E")
+    )]
+    fn test_with_custom(tuning: Tuning, s: &str) {
+        let custom = CustomTuning::from_str(s).unwrap();
+        assert_eq!(tuning.with_custom(custom).unwrap(), Tuning::Custom(custom));
+    }
+
+    #[rstest(
+        tuning,
+        s,
+        error,
+        case(
+            Tuning::C,
+            "D G B",
+            "the custom tuning has 3 strings but tuning C has 4 strings"
+        ),
+        case(
+            Tuning::Guitalele,
+            "DGBE",
+            "the custom tuning has 4 strings but tuning guitalele has 6 strings"
+        )
+    )]
+    fn test_with_custom_fail(tuning: Tuning, s: &str, error: &str) {
+        let custom = CustomTuning::from_str(s).unwrap();
+        assert_eq!(tuning.with_custom(custom).unwrap_err().to_string(), error);
+    }
+}
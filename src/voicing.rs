@@ -1,20 +1,37 @@
 use std::{
     cmp::{max, min, Ordering},
-    convert::{TryFrom, TryInto},
+    collections::BTreeSet,
     fmt,
-    slice::Iter,
+    ops::RangeInclusive,
 };
 
-use itertools::Itertools;
+use itertools::{izip, Itertools};
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 use crate::{
-    Chord, Distance, Fingering, FretID, FretPattern, Note, PitchClass, Tuning, UkeString,
-    FINGER_COUNT, STRING_COUNT,
+    Chord, Distance, DistanceWeights, Fingering, FretID, FretPattern, Interval, Note, Octave,
+    PitchClass, Spelling, Tuning, UkeString, VoicingBuilder, FINGER_COUNT, PITCH_CLASS_COUNT,
 };
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Voicing {
-    uke_strings: [UkeString; STRING_COUNT],
+    /// One entry per string of the instrument, so the number of strings
+    /// is determined by the tuning.
+    uke_strings: Vec<UkeString>,
+    /// Strings that are not played. The corresponding entries in `uke_strings`
+    /// only keep track of the root note of the string.
+    muted: Vec<bool>,
+}
+
+/// Which tones of a chord are doubled or left out in a voicing
+/// (see `Voicing::tone_analysis`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToneAnalysis {
+    /// Intervals (counted from the root) of the chord tones played on several strings
+    pub doubled: Vec<Interval>,
+    /// Intervals (counted from the root) of the chord tones that are not played at all
+    pub missing: Vec<Interval>,
 }
 
 /// The voicing of a chord describes the order of the individual notes within
@@ -30,31 +47,172 @@ impl Voicing {
     pub fn new(fret_pattern: impl Into<FretPattern>, tuning: Tuning) -> Self {
         let fret_pattern = fret_pattern.into();
 
-        let uke_strings: Vec<UkeString> = tuning
+        let uke_strings = tuning
             .roots()
             .zip(fret_pattern.iter())
-            .map(|(root, &fret)| (root, fret, root + fret))
+            .map(|(root, fret)| {
+                let fret = fret.unwrap_or_default();
+                (root, fret, root + fret)
+            })
+            .collect();
+        let muted = fret_pattern.iter().map(Option::is_none).collect();
+
+        Self { uke_strings, muted }
+    }
+
+    /// Return a builder to search for the voicings of a chord
+    /// (see `VoicingBuilder`).
+    pub fn builder<'a>() -> VoicingBuilder<'a> {
+        VoicingBuilder::new()
+    }
+
+    /// Return a copy of this voicing in which the string with the given index
+    /// is not played.
+    pub fn mute(&self, string_id: usize) -> Self {
+        let mut voicing = self.clone();
+        let (root, _fret, _note) = voicing.uke_strings[string_id];
+        voicing.uke_strings[string_id] = (root, 0, root);
+        voicing.muted[string_id] = true;
+        voicing
+    }
+
+    /// Return the same shape moved one octave up the neck, i.e. with each
+    /// played string pressed down twelve frets higher (so open strings are
+    /// pressed down at the twelfth fret).
+    /// Return `None` if the shape would extend beyond `max_fret`.
+    pub fn next_position(&self, max_fret: FretID) -> Option<Self> {
+        if self.get_max_fret() + PITCH_CLASS_COUNT > max_fret {
+            return None;
+        }
+
+        let uke_strings = self
+            .uke_strings
+            .iter()
+            .zip(self.muted.iter())
+            .map(|(&(root, fret, note), &muted)| match muted {
+                true => (root, fret, note),
+                false => (root, fret + PITCH_CLASS_COUNT, note + PITCH_CLASS_COUNT),
+            })
+            .collect();
+
+        Some(Self {
+            uke_strings,
+            muted: self.muted.clone(),
+        })
+    }
+
+    /// Return the same voicing with the notes (and the roots of the strings)
+    /// spelled according to `spelling`.
+    pub fn respell(&self, spelling: Spelling) -> Self {
+        let uke_strings = self
+            .uke_strings
+            .iter()
+            .map(|&(root, fret, note)| (root.respell(spelling), fret, note.respell(spelling)))
+            .collect();
+
+        Self {
+            uke_strings,
+            muted: self.muted.clone(),
+        }
+    }
+
+    /// Return the shape of this voicing (a voicing of `chord` transposed down by
+    /// `capo` semitones) as played with a capo placed at fret `capo`, i.e. fret 0
+    /// corresponds to the capo and all strings sound `capo` semitones higher.
+    /// The notes are spelled as in `chord`.
+    pub fn capo_shape(&self, capo: FretID, chord: &Chord) -> Self {
+        let uke_strings = self
+            .uke_strings
+            .iter()
+            .map(|&(root, fret, note)| {
+                let note = note + capo;
+                let note = chord
+                    .notes
+                    .iter()
+                    .chain(&chord.bass)
+                    .find(|&&n| n == note)
+                    .map_or(note, |n| n.with_octave(note.octave()));
+                (root + capo, fret, note)
+            })
             .collect();
 
         Self {
-            uke_strings: uke_strings.try_into().unwrap(),
+            uke_strings,
+            muted: self.muted.clone(),
         }
     }
 
-    pub fn uke_strings(&self) -> Iter<'_, UkeString> {
-        self.uke_strings.iter()
+    /// Return an iterator over all the strings of the ukulele (in the order
+    /// of the tuning), yielding `None` for each muted string.
+    pub fn strings(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Option<&UkeString>> + ExactSizeIterator + Clone + '_ {
+        self.uke_strings
+            .iter()
+            .zip(self.muted.iter())
+            .map(|(uke_string, &muted)| match muted {
+                true => None,
+                false => Some(uke_string),
+            })
+    }
+
+    /// Return an iterator over the strings that are played, i.e. all the strings
+    /// that are not muted.
+    pub fn uke_strings(&self) -> impl Iterator<Item = &UkeString> + Clone + '_ {
+        self.strings().flatten()
     }
 
-    pub fn roots(&self) -> impl Iterator<Item = Note> + '_ {
+    /// Return an iterator over the root notes of all the strings,
+    /// including the muted ones.
+    pub fn roots(&self) -> impl DoubleEndedIterator<Item = Note> + ExactSizeIterator + '_ {
         self.uke_strings.iter().map(|(r, _f, _n)| *r)
     }
 
     pub fn frets(&self) -> impl Iterator<Item = FretID> + '_ {
-        self.uke_strings.iter().map(|(_r, f, _n)| *f)
+        self.uke_strings().map(|(_r, f, _n)| *f)
     }
 
     pub fn notes(&self) -> impl Iterator<Item = Note> + '_ {
-        self.uke_strings.iter().map(|(_r, _f, n)| *n)
+        self.uke_strings().map(|(_r, _f, n)| *n)
+    }
+
+    /// Return the set of pitch classes played in the voicing
+    /// (ignoring octaves and muted strings).
+    pub fn pitch_classes(&self) -> BTreeSet<PitchClass> {
+        self.notes().map(|note| note.pitch_class).collect()
+    }
+
+    /// Return `true` if a note of the given pitch class is played in the voicing.
+    pub fn contains_pitch_class(&self, pitch_class: PitchClass) -> bool {
+        self.notes().any(|note| note.pitch_class == pitch_class)
+    }
+
+    /// Return the frets of all the strings (in the order of the tuning),
+    /// `None` standing for a muted string.
+    pub fn fret_pattern(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Option<FretID>> + ExactSizeIterator + '_ {
+        self.strings().map(|s| s.map(|(_r, f, _n)| *f))
+    }
+
+    /// Return the frets of all the strings as a `FretPattern`, the inverse of
+    /// `Voicing::new`, e.g. to print the voicing in the compact form `x232`.
+    pub fn to_fret_pattern(&self) -> FretPattern {
+        self.fret_pattern().collect()
+    }
+
+    /// Return a canonical key describing the shape of the voicing.
+    ///
+    /// For each string, the key holds the fret relative to the lowest fret of the
+    /// voicing (reduced to a single octave) and the pitch class of the sounding
+    /// note, `None` standing for a muted string. Voicings that only differ in the
+    /// octave in which some of their strings are played have the same shape.
+    pub fn shape(&self) -> Vec<Option<(FretID, PitchClass)>> {
+        let min_fret = self.frets().min().unwrap_or_default();
+
+        self.strings()
+            .map(|s| s.map(|(_r, f, n)| ((f - min_fret) % PITCH_CLASS_COUNT, n.pitch_class)))
+            .collect()
     }
 
     /// Return the overall number of strings pressed down when playing
@@ -63,6 +221,11 @@ impl Voicing {
         self.frets().filter(|&f| f > 0).count()
     }
 
+    /// Return the number of strings played open (muted strings do not count).
+    pub fn count_open_strings(&self) -> usize {
+        self.frets().filter(|&f| f == 0).count()
+    }
+
     /// Return the lowest fret at which a string is pressed down.
     pub fn get_min_pressed_fret(&self) -> FretID {
         self.frets().filter(|&x| x > 0).min().unwrap_or_default()
@@ -78,13 +241,18 @@ impl Voicing {
         self.frets().max().unwrap()
     }
 
-    pub fn get_span(&self) -> FretID {
-        let max_fret = self.get_max_fret();
+    /// Return the lowest and the highest fret at which a string is pressed down,
+    /// i.e. the smallest range of frets containing all fretted (non-open) strings.
+    /// Returns `(0, 0)` if all strings are played open.
+    pub fn fret_window(&self) -> (FretID, FretID) {
+        (self.get_min_pressed_fret(), self.get_max_fret())
+    }
 
-        match max_fret {
+    pub fn get_span(&self) -> FretID {
+        match self.fret_window() {
             // Special case [0, 0, 0, 0]: span is zero.
-            0 => 0,
-            _ => max_fret - self.get_min_pressed_fret() + 1,
+            (_, 0) => 0,
+            (min_fret, max_fret) => max_fret - min_fret + 1,
         }
     }
 
@@ -94,30 +262,102 @@ impl Voicing {
         self.notes()
             .sorted()
             .dedup()
-            .eq(chord.played_notes().sorted().dedup())
+            .eq(chord.played_notes(self.strings().len()).sorted().dedup())
     }
 
-    pub fn get_chords(&self) -> Vec<Chord> {
-        let mut chords = vec![];
-
-        let mut pitches: Vec<PitchClass> = self.notes().map(|n| n.pitch_class).collect();
-        pitches.sort();
-        pitches.dedup();
+    /// Return which tones of `chord` are doubled (played on several strings)
+    /// and which are missing in the voicing, e.g. a doubled root and a missing
+    /// fifth. Notes that are not part of the chord are ignored.
+    ///
+    /// The intervals are listed in the same order as in the chord type.
+    pub fn tone_analysis(&self, chord: &Chord) -> ToneAnalysis {
+        let counts = self
+            .notes()
+            .filter_map(|note| chord.interval_of(note))
+            .counts();
+        let mut analysis = ToneAnalysis::default();
 
-        // Rotate pitch class list and collect all matching chords.
-        // For example, try [C, DSharp, GSharp], [DSharp, GSharp, C], [GSharp, C, FSharp].
-        for _ in 0..pitches.len() {
-            if let Ok(chord) = Chord::try_from(&pitches[..]) {
-                chords.push(chord);
+        for interval in chord.chord_type.intervals() {
+            match counts.get(&interval) {
+                Some(&count) if count > 1 => analysis.doubled.push(interval),
+                Some(_) => (),
+                // Omitted tones (as in `C(no5)`) are not expected to be played.
+                None if chord.omitted.contains(&interval.to_number()) => (),
+                None => analysis.missing.push(interval),
             }
-
-            pitches.rotate_left(1);
         }
 
+        analysis
+    }
+
+    /// Return the intervals between each pair of sounding notes.
+    ///
+    /// The notes are sorted by pitch (so re-entrant tunings are handled
+    /// correctly) and each interval is measured upwards from the lower note
+    /// of the pair. Intervals are given as a simple interval together with
+    /// the number of whole octaves it spans, e.g. a major tenth is
+    /// `(MajorThird, 1)` and an octave is `(PerfectUnison, 1)`.
+    pub fn internal_intervals(&self) -> Vec<(Interval, Octave)> {
+        self.notes()
+            .sorted_by_key(|note| note.midi_number())
+            .tuple_combinations()
+            .map(|(low, high)| {
+                let semitones = high.midi_number() - low.midi_number();
+                (
+                    Interval::from(semitones % PITCH_CLASS_COUNT),
+                    semitones / PITCH_CLASS_COUNT,
+                )
+            })
+            .collect()
+    }
+
+    /// Return the lowest note played in this voicing (taking octaves into account).
+    pub fn bass(&self) -> Note {
+        self.notes().min_by_key(|n| n.midi_number()).unwrap()
+    }
+
+    /// Return all the chords that this voicing represents.
+    ///
+    /// If the lowest note of the voicing is not the root of a chord,
+    /// the chord is an inversion and the lowest note is stored as its bass note.
+    pub fn get_chords(&self) -> Vec<Chord> {
+        let notes: Vec<Note> = self.notes().collect();
+
+        let mut chords = Chord::from_notes(&notes);
         chords.sort();
         chords
     }
 
+    /// Describe each string (highest string first) by its fret, its note and the
+    /// interval of the note from the root of `chord` (`R` for the root).
+    /// Notes that are not part of the chord are marked as such.
+    pub fn explain(&self, chord: &Chord) -> String {
+        self.roots()
+            .zip(self.strings())
+            .rev()
+            .map(|(root, uke_string)| {
+                let root = root.to_string();
+
+                match uke_string {
+                    Some(&(_root, fret, note)) => {
+                        let (note, label) = match chord.interval_of(note) {
+                            Some(Interval::PerfectUnison) => (chord.root, "R".to_string()),
+                            Some(interval) => (chord.root + interval, interval.short().to_string()),
+                            None => (
+                                note,
+                                format!("{} (non-chord tone)", (note - chord.root).short()),
+                            ),
+                        };
+                        let note = note.to_string();
+
+                        format!("  {root:<2} {fret:>2}  {note:<2} {label}")
+                    }
+                    None => format!("  {root:<2}  x"),
+                }
+            })
+            .join("\n")
+    }
+
     /// Return `true` if the current voicing requires the player to play a barre chord.
     /// For this, I took some inspiration from
     /// https://github.com/hyvyys/chord-fingering/blob/master/src/barre.js
@@ -155,13 +395,49 @@ impl Voicing {
         }
 
         // 0111 can be played with fingering 0123.
-        if min_fret_count < STRING_COUNT && min_fret_count == pressed_frets.len() {
+        if min_fret_count < self.strings().len() && min_fret_count == pressed_frets.len() {
             return false;
         }
 
         min_fret_count >= 2
     }
 
+    /// Return a score for how difficult it is to play this voicing.
+    /// The higher the score, the harder the voicing is to play.
+    ///
+    /// The score adds up two points per fret of span, one point per pressed string,
+    /// three points for a barre and one point for every two frets up the neck.
+    pub fn difficulty(&self) -> u32 {
+        let span = self.get_span() as u32;
+        let pressed_strings = self.count_pressed_strings() as u32;
+        let barre = match self.has_barre() {
+            true => 3,
+            false => 0,
+        };
+        let position = self.get_min_pressed_fret() as u32 / 2;
+
+        2 * span + pressed_strings + barre + position
+    }
+
+    /// Return the fret and the range of strings (by index) to be covered
+    /// by a barre when playing this voicing, or `None` if the voicing is
+    /// not played as a barre chord.
+    ///
+    /// The barre is placed on the lowest pressed fret and spans all the strings
+    /// between the first and the last string pressed down on that fret.
+    pub fn barre(&self) -> Option<(FretID, RangeInclusive<usize>)> {
+        if !self.has_barre() {
+            return None;
+        }
+
+        let fret = self.get_min_pressed_fret();
+        let frets = self.fret_pattern().collect_vec();
+        let first = frets.iter().position(|&f| f == Some(fret))?;
+        let last = frets.iter().rposition(|&f| f == Some(fret))?;
+
+        Some((fret, first..=last))
+    }
+
     /// Compute a fingering for the current voicing, i.e. assign the player's
     /// fingers to the positions on the fretboard that have to be pressed down.
     /// The return value is an array of numbers representing the fingers
@@ -171,7 +447,7 @@ impl Voicing {
     /// strategy here is based on my own way to play certain chords. For example,
     /// I tend to avoid barre chords if possible, e.g. I play the G major chord
     /// as 0132 and not as 0121.
-    pub fn fingers_on_strings(&self) -> [u8; STRING_COUNT] {
+    pub fn fingers_on_strings(&self) -> Vec<u8> {
         // Total number of strings on which we need to place our fingers.
         let pressed_strings = self.count_pressed_strings();
 
@@ -189,7 +465,7 @@ impl Voicing {
             _ => self.get_min_pressed_fret(),
         };
 
-        let mut fingering = [0; STRING_COUNT];
+        let mut fingering = vec![0; self.strings().len()];
 
         // Current finger (can have values 1 to 4).
         let mut finger = 1;
@@ -199,8 +475,8 @@ impl Voicing {
         let mut used_strings = 0;
 
         for fret_id in min_fret..max_fret + 1 {
-            for (i, f) in self.frets().enumerate() {
-                if f == fret_id {
+            for (i, f) in self.fret_pattern().enumerate() {
+                if f == Some(fret_id) {
                     fingering[i] = finger as u8;
                     used_strings += 1;
                     if (!self.has_barre() || finger > 1) && finger < FINGER_COUNT {
@@ -224,29 +500,88 @@ impl Voicing {
         fingering
     }
 
+    /// Return the positions of the fingers used to play this voicing
+    /// (see `fingers_on_strings`).
+    pub fn fingering(&self) -> Fingering {
+        Fingering::from(self)
+    }
+
     /// Return the distance in semitones between this and another voicing.
     /// It's computed by simply summing up the distances between the frets that
     /// are pressed down on the same string when moving from one voicing to the other.
     /// Inspired by http://www.petecorey.com/blog/2018/07/30/voice-leading-with-elixir/
-    pub fn semitone_distance(&self, other: Self) -> u8 {
-        self.frets()
-            .zip(other.frets())
+    /// Muted strings are treated like open strings.
+    pub fn semitone_distance(&self, other: &Self) -> u8 {
+        self.fret_pattern()
+            .zip(other.fret_pattern())
+            .map(|(f1, f2)| (f1.unwrap_or_default(), f2.unwrap_or_default()))
             .map(|(f1, f2)| max(f1, f2) - min(f1, f2))
             .sum()
     }
 
-    pub fn fingering_distance(&self, other: Self) -> u8 {
-        let l_fingering = Fingering::from(*self);
-        let r_fingering = Fingering::from(other);
+    pub fn fingering_distance(&self, other: &Self) -> u8 {
+        self.fingering().distance(other.fingering())
+    }
 
-        l_fingering.distance(r_fingering)
+    pub fn distance(&self, other: &Self) -> Distance {
+        self.weighted_distance(other, DistanceWeights::default())
     }
 
-    pub fn distance(&self, other: Self) -> Distance {
+    /// Return the distance between this and another voicing, using the given
+    /// `weights` to compute its cost.
+    pub fn weighted_distance(&self, other: &Self, weights: DistanceWeights) -> Distance {
         let semitone_distance = self.semitone_distance(other);
         let fingering_distance = self.fingering_distance(other);
 
-        Distance::new(semitone_distance, fingering_distance)
+        Distance::weighted(
+            semitone_distance,
+            fingering_distance,
+            other.get_max_fret(),
+            weights,
+        )
+    }
+
+    /// Describe the movement on each string (highest string first) when changing
+    /// from this voicing to `other`, e.g. `E   0 ->  3  +3  finger - -> 3`.
+    pub fn explain_diff(&self, other: &Self) -> String {
+        let format_fret = |fret: Option<FretID>| fret.map_or("x".to_string(), |f| f.to_string());
+        let format_finger = |finger: u8| match finger {
+            0 => "-".to_string(),
+            _ => finger.to_string(),
+        };
+
+        izip!(
+            self.roots(),
+            self.fret_pattern(),
+            other.fret_pattern(),
+            self.fingers_on_strings(),
+            other.fingers_on_strings()
+        )
+        .rev()
+        .map(|(root, fret1, fret2, finger1, finger2)| {
+            let root = root.to_string();
+            // Muted strings are treated like open strings.
+            let delta = fret2.unwrap_or_default() as i16 - fret1.unwrap_or_default() as i16;
+            let delta = match delta {
+                0 => "0".to_string(),
+                _ => format!("{delta:+}"),
+            };
+            let line = format!(
+                "  {root:<2} {:>2} -> {:>2}  {delta:<3}",
+                format_fret(fret1),
+                format_fret(fret2)
+            );
+
+            match (finger1, finger2) {
+                (0, 0) => line.trim_end().to_string(),
+                _ => format!(
+                    "{line} finger {} -> {}",
+                    format_finger(finger1),
+                    format_finger(finger2)
+                ),
+            }
+        })
+        .join("\n")
     }
 }
 
@@ -256,20 +591,27 @@ impl PartialOrd for Voicing {
     }
 }
 
+/// Voicings are ordered by the lowest pressed fret first, then by the frets
+/// of the strings (starting from the last string), and finally by the pitch
+/// classes of the roots and notes of the strings.
+///
+/// The ordering is total and consistent with `Eq`: as notes are compared by
+/// pitch class, two voicings with the same frets on the same tuning compare
+/// equal regardless of how their notes are spelled.
 impl Ord for Voicing {
     fn cmp(&self, other: &Self) -> Ordering {
-        // In order to iterate over frets in reversed order, we could implement
-        // DoubleEndedIterator ... or use this hack.
-        let frets1: Vec<FretID> = self.frets().collect();
-        let frets2: Vec<FretID> = other.frets().collect();
+        let pitch_classes = |voicing: &Self| {
+            voicing
+                .uke_strings
+                .iter()
+                .map(|(root, _fret, note)| (root.pitch_class, note.pitch_class))
+                .collect_vec()
+        };
 
-        match self
-            .get_min_pressed_fret()
+        self.get_min_pressed_fret()
             .cmp(&other.get_min_pressed_fret())
-        {
-            Ordering::Equal => frets1.iter().rev().cmp(frets2.iter().rev()),
-            other => other,
-        }
+            .then_with(|| self.fret_pattern().rev().cmp(other.fret_pattern().rev()))
+            .then_with(|| pitch_classes(self).cmp(&pitch_classes(other)))
     }
 }
 
@@ -284,7 +626,7 @@ impl Default for Voicing {
 impl fmt::Debug for Voicing {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let roots = self.roots().map(|r| r.to_string()).collect::<Vec<String>>();
-        let frets = self.frets().collect::<Vec<FretID>>();
+        let frets = self.fret_pattern().collect::<Vec<Option<FretID>>>();
         let notes = self.notes().map(|n| n.to_string()).collect::<Vec<String>>();
 
         f.debug_struct("Voicing")
@@ -298,17 +640,31 @@ impl fmt::Debug for Voicing {
 impl From<&[UkeString]> for Voicing {
     fn from(uke_strings: &[UkeString]) -> Self {
         Self {
-            // Let's assume that all the Vecs coming in here have the correct size.
-            uke_strings: uke_strings.try_into().unwrap(),
+            uke_strings: uke_strings.to_vec(),
+            muted: vec![false; uke_strings.len()],
         }
     }
 }
 
+/// Muted strings are serialized as `null`.
+#[cfg(feature = "serde")]
+impl Serialize for Voicing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Voicing", 1)?;
+        state.serialize_field("uke_strings", &self.strings().collect_vec())?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use rstest::rstest;
 
     use super::*;
+    use crate::{VoicingConfig, STRING_COUNT};
+    use PitchClass::*;
 
     #[rstest(
         frets1, frets2,
@@ -322,6 +678,104 @@ mod tests {
         assert!(voicing1 < voicing2);
     }
 
+    #[rstest(
+        frets,
+        case([0, 0, 0, 0]),
+        case([1, 1, 1, 4]),
+        case([3, 3, 4, 1]),
+        case([6, 5, 4, 4]),
+    )]
+    fn test_compare_respelled(frets: [FretID; STRING_COUNT]) {
+        let voicing = Voicing::new(frets, Tuning::C);
+
+        for spelling in [Spelling::Sharps, Spelling::Flats] {
+            let respelled = voicing.respell(spelling);
+            assert_eq!(voicing, respelled);
+            assert_eq!(voicing.cmp(&respelled), Ordering::Equal);
+        }
+    }
+
+    #[rstest(
+        chord,
+        case("C"),
+        case("Eb"),
+        case("F#m7"),
+        case("Bbmaj7"),
+        case("Gdim")
+    )]
+    fn test_voicings_sorted_deterministically(chord: Chord) {
+        let config = VoicingConfig::default();
+        let voicings = chord.voicings(config).collect_vec();
+
+        assert_eq!(chord.voicings(config).collect_vec(), voicings);
+        assert!(voicings.windows(2).all(|w| w[0] <= w[1]));
+
+        // Sorting the respelled voicings leads to the same order.
+        let respelled = voicings
+            .iter()
+            .rev()
+            .map(|voicing| voicing.respell(Spelling::Flats))
+            .sorted()
+            .collect_vec();
+        assert_eq!(respelled, voicings);
+    }
+
+    #[rstest(
+        frets1, frets2, same_shape,
+        case([0, 0, 0, 3], [0, 0, 0, 3], true),
+        case([0, 0, 0, 3], [12, 0, 0, 3], true),
+        case([0, 0, 0, 3], [12, 12, 12, 15], true),
+        case([0, 0, 0, 3], [0, 0, 0, 15], true),
+        case([0, 0, 0, 3], [5, 4, 3, 3], false),
+        case([2, 2, 2, 0], [4, 4, 4, 2], false),
+    )]
+    fn test_shape(
+        frets1: [FretID; STRING_COUNT],
+        frets2: [FretID; STRING_COUNT],
+        same_shape: bool,
+    ) {
+        let voicing1 = Voicing::new(frets1, Tuning::C);
+        let voicing2 = Voicing::new(frets2, Tuning::C);
+        assert_eq!(voicing1.shape() == voicing2.shape(), same_shape);
+    }
+
+    #[rstest(
+        fret_pattern,
+        max_fret,
+        next_position,
+        case("0003", 15, Some("12 12 12 15")),
+        case("0003", 21, Some("12 12 12 15")),
+        case("0003", 14, None),
+        case("x232", 15, Some("x 14 15 14")),
+        case("5433", 17, Some("17 16 15 15")),
+        case("5433", 16, None),
+        case("12 12 12 15", 21, None)
+    )]
+    fn test_next_position(
+        fret_pattern: FretPattern,
+        max_fret: FretID,
+        next_position: Option<&str>,
+    ) {
+        let voicing = Voicing::new(fret_pattern, Tuning::C);
+        let next_position =
+            next_position.map(|s| Voicing::new(FretPattern::from_str(s).unwrap(), Tuning::C));
+        assert_eq!(voicing.next_position(max_fret), next_position);
+    }
+
+    #[test]
+    fn test_next_position_is_valid() {
+        let chord = Chord::from_str("C").unwrap();
+        let voicing = Voicing::new([0, 0, 0, 3], Tuning::C);
+        let next_position = voicing.next_position(15).unwrap();
+
+        assert!(next_position.spells_out(&chord));
+        assert_eq!(next_position.get_chords(), voicing.get_chords());
+        assert!(next_position
+            .notes()
+            .zip(voicing.notes())
+            .all(|(n1, n2)| n1.midi_number() == n2.midi_number() + 12));
+    }
+
     #[rstest(
         frets, count,
         case([0, 0, 0, 0], 0),
@@ -357,6 +811,20 @@ mod tests {
         assert_eq!(voicing.get_span(), span);
     }
 
+    #[rstest(
+        frets, fret_window,
+        case([0, 0, 0, 0], (0, 0)),
+        case([0, 0, 0, 3], (3, 3)),
+        case([2, 0, 1, 3], (1, 3)),
+        case([0, 4, 3, 3], (3, 4)),
+        case([5, 5, 5, 6], (5, 6)),
+        case([7, 0, 0, 10], (7, 10)),
+    )]
+    fn test_fret_window(frets: [FretID; STRING_COUNT], fret_window: (FretID, FretID)) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        assert_eq!(voicing.fret_window(), fret_window);
+    }
+
     #[rstest(
         frets, chord, spells_out,
         case([0, 0, 0, 3], "C", true), // G C E C
@@ -371,6 +839,56 @@ mod tests {
         assert_eq!(voicing.spells_out(&chord), spells_out);
     }
 
+    #[rstest(
+        fret_pattern, chord, spells_out,
+        case("x232", "G", true), // D G B
+        case("x003", "C", false), // C E C
+        case("0x03", "C", true), // G E C
+        case("x 7 8 x", "C", false), // G C
+    )]
+    fn test_spells_out_muted(fret_pattern: FretPattern, chord: Chord, spells_out: bool) {
+        let voicing = Voicing::new(fret_pattern, Tuning::C);
+        assert_eq!(voicing.spells_out(&chord), spells_out);
+    }
+
+    #[rstest(
+        s,
+        tuning,
+        case("2220", Tuning::C),
+        case("x232", Tuning::C),
+        case("0003", Tuning::D),
+        case("7 8 9 10", Tuning::C),
+        case("x32010", Tuning::Guitalele)
+    )]
+    fn test_to_fret_pattern(s: &str, tuning: Tuning) {
+        let fret_pattern = FretPattern::from_str(s).unwrap();
+        let voicing = Voicing::new(fret_pattern.clone(), tuning);
+
+        assert_eq!(voicing.to_fret_pattern(), fret_pattern);
+        assert_eq!(voicing.to_fret_pattern().to_string(), s);
+    }
+
+    #[test]
+    fn test_muted_strings() {
+        let voicing = Voicing::new(FretPattern::from_str("x232").unwrap(), Tuning::C);
+
+        assert_eq!(voicing.strings().len(), STRING_COUNT);
+        assert_eq!(voicing.uke_strings().count(), 3);
+        assert_eq!(voicing.roots().count(), STRING_COUNT);
+        assert_eq!(
+            voicing.fret_pattern().collect_vec(),
+            vec![None, Some(2), Some(3), Some(2)]
+        );
+        assert_eq!(voicing.count_pressed_strings(), 3);
+        assert_eq!(voicing.get_min_fret(), 2);
+        assert_eq!(voicing.fingers_on_strings(), [0, 1, 3, 2]);
+        assert_eq!(voicing, Voicing::new([0, 2, 3, 2], Tuning::C).mute(0));
+
+        let chord = Chord::from_str("G").unwrap();
+        let d = Note::from_str("D").unwrap();
+        assert_eq!(voicing.get_chords(), vec![chord.with_bass(d)]);
+    }
+
     #[rstest(
         frets, chord, tuning,
         case([0, 0, 0, 3], "C", Tuning::C),
@@ -383,6 +901,158 @@ mod tests {
         assert_eq!(chords, vec![chord]);
     }
 
+    #[rstest(
+        tuning, midi_numbers,
+        case(Tuning::C, [67, 60, 64, 69]),
+        case(Tuning::CLowG, [55, 60, 64, 69]),
+        case(Tuning::D, [69, 62, 66, 71]),
+        case(Tuning::G, [62, 55, 59, 64]),
+        case(Tuning::Baritone, [50, 55, 59, 64]),
+    )]
+    fn test_open_strings_midi_numbers(tuning: Tuning, midi_numbers: [u8; STRING_COUNT]) {
+        let voicing = Voicing::new([0, 0, 0, 0], tuning);
+        let numbers: Vec<u8> = voicing.notes().map(|n| n.midi_number()).collect();
+        assert_eq!(numbers, midi_numbers);
+    }
+
+    #[rstest(
+        frets, difficulty,
+        case([0, 0, 0, 0], 0),
+        case([0, 0, 0, 3], 4),
+        case([2, 0, 1, 0], 6),
+        case([3, 2, 1, 1], 13),
+        case([5, 4, 3, 3], 14),
+        case([9, 7, 8, 7], 16),
+    )]
+    fn test_difficulty(frets: [FretID; STRING_COUNT], difficulty: u32) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        assert_eq!(voicing.difficulty(), difficulty);
+    }
+
+    #[test]
+    fn test_difficulty_open_vs_barre() {
+        // Open C is easier to play than C as a barre chord at the 7th fret.
+        let open_c = Voicing::new([0, 0, 0, 3], Tuning::C);
+        let barre_c = Voicing::new([12, 7, 8, 7], Tuning::C);
+
+        assert!(barre_c.has_barre());
+        assert!(open_c.difficulty() < barre_c.difficulty());
+    }
+
+    #[rstest(
+        frets, barre,
+        case([0, 0, 0, 3], None),
+        case([3, 2, 1, 1], Some((1, 2..=3))),
+        case([1, 1, 1, 1], Some((1, 0..=3))),
+        case([1, 3, 3, 1], Some((1, 0..=3))),
+        case([3, 1, 1, 3], Some((1, 1..=2))),
+        case([0, 2, 1, 1], Some((1, 2..=3))),
+        case([2, 1, 1, 0], None),
+        case([1, 2, 1, 2], None),
+    )]
+    fn test_barre(frets: [FretID; STRING_COUNT], barre: Option<(FretID, RangeInclusive<usize>)>) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        assert_eq!(voicing.barre(), barre);
+    }
+
+    #[rstest(
+        frets, bass,
+        case([0, 0, 0, 3], "C"), // G4 C4 E4 C5
+        case([0, 4, 0, 3], "E"), // G4 E4 E4 C5
+        case([2, 0, 1, 0], "C"), // A4 C4 F4 A4
+        case([0, 7, 0, 0], "E"), // G4 G4 E4 A4
+    )]
+    fn test_bass(frets: [FretID; STRING_COUNT], bass: Note) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        assert_eq!(voicing.bass(), bass);
+    }
+
+    #[test]
+    fn test_get_chords_first_inversion() {
+        // G E E C: C major with E in the bass.
+        let voicing = Voicing::new([0, 4, 0, 3], Tuning::C);
+        let chords = voicing.get_chords();
+
+        let c = Chord::from_str("C").unwrap();
+        let e = Note::from_str("E").unwrap();
+        assert_eq!(chords, vec![c.clone().with_bass(e)]);
+        assert_eq!(chords[0].bass, Some(e));
+        assert_eq!(chords[0].inversion(e), Some(1));
+        assert_eq!(chords[0].to_string(), "C/E - C major");
+    }
+
+    #[rstest(
+        frets, intervals,
+        // G4 C4 E4 A4, sorted C4 E4 G4 A4
+        case([0, 0, 0, 0], vec![("M3", 0), ("P5", 0), ("M6", 0), ("m3", 0), ("P4", 0), ("M2", 0)]),
+        // G4 C4 E4 C5, sorted C4 E4 G4 C5
+        case([0, 0, 0, 3], vec![("M3", 0), ("P5", 0), ("P1", 1), ("m3", 0), ("m6", 0), ("P4", 0)]),
+        // G4 C4 E4 E5, sorted C4 E4 G4 E5: C4 to E5 is a major tenth
+        case([0, 0, 0, 7], vec![("M3", 0), ("P5", 0), ("M3", 1), ("m3", 0), ("P1", 1), ("M6", 0)]),
+    )]
+    fn test_internal_intervals(frets: [FretID; STRING_COUNT], intervals: Vec<(&str, Octave)>) {
+        let voicing = Voicing::new(frets, Tuning::C);
+        let intervals: Vec<(Interval, Octave)> = intervals
+            .iter()
+            .map(|(s, octaves)| (s.parse().unwrap(), *octaves))
+            .collect();
+        assert_eq!(voicing.internal_intervals(), intervals);
+    }
+
+    #[rstest(chord, case("G"), case("C"), case("Am"))]
+    fn test_low_g_tuning(chord: Chord) {
+        let config = VoicingConfig::default();
+        let low_g_config = VoicingConfig {
+            tuning: Tuning::CLowG,
+            ..config
+        };
+
+        // Both tunings use the same note names on the same strings, so the same
+        // frets are pressed and the spans are the same.
+        let voicings: Vec<Voicing> = chord.voicings(config).collect();
+        let low_g_voicings: Vec<Voicing> = chord.voicings(low_g_config).collect();
+        assert_eq!(voicings, low_g_voicings);
+
+        for (v1, v2) in voicings.iter().zip(low_g_voicings.iter()) {
+            assert_eq!(v1.get_span(), v2.get_span());
+
+            // But the notes on the G string are played an octave lower.
+            let (_, _, n1) = v1.uke_strings().next().unwrap();
+            let (_, _, n2) = v2.uke_strings().next().unwrap();
+            assert_eq!(n1.octave(), n2.octave() + 1);
+        }
+    }
+
+    #[rstest(
+        chord, frets, c_bass,
+        // G3 D4 G4 B4 (G/D in standard C tuning)
+        case("G", [0, 2, 3, 2], "D"),
+        // G3 D4 G4 Bb4 (Gm/D in standard C tuning)
+        case("Gm", [0, 2, 3, 1], "D"),
+        // G3 D4 F4 B4 (G7/D in standard C tuning)
+        case("G7", [0, 2, 1, 2], "D"),
+    )]
+    fn test_low_g_tuning_bass(chord: Chord, frets: [FretID; STRING_COUNT], c_bass: Note) {
+        let config = VoicingConfig {
+            tuning: Tuning::CLowG,
+            ..VoicingConfig::default()
+        };
+        let voicing = Voicing::new(frets, Tuning::CLowG);
+        assert!(chord.voicings(config).any(|v| v == voicing));
+
+        // With a low G string the open G is the lowest sounding note, so the
+        // chord is played in root position.
+        let bass = voicing.bass();
+        assert_eq!(bass.pitch_class, chord.root.pitch_class);
+        assert_eq!(bass.octave(), 3);
+        assert_eq!(voicing.get_chords()[0], chord);
+
+        // In standard C tuning the same frets give an inversion instead.
+        let voicing = Voicing::new(frets, Tuning::C);
+        assert_eq!(voicing.bass().pitch_class, c_bass.pitch_class);
+        assert_ne!(voicing.get_chords()[0], chord);
+    }
+
     #[rstest(
         frets,
         case([1, 2, 3, 4]),
@@ -392,6 +1062,85 @@ mod tests {
         assert!(voicing.get_chords().is_empty());
     }
 
+    #[rstest(
+        frets, capo, chord, roots, notes,
+        // With a capo at the second fret, D major is played using the C major shape.
+        case([0, 0, 0, 3], 2, "D", "A D F# B", "A D F# D"),
+        case([0, 0, 0, 3], 0, "C", "G C E A", "G C E C"),
+        // The notes (but not the roots of the strings) are spelled as in the chord.
+        case([0, 0, 0, 3], 3, "Eb", "A# D# G C", "Bb Eb G Eb"),
+    )]
+    fn test_capo_shape(
+        frets: [FretID; STRING_COUNT],
+        capo: FretID,
+        chord: Chord,
+        roots: &str,
+        notes: &str,
+    ) {
+        let voicing = Voicing::new(frets, Tuning::C).capo_shape(capo, &chord);
+        assert_eq!(voicing.frets().collect_vec(), frets);
+        assert_eq!(voicing.roots().join(" "), roots);
+        assert_eq!(voicing.notes().join(" "), notes);
+        assert_eq!(
+            voicing.get_chords()[0].root.pitch_class,
+            chord.root.pitch_class
+        );
+    }
+
+    #[test]
+    fn test_capo_shape_muted_string() {
+        let chord = Chord::from_str("D").unwrap();
+        let voicing = Voicing::new(FretPattern::from_str("x003").unwrap(), Tuning::C);
+        let voicing = voicing.capo_shape(2, &chord);
+        assert_eq!(voicing.to_fret_pattern().to_string(), "x003");
+        assert_eq!(voicing.notes().join(" "), "D F# D");
+    }
+
+    #[rstest(
+        fret_pattern,
+        chord,
+        explanation,
+        case(
+            "0003",
+            "C",
+            "  A   3  C  R\n  E   0  E  3\n  C   0  C  R\n  G   0  G  5"
+        ),
+        case("x232", "G", "  A   2  B  3\n  E   3  G  R\n  C   2  D  5\n  G   x"),
+        case(
+            "0000",
+            "C",
+            "  A   0  A  6 (non-chord tone)\n  E   0  E  3\n  C   0  C  R\n  G   0  G  5"
+        )
+    )]
+    fn test_explain(fret_pattern: FretPattern, chord: Chord, explanation: &str) {
+        let voicing = Voicing::new(fret_pattern, Tuning::C);
+        assert_eq!(voicing.explain(&chord), explanation);
+    }
+
+    #[rstest(
+        from, to, diff,
+        case(
+            "0003",
+            "0003",
+            "  A   3 ->  3  0   finger 3 -> 3\n  E   0 ->  0  0\n  C   0 ->  0  0\n  G   0 ->  0  0"
+        ),
+        case(
+            "0003",
+            "0232",
+            "  A   3 ->  2  -1  finger 3 -> 2\n  E   0 ->  3  +3  finger - -> 3\n  C   0 ->  2  +2  finger - -> 1\n  G   0 ->  0  0"
+        ),
+        case(
+            "x232",
+            "0000",
+            "  A   2 ->  0  -2  finger 2 -> -\n  E   3 ->  0  -3  finger 3 -> -\n  C   2 ->  0  -2  finger 1 -> -\n  G   x ->  0  0"
+        ),
+    )]
+    fn test_explain_diff(from: FretPattern, to: FretPattern, diff: &str) {
+        let from = Voicing::new(from, Tuning::C);
+        let to = Voicing::new(to, Tuning::C);
+        assert_eq!(from.explain_diff(&to), diff);
+    }
+
     #[rstest(
         frets, has_barre,
         // No fingered strings.
@@ -511,6 +1260,91 @@ mod tests {
     ) {
         let voicing1 = Voicing::new(frets1, Tuning::C);
         let voicing2 = Voicing::new(frets2, Tuning::C);
-        assert_eq!(voicing1.semitone_distance(voicing2), dist);
+        assert_eq!(voicing1.semitone_distance(&voicing2), dist);
+    }
+
+    #[rstest(
+        chord,
+        frets,
+        doubled,
+        missing,
+        // The root is doubled.
+        case("C", [0, 0, 0, 3], vec!["P1"], vec![]),
+        case("C", [5, 4, 3, 3], vec!["P1"], vec![]),
+        case("C", [5, 7, 8, 7], vec!["P1"], vec![]),
+        // The fifth is doubled.
+        case("C", [0, 4, 3, 3], vec!["P5"], vec![]),
+        // The seventh is doubled and the fifth is missing.
+        case("C7", [3, 0, 0, 1], vec!["m7"], vec!["P5"]),
+        case("G7", [0, 2, 1, 2], vec![], vec![]),
+        // The root is missing.
+        case("Cmaj9", [0, 2, 0, 2], vec![], vec!["P1"]),
+        // Notes outside of the chord are ignored.
+        case("C", [0, 0, 0, 0], vec![], vec![]),
+        // Omitted tones are not missing.
+        case("C(no5)", [5, 4, 3, 3], vec!["P1"], vec![]),
+        case("C(no3)", [0, 0, 3, 3], vec!["P1", "P5"], vec![])
+    )]
+    fn test_tone_analysis(
+        chord: Chord,
+        frets: [FretID; STRING_COUNT],
+        doubled: Vec<&str>,
+        missing: Vec<&str>,
+    ) {
+        let parse = |intervals: Vec<&str>| -> Vec<Interval> {
+            intervals
+                .iter()
+                .map(|i| Interval::from_str(i).unwrap())
+                .collect()
+        };
+
+        let voicing = Voicing::new(frets, Tuning::C);
+        let analysis = voicing.tone_analysis(&chord);
+
+        assert_eq!(analysis.doubled, parse(doubled));
+        assert_eq!(analysis.missing, parse(missing));
+    }
+
+    #[rstest(
+        fret_pattern,
+        tuning,
+        count,
+        case("0003", Tuning::C, 3),
+        case("5433", Tuning::C, 0),
+        case("x000", Tuning::C, 3),
+        case("x 7 8 x", Tuning::C, 0),
+        case("320003", Tuning::Guitalele, 3)
+    )]
+    fn test_count_open_strings(fret_pattern: FretPattern, tuning: Tuning, count: usize) {
+        let voicing = Voicing::new(fret_pattern, tuning);
+        assert_eq!(voicing.count_open_strings(), count);
+    }
+
+    #[rstest(
+        fret_pattern,
+        tuning,
+        pitch_classes,
+        case("0003", Tuning::C, vec![C, E, G]),
+        case("0232", Tuning::C, vec![D, G, B]),
+        case("x000", Tuning::C, vec![C, E, A]),
+        case("2010", Tuning::D, vec![D, G, B]),
+        case("320003", Tuning::Guitalele, vec![C, E, G]),
+        case("x x x 0", Tuning::C, vec![A])
+    )]
+    fn test_pitch_classes(
+        fret_pattern: FretPattern,
+        tuning: Tuning,
+        pitch_classes: Vec<PitchClass>,
+    ) {
+        let voicing = Voicing::new(fret_pattern, tuning);
+        let expected: BTreeSet<PitchClass> = pitch_classes.iter().copied().collect();
+        assert_eq!(voicing.pitch_classes(), expected);
+
+        for pitch_class in (0..PITCH_CLASS_COUNT).map(PitchClass::from) {
+            assert_eq!(
+                voicing.contains_pitch_class(pitch_class),
+                expected.contains(&pitch_class)
+            );
+        }
     }
 }
@@ -0,0 +1,282 @@
+use crate::{Chord, FretID, Note, Semitones, Tuning, Voicing, VoicingConfig};
+
+/// Custom error for invalid settings of a `VoicingBuilder`.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum VoicingBuilderError {
+    #[error("the minimal fret ({min_fret}) is greater than the maximal fret ({max_fret})")]
+    InvalidFretRange { min_fret: FretID, max_fret: FretID },
+    #[error("the span ({max_span}) is greater than the number of frets between fret {min_fret} and fret {max_fret}")]
+    SpanTooLarge {
+        max_span: Semitones,
+        min_fret: FretID,
+        max_fret: FretID,
+    },
+    #[error(
+        "the lowest note ({}{}) is higher than the highest note ({}{})",
+        .lowest_note, .lowest_note.octave(), .highest_note, .highest_note.octave()
+    )]
+    InvalidPitchRange {
+        lowest_note: Note,
+        highest_note: Note,
+    },
+    #[error("no chord was given to find voicings for")]
+    MissingChord,
+}
+
+/// Builder for searching the voicings of a chord without constructing
+/// a `VoicingConfig` directly, e.g.
+/// `Voicing::builder().tuning(Tuning::D).frets(0, 5).for_chord(&chord).build_all()`.
+///
+/// Settings that are not given explicitly take their values from
+/// `VoicingConfig::default`.
+#[derive(Clone, Default)]
+pub struct VoicingBuilder<'a> {
+    config: VoicingConfig,
+    chord: Option<&'a Chord>,
+}
+
+impl<'a> VoicingBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tuning(self, tuning: Tuning) -> Self {
+        let config = VoicingConfig {
+            tuning,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    /// Only consider the frets from `min_fret` up to `max_fret` (inclusive).
+    pub fn frets(self, min_fret: FretID, max_fret: FretID) -> Self {
+        let config = VoicingConfig {
+            min_fret,
+            max_fret,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    /// Set the maximal span between the first and the last fret pressed down.
+    pub fn span(self, max_span: Semitones) -> Self {
+        let config = VoicingConfig {
+            max_span,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    /// Order the voicings by their difficulty instead of by their position.
+    pub fn sort_by_difficulty(self, sort_by_difficulty: bool) -> Self {
+        let config = VoicingConfig {
+            sort_by_difficulty,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    /// Skip the voicings that are physically impossible to play.
+    pub fn only_playable(self, only_playable: bool) -> Self {
+        let config = VoicingConfig {
+            only_playable,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    /// Only keep the voicings whose notes all sound between `lowest_note`
+    /// and `highest_note` (inclusive), `None` leaving a side unbounded.
+    pub fn pitch_range(self, lowest_note: Option<Note>, highest_note: Option<Note>) -> Self {
+        let config = VoicingConfig {
+            lowest_note,
+            highest_note,
+            ..self.config
+        };
+        Self { config, ..self }
+    }
+
+    pub fn for_chord(self, chord: &'a Chord) -> Self {
+        Self {
+            chord: Some(chord),
+            ..self
+        }
+    }
+
+    /// Validate the settings and return the resulting configuration.
+    pub fn build_config(&self) -> Result<VoicingConfig, VoicingBuilderError> {
+        let VoicingConfig {
+            min_fret,
+            max_fret,
+            max_span,
+            lowest_note,
+            highest_note,
+            ..
+        } = self.config;
+
+        if min_fret > max_fret {
+            return Err(VoicingBuilderError::InvalidFretRange { min_fret, max_fret });
+        }
+
+        if max_span as usize > (max_fret - min_fret) as usize + 1 {
+            return Err(VoicingBuilderError::SpanTooLarge {
+                max_span,
+                min_fret,
+                max_fret,
+            });
+        }
+
+        if let (Some(lowest_note), Some(highest_note)) = (lowest_note, highest_note) {
+            if lowest_note.midi_number() > highest_note.midi_number() {
+                return Err(VoicingBuilderError::InvalidPitchRange {
+                    lowest_note,
+                    highest_note,
+                });
+            }
+        }
+
+        Ok(self.config)
+    }
+
+    /// Validate the settings and return all the voicings of the chord
+    /// in the same order as `Chord::voicings`.
+    pub fn build_all(self) -> Result<impl Iterator<Item = Voicing> + 'a, VoicingBuilderError> {
+        let config = self.build_config()?;
+        let chord = self.chord.ok_or(VoicingBuilderError::MissingChord)?;
+
+        Ok(chord.voicings(config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        chord,
+        tuning,
+        min_fret,
+        max_fret,
+        max_span,
+        case("C", Tuning::C, 0, 12, 4),
+        case("C", Tuning::D, 0, 5, 3),
+        case("Bbm7", Tuning::C, 3, 10, 5),
+        case("G7", Tuning::Guitalele, 0, 7, 4)
+    )]
+    fn test_build_all(
+        chord: Chord,
+        tuning: Tuning,
+        min_fret: FretID,
+        max_fret: FretID,
+        max_span: Semitones,
+    ) {
+        let config = VoicingConfig {
+            tuning,
+            min_fret,
+            max_fret,
+            max_span,
+            ..Default::default()
+        };
+
+        let voicings = Voicing::builder()
+            .tuning(tuning)
+            .frets(min_fret, max_fret)
+            .span(max_span)
+            .for_chord(&chord)
+            .build_all()
+            .unwrap()
+            .collect_vec();
+
+        assert_eq!(voicings, chord.voicings(config).collect_vec());
+    }
+
+    #[test]
+    fn test_build_all_default() {
+        let chord = Chord::from_str("F#m").unwrap();
+        let voicings = Voicing::builder()
+            .for_chord(&chord)
+            .build_all()
+            .unwrap()
+            .collect_vec();
+
+        assert_eq!(
+            voicings,
+            chord.voicings(VoicingConfig::default()).collect_vec()
+        );
+    }
+
+    #[rstest(
+        min_fret, max_fret, max_span, error,
+        case(5, 3, 4, VoicingBuilderError::InvalidFretRange { min_fret: 5, max_fret: 3 }),
+        case(0, 2, 4, VoicingBuilderError::SpanTooLarge { max_span: 4, min_fret: 0, max_fret: 2 }),
+        case(7, 9, 4, VoicingBuilderError::SpanTooLarge { max_span: 4, min_fret: 7, max_fret: 9 }),
+    )]
+    fn test_build_all_invalid(
+        min_fret: FretID,
+        max_fret: FretID,
+        max_span: Semitones,
+        error: VoicingBuilderError,
+    ) {
+        let chord = Chord::from_str("C").unwrap();
+        let res = Voicing::builder()
+            .frets(min_fret, max_fret)
+            .span(max_span)
+            .for_chord(&chord)
+            .build_all();
+
+        assert_eq!(res.err(), Some(error));
+    }
+
+    #[test]
+    fn test_build_all_missing_chord() {
+        let res = Voicing::builder().build_all();
+        assert_eq!(res.err(), Some(VoicingBuilderError::MissingChord));
+    }
+
+    #[test]
+    fn test_build_all_pitch_range() {
+        let chord = Chord::from_str("C").unwrap();
+        let lowest_note = Note::parse_with_octave("G4").ok();
+        let config = VoicingConfig {
+            lowest_note,
+            ..Default::default()
+        };
+        let voicings = Voicing::builder()
+            .pitch_range(lowest_note, None)
+            .for_chord(&chord)
+            .build_all()
+            .unwrap()
+            .collect_vec();
+
+        assert_eq!(voicings, chord.voicings(config).collect_vec());
+    }
+
+    #[test]
+    fn test_build_all_invalid_pitch_range() {
+        let chord = Chord::from_str("C").unwrap();
+        let lowest_note = Note::parse_with_octave("A5").unwrap();
+        let highest_note = Note::parse_with_octave("C4").unwrap();
+        let res = Voicing::builder()
+            .pitch_range(Some(lowest_note), Some(highest_note))
+            .for_chord(&chord)
+            .build_all();
+
+        let error = res.err().unwrap();
+        assert_eq!(
+            error,
+            VoicingBuilderError::InvalidPitchRange {
+                lowest_note,
+                highest_note
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "the lowest note (A5) is higher than the highest note (C4)"
+        );
+    }
+}
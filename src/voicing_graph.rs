@@ -1,26 +1,45 @@
 use std::iter::Iterator;
 
 use itertools::Itertools;
-use petgraph::{algo::all_simple_paths, prelude::NodeIndex, Graph};
+use petgraph::{algo::all_simple_paths, prelude::NodeIndex, stable_graph::StableGraph};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-use crate::{Chord, ChordSequence, Distance, Semitones, Voicing, VoicingConfig};
+use crate::{Chord, ChordSequence, Distance, DistanceWeights, Semitones, Voicing, VoicingConfig};
 
 const MAX_DIST: Semitones = 10;
 
+/// Custom error for voicings that cannot be used to start a voice leading.
+#[derive(Debug, thiserror::Error)]
+#[error("the start voicing {frets} does not spell out chord {chord}")]
+pub struct InvalidStartVoicingError {
+    frets: String,
+    chord: String,
+}
+
 /// A graph whose nodes represent chord voicings and whose edges
 /// are weighted by the distances between the voicings. It is used
 /// to find the (by some definition) optimal voice leading for
 /// a given sequence of chords.
+///
+/// The graph is built layer by layer, one layer of voicing nodes per chord.
+/// A stable graph is used so that node indices stay valid when unused nodes
+/// are removed.
 pub struct VoicingGraph {
-    graph: Graph<Voicing, Distance>,
+    graph: StableGraph<Voicing, Distance>,
     start_node: NodeIndex,
     end_node: NodeIndex,
+    /// The nodes of the last chord added (initially the start node).
+    last_nodes: Vec<NodeIndex>,
     config: VoicingConfig,
+    weights: DistanceWeights,
 }
 
 impl VoicingGraph {
-    pub fn new(config: VoicingConfig) -> Self {
-        let mut graph = Graph::new();
+    /// Create an empty voicing graph. The voicings are determined according to
+    /// `config`, the distances between them are weighted by `weights`.
+    pub fn new(config: VoicingConfig, weights: DistanceWeights) -> Self {
+        let mut graph = StableGraph::new();
 
         // We need a fake start and end node for finding the best path.
         let start_node = graph.add_node(Voicing::default());
@@ -30,63 +49,137 @@ impl VoicingGraph {
             graph,
             start_node,
             end_node,
+            last_nodes: vec![start_node],
             config,
+            weights,
         }
     }
 
-    fn add_nodes(&mut self, chord: &Chord) -> Vec<NodeIndex> {
-        let voicings: Vec<Voicing> = chord.voicings(self.config).collect();
-
+    fn add_nodes(&mut self, voicings: Vec<Voicing>) -> Vec<NodeIndex> {
         // When determining the "path" of a single chord we want to get the voicing
         // in the lowest position. To achieve this we have to iterate over the chord's
         // voicings in reversed order to account for the behaviour of the path-finding
         // algorithm when all distances are equal.
         voicings
-            .iter()
+            .into_iter()
             .rev()
-            .map(|voicing| self.graph.add_node(*voicing))
+            .map(|voicing| self.graph.add_node(voicing))
             .collect()
     }
 
-    fn add_edges(&mut self, left_nodes: &[NodeIndex], right_nodes: &[NodeIndex]) {
-        for (l, r) in left_nodes.iter().cartesian_product(right_nodes.iter()) {
-            let l_voicing = self.graph[*l];
-            let r_voicing = self.graph[*r];
+    /// Return the distance between the voicings of nodes `l` and `r`.
+    fn distance(&self, l: NodeIndex, r: NodeIndex) -> Distance {
+        let l_voicing = &self.graph[l];
+        let r_voicing = &self.graph[r];
 
-            let dist = match l {
-                l if *l == self.start_node => Distance::default(),
-                _ => l_voicing.distance(r_voicing),
-            };
+        match l {
+            // Only the position of the first voicing matters.
+            l if l == self.start_node => {
+                Distance::weighted(0, 0, r_voicing.get_max_fret(), self.weights)
+            }
+            _ => l_voicing.weighted_distance(r_voicing, self.weights),
+        }
+    }
+
+    /// Return the distances between all pairs of left and right nodes
+    /// (computed in parallel if the `rayon` feature is enabled).
+    fn distances(
+        &self,
+        left_nodes: &[NodeIndex],
+        right_nodes: &[NodeIndex],
+    ) -> Vec<(NodeIndex, NodeIndex, Distance)> {
+        let pairs = left_nodes.iter().cartesian_product(right_nodes.iter());
+
+        #[cfg(feature = "rayon")]
+        let pairs = pairs.collect_vec().into_par_iter();
+
+        pairs.map(|(&l, &r)| (l, r, self.distance(l, r))).collect()
+    }
 
+    fn add_edges(&mut self, left_nodes: &[NodeIndex], right_nodes: &[NodeIndex]) {
+        for (l, r, dist) in self.distances(left_nodes, right_nodes) {
             // Ignore voicings that are too far away from each other.
             if dist.semitone_distance() <= MAX_DIST {
-                self.graph.add_edge(*l, *r, dist);
+                self.graph.add_edge(l, r, dist);
             }
         }
     }
 
+    /// Add the chords of `chord_seq` to the graph (after the chords added before).
     pub fn add(&mut self, chord_seq: &ChordSequence) {
-        // Add edges from the start node to all the voicings of the first chord.
-        let mut prev_nodes = vec![self.start_node];
+        self.add_chords(chord_seq, None);
+    }
 
-        for chord in chord_seq.chords() {
-            let nodes = self.add_nodes(chord);
-            self.add_edges(&prev_nodes, &nodes);
+    /// Add the chords of `chord_seq` to the graph with the first chord
+    /// played as `first` so that only the voicings of the other chords are
+    /// optimized.
+    ///
+    /// Return an error if `first` does not spell out the first chord.
+    pub fn add_with_start(
+        &mut self,
+        chord_seq: &ChordSequence,
+        first: Voicing,
+    ) -> Result<(), InvalidStartVoicingError> {
+        if let Some(chord) = chord_seq.chords().next() {
+            if !first.spells_out(chord) {
+                let frets = first.to_fret_pattern().to_string();
+                let chord = chord.name();
+                return Err(InvalidStartVoicingError { frets, chord });
+            }
+        }
+
+        self.add_chords(chord_seq, Some(first));
+        Ok(())
+    }
+
+    fn add_chords(&mut self, chord_seq: &ChordSequence, first: Option<Voicing>) {
+        let skip = first.is_some() as usize;
+        let chords = chord_seq.chords().skip(skip).collect_vec();
+        let voicings = first
+            .into_iter()
+            .map(|voicing| vec![voicing])
+            .chain(chord_voicings(&chords, self.config));
+
+        for voicings in voicings {
+            self.push_voicings(voicings);
+        }
+    }
+
+    /// Extend the graph by the voicings of `chord` so that the paths
+    /// end with one of them. Only the edges to the new voicings are computed,
+    /// so a chord sequence can be built up one chord at a time.
+    pub fn push(&mut self, chord: &Chord) {
+        self.push_voicings(chord.voicings(self.config).collect());
+    }
 
-            prev_nodes = nodes;
+    fn push_voicings(&mut self, voicings: Vec<Voicing>) {
+        // Detach the voicings of the previous chord from the end node.
+        for node in self.last_nodes.iter() {
+            if let Some(edge) = self.graph.find_edge(*node, self.end_node) {
+                self.graph.remove_edge(edge);
+            }
         }
 
-        // Add edges from all the voicings of the last chord to the end node.
-        for node in prev_nodes.iter() {
+        // Add edges from the voicings of the previous chord (or from the start node)
+        // to the new voicings and from the new voicings to the end node.
+        let nodes = self.add_nodes(voicings);
+        self.add_edges(&self.last_nodes.clone(), &nodes);
+
+        for node in nodes.iter() {
             self.graph
                 .add_edge(*node, self.end_node, Distance::default());
         }
 
-        // Remove unused nodes.
+        self.last_nodes = nodes;
+
+        // Remove unused nodes. The start and end node have to be kept in any case
+        // (e.g. if the first chord has no voicings, the start node has no outgoing
+        // edges). Without any chords, there is no path, not even an empty one.
+        let start_node = self.start_node;
         let end_node = self.end_node;
 
         self.graph
-            .retain_nodes(|g, n| g.neighbors(n).count() > 0 || n == end_node);
+            .retain_nodes(|g, n| g.neighbors(n).count() > 0 || n == start_node || n == end_node);
     }
 
     /// Return an iterator over the paths between the voicing nodes.
@@ -97,7 +190,7 @@ impl VoicingGraph {
         &self,
         max_suggestions: usize,
     ) -> impl Iterator<Item = (Vec<Voicing>, Distance)> + '_ {
-        let all_paths = all_simple_paths::<Vec<NodeIndex>, &Graph<Voicing, Distance>>(
+        let all_paths = all_simple_paths::<Vec<NodeIndex>, &StableGraph<Voicing, Distance>>(
             &self.graph,
             self.start_node,
             self.end_node,
@@ -125,7 +218,7 @@ impl VoicingGraph {
                 .enumerate()
                 // Ignore start and end node.
                 .filter(|(i, _node)| *i > 0 && *i < path.len() - 1)
-                .map(|(_i, node)| self.graph[*node])
+                .map(|(_i, node)| self.graph[*node].clone())
                 .collect();
 
             paths_with_dist.push((voicing_path, weight_sum(&path)))
@@ -134,3 +227,274 @@ impl VoicingGraph {
         paths_with_dist.into_iter()
     }
 }
+
+/// Return the voicings of each of the chords (searched in parallel if the
+/// `rayon` feature is enabled).
+fn chord_voicings(chords: &[&Chord], config: VoicingConfig) -> Vec<Vec<Voicing>> {
+    #[cfg(not(feature = "rayon"))]
+    let chords = chords.iter();
+    #[cfg(feature = "rayon")]
+    let chords = chords.par_iter();
+
+    chords
+        .map(|chord| chord.voicings(config).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(chord_seq, case("C F G"), case("Am Dm E7"), case("C G Am F"))]
+    fn test_paths(chord_seq: &str) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        let paths: Vec<_> = voicing_graph.paths(3).collect();
+        assert_eq!(paths.len(), 3);
+
+        for ((path1, dist1), (path2, dist2)) in paths.iter().tuple_windows() {
+            assert_eq!(path1.len(), chord_seq.chords().count());
+            assert!(dist1 <= dist2);
+            assert_ne!(path1, path2);
+        }
+
+        // The best path is the same as the one found when asking for a single path.
+        let (best_path, _dist) = voicing_graph.paths(1).next().unwrap();
+        assert_eq!(best_path, paths[0].0);
+    }
+
+    #[rstest(
+        chord_seq,
+        case("Csus4 C F"),
+        case("Csus2 C G7sus4 G7"),
+        case("C6 Am7 F6 G"),
+        case("C5 F5 G5"),
+        case("Caug Cdim C")
+    )]
+    fn test_paths_few_tones(chord_seq: &str) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        let (path, _dist) = voicing_graph.paths(1).next().unwrap();
+        assert_eq!(path.len(), chord_seq.chords().count());
+
+        for (voicing, chord) in path.iter().zip(chord_seq.chords()) {
+            assert!(voicing.spells_out(chord));
+        }
+    }
+
+    #[rstest(
+        chord_seq,
+        case("Cm C6"),
+        case("Cm C6 Am7"),
+        case("C6 Cm"),
+        case("C6 Cm Am7")
+    )]
+    fn test_paths_no_voicings(chord_seq: &str) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+        // Cm cannot be played using open strings only.
+        let config = VoicingConfig {
+            max_fret: 0,
+            ..Default::default()
+        };
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        assert_eq!(voicing_graph.paths(1).count(), 0);
+    }
+
+    #[rstest(chord, case("C"), case("Bb"), case("F#m7"), case("Csus4"))]
+    fn test_paths_single_chord(chord: &str) {
+        let chord_seq = ChordSequence::from_str(chord).unwrap();
+        let chord = Chord::from_str(chord).unwrap();
+        let config = VoicingConfig::default();
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        // The voicing in the lowest position is chosen.
+        let (path, dist) = voicing_graph.paths(1).next().unwrap();
+        assert_eq!(path, vec![chord.voicings(config).next().unwrap()]);
+        assert_eq!(dist, Distance::default());
+    }
+
+    #[test]
+    fn test_paths_empty() {
+        let chord_seq = ChordSequence::from_str("").unwrap();
+
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        assert_eq!(voicing_graph.paths(1).count(), 0);
+    }
+
+    #[test]
+    fn test_position_weight() {
+        let chord_seq = ChordSequence::from_str("C Em Am F G").unwrap();
+        let highest_fret = |weights| {
+            let mut voicing_graph = VoicingGraph::new(VoicingConfig::default(), weights);
+            voicing_graph.add(&chord_seq);
+
+            let (path, _dist) = voicing_graph.paths(1).next().unwrap();
+            path.iter().map(|v| v.get_max_fret()).max().unwrap()
+        };
+
+        // By default, the best voice leading moves up the neck ...
+        assert!(highest_fret(DistanceWeights::default()) > 5);
+
+        // ... but it stays in the lowest position if the position is weighted heavily.
+        let weights = DistanceWeights {
+            position: 10,
+            ..Default::default()
+        };
+        assert_eq!(highest_fret(weights), 4);
+    }
+
+    #[test]
+    fn test_add_with_start() {
+        let chord_seq = ChordSequence::from_str("C Em Am F G").unwrap();
+        let config = VoicingConfig::default();
+        let open_c = Voicing::new([0, 0, 0, 3], config.tuning);
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+        let (free_path, _dist) = voicing_graph.paths(1).next().unwrap();
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph
+            .add_with_start(&chord_seq, open_c.clone())
+            .unwrap();
+        let (path, _dist) = voicing_graph.paths(1).next().unwrap();
+
+        assert_eq!(path.len(), chord_seq.chords().count());
+        assert_eq!(path[0], open_c);
+        assert_ne!(free_path[0], open_c);
+
+        // Starting from open C changes the voicing chosen for the second chord.
+        assert_ne!(path[1], free_path[1]);
+        assert!(path[1].get_max_fret() <= 4);
+    }
+
+    #[rstest(
+        chord_seq,
+        frets,
+        case("C F G", [0, 0, 0, 2]),
+        case("Am Dm E7", [0, 0, 0, 3]),
+        case("G7 C", [0, 0, 0, 3])
+    )]
+    fn test_add_with_start_invalid(chord_seq: &str, frets: [u8; 4]) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+        let config = VoicingConfig::default();
+        let start = Voicing::new(frets, config.tuning);
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        assert!(voicing_graph.add_with_start(&chord_seq, start).is_err());
+    }
+
+    #[rstest(
+        chord_seq,
+        case("C F G"),
+        case("Cmaj9 Dm11 G13"),
+        case("C9 F13 G7alt C6/9"),
+        case("Am7b5 D7b9 Gm9"),
+        case("C/E F/A G7/B C")
+    )]
+    fn test_paths_keep_root_and_third(chord_seq: &str) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        // Only optional tones (such as the fifth or the ninth of thirteenth chords)
+        // are left out of the voicings, so there is no need to penalize voicings
+        // lacking the root or the third when searching for the best voice leading.
+        for (path, _dist) in voicing_graph.paths(3) {
+            for (voicing, chord) in path.iter().zip(chord_seq.chords()) {
+                let missing = voicing.tone_analysis(chord).missing;
+                assert!(missing
+                    .iter()
+                    .all(|i| i.to_number() != 1 && i.to_number() != 3));
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let chord_seq = ChordSequence::from_str("C Am F G7 Em A7 Dm G7 C/E Bbmaj7").unwrap();
+        let chords = chord_seq.chords().collect_vec();
+        let config = VoicingConfig::default();
+
+        let serial = chords
+            .iter()
+            .map(|chord| chord.voicings(config).collect_vec())
+            .collect_vec();
+        assert_eq!(chord_voicings(&chords, config), serial);
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+        let nodes = voicing_graph.graph.node_indices().collect_vec();
+
+        let serial = nodes
+            .iter()
+            .cartesian_product(nodes.iter())
+            .map(|(&l, &r)| (l, r, voicing_graph.distance(l, r)))
+            .collect_vec();
+        assert_eq!(voicing_graph.distances(&nodes, &nodes), serial);
+    }
+
+    #[rstest(
+        chord_seq,
+        case("C F G"),
+        case("Am Dm E7"),
+        case("C G Am F"),
+        case("Cm C6"),
+        case("")
+    )]
+    fn test_push(chord_seq: &str) {
+        let chord_seq = ChordSequence::from_str(chord_seq).unwrap();
+        let config = VoicingConfig::default();
+
+        let mut voicing_graph = VoicingGraph::new(config, DistanceWeights::default());
+        voicing_graph.add(&chord_seq);
+
+        let mut incremental_graph = VoicingGraph::new(config, DistanceWeights::default());
+        for chord in chord_seq.chords() {
+            incremental_graph.push(chord);
+        }
+
+        assert_eq!(
+            incremental_graph.paths(3).collect_vec(),
+            voicing_graph.paths(3).collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_push_keeps_paths_valid() {
+        let mut voicing_graph =
+            VoicingGraph::new(VoicingConfig::default(), DistanceWeights::default());
+
+        for (i, chord) in ["C", "F", "G"].iter().enumerate() {
+            let chord = Chord::from_str(chord).unwrap();
+            voicing_graph.push(&chord);
+
+            let (path, _dist) = voicing_graph.paths(1).next().unwrap();
+            assert_eq!(path.len(), i + 1);
+            assert!(path[i].spells_out(&chord));
+        }
+    }
+}
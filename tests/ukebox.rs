@@ -5,6 +5,7 @@ use assert_cmd::prelude::*; // Add methods on commands
 use indoc::indoc;
 use predicates::prelude::*; // Used for writing assertions
 use rstest::rstest;
+use serde_json::Value;
 
 #[test]
 fn test_no_args() -> Result<(), Box<dyn Error>> {
@@ -28,6 +29,29 @@ fn test_unknown_chord() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[rstest(chord, name, case("E♭maj7", "Ebmaj7"), case("CMAJ7", "Cmaj7"))]
+fn test_chord_name_lenient(chord: &str, name: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg(chord);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(format!("[{name} - ")));
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_chord_suggestion() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart");
+    cmd.arg("CmMaj7b5");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "could not parse chord name 'CmMaj7b5' (did you mean CmMaj7?)",
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_no_voicing_found() -> Result<(), Box<dyn Error>> {
     let mut cmd = Command::cargo_bin("ukebox")?;
@@ -47,7 +71,7 @@ fn test_invalid_pattern() -> Result<(), Box<dyn Error>> {
     cmd.arg("name");
     cmd.arg("blafoo");
     cmd.assert().failure().stderr(predicate::str::contains(
-        "error: invalid value 'blafoo' for '<FRET_PATTERN>': fret pattern has wrong format (should be something like 1234 or '7 8 9 10')",
+        "error: invalid value 'blafoo' for '<FRET_PATTERN>': fret pattern has wrong format (should be something like 1234, x232 or '7 8 9 10')",
     ));
 
     Ok(())
@@ -66,11 +90,91 @@ fn test_unknown_pattern() -> Result<(), Box<dyn Error>> {
 }
 
 #[test]
-fn test_no_voicing_seq_found() -> Result<(), Box<dyn Error>> {
+fn test_voice_lead_single_chord() -> Result<(), Box<dyn Error>> {
+    let output = indoc!(
+        "
+        [C - C major]
+
+        A  ||---|---|-3-|---|- C
+        E o||---|---|---|---|- E
+        C o||---|---|---|---|- C
+        G o||---|---|---|---|- G
+
+    "
+    );
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("C");
+    cmd.assert().success().stdout(output);
+
+    Ok(())
+}
+
+#[rstest(chord_seq, case(""), case(" | | "), case("\n"))]
+fn test_voice_lead_empty(chord_seq: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg(chord_seq);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the chord sequence is empty (it should contain at least one chord)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_start() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("--start").arg("0003").arg("C Em");
+    cmd.assert().success().stdout(indoc!(
+        "
+        [C - C major]
+
+        A  ||---|---|-3-|---|- C
+        E o||---|---|---|---|- E
+        C o||---|---|---|---|- C
+        G o||---|---|---|---|- G
+
+        [Em - E minor]
+
+        A  ||---|-1-|---|---|- B
+        E o||---|---|---|---|- E
+        C  ||---|---|---|-3-|- E
+        G o||---|---|---|---|- G
+
+        "
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    start,
+    error,
+    case("0002", "the start voicing 0002 does not spell out chord C"),
+    case("000", "the fret pattern has 3 frets but tuning C has 4 strings")
+)]
+fn test_voice_lead_start_invalid(start: &str, error: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("--start").arg(start).arg("C Em");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+
+    Ok(())
+}
+
+#[rstest(
+    max_fret,
+    chord_seq,
+    case("2", "C F G"),
+    // The first chord cannot be played, the second one can.
+    case("0", "Cm C6")
+)]
+fn test_no_voicing_seq_found(max_fret: &str, chord_seq: &str) -> Result<(), Box<dyn Error>> {
     let mut cmd = Command::cargo_bin("ukebox")?;
     cmd.arg("voice-lead");
-    cmd.arg("--max-fret").arg("2");
-    cmd.arg("C F G");
+    cmd.arg("--max-fret").arg(max_fret);
+    cmd.arg(chord_seq);
     cmd.assert()
         .success()
         .stdout("No matching chord voicing sequence was found\n");
@@ -98,9 +202,9 @@ fn test_no_voicing_seq_found() -> Result<(), Box<dyn Error>> {
             [C# - C# major]
 
             A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
         ")
     ),
     case(
@@ -109,9 +213,9 @@ fn test_no_voicing_seq_found() -> Result<(), Box<dyn Error>> {
             [Db - Db major]
 
             A  ||---|---|---|-4-|- Db
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- Db
-            G  ||-1-|---|---|---|- Ab
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- Db
+            G  ||=1=|---|---|---|- Ab
         ")
     ),
 )]
@@ -163,6 +267,30 @@ fn test_chart(chord: &str, chart: &'static str) -> Result<(), Box<dyn Error + 's
             D o||---|---|---|---|- D
         ")
     ),
+    case(
+        "C",
+        "c-low-g",
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+        ")
+    ),
+    case(
+        "C",
+        "baritone",
+        indoc!("
+            [C - C major]
+
+            E o||---|---|---|---|- E
+            B  ||-1-|---|---|---|- C
+            G o||---|---|---|---|- G
+            D  ||---|-2-|---|---|- E
+        ")
+    ),
 )]
 fn test_tuning(
     chord: &str,
@@ -188,8 +316,8 @@ fn test_tuning(
         indoc!("
             [C - C major]
 
-            A  -|-1-|---|---|---|- C
-            E  -|-1-|---|---|---|- G
+            A  -|=1=|---|---|---|- C
+            E  -|=1=|---|---|---|- G
             C  -|---|-2-|---|---|- E
             G  -|---|---|-3-|---|- C
                   3
@@ -201,8 +329,8 @@ fn test_tuning(
         indoc!("
             [C - C major]
 
-            A  -|-1-|---|---|---|- C
-            E  -|-1-|---|---|---|- G
+            A  -|=1=|---|---|---|- C
+            E  -|=1=|---|---|---|- G
             C  -|---|-2-|---|---|- E
             G  -|---|---|-3-|---|- C
                   3
@@ -246,8 +374,8 @@ fn test_min_fret(
         indoc!("
             [C# - C# major]
 
-            A  -|-1-|---|---|---|- C#
-            E  -|-1-|---|---|---|- G#
+            A  -|=1=|---|---|---|- C#
+            E  -|=1=|---|---|---|- G#
             C  -|---|-2-|---|---|- F
             G  -|---|---|-3-|---|- C#
                   4
@@ -260,9 +388,9 @@ fn test_min_fret(
             [C# - C# major]
 
             A  ||---|---|---|-4-|---|- C#
-            E  ||-1-|---|---|---|---|- F
-            C  ||-1-|---|---|---|---|- C#
-            G  ||-1-|---|---|---|---|- G#
+            E  ||=1=|---|---|---|---|- F
+            C  ||=1=|---|---|---|---|- C#
+            G  ||=1=|---|---|---|---|- G#
         ")
     ),
 )]
@@ -303,9 +431,9 @@ fn test_max_span(
             [C# - C# major]
 
             A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
         ")
     ),
     case(
@@ -315,9 +443,9 @@ fn test_max_span(
             [C# - C# major]
 
             A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
         ")
     ),
     case(
@@ -327,9 +455,9 @@ fn test_max_span(
             [Db - Db major]
 
             A  ||---|---|---|-4-|- Db
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- Db
-            G  ||-1-|---|---|---|- Ab
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- Db
+            G  ||=1=|---|---|---|- Ab
         ")
     ),
 )]
@@ -357,31 +485,39 @@ fn test_transpose(
         None,
         None,
         indoc!("
-            [C# - C# major]
+            [C# - C# major] (1/5)
 
             A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
 
-            A  -|-1-|---|---|---|- C#
-            E  -|-1-|---|---|---|- G#
+            [C# - C# major] (2/5)
+
+            A  -|=1=|---|---|---|- C#
+            E  -|=1=|---|---|---|- G#
             C  -|---|-2-|---|---|- F
             G  -|---|---|-3-|---|- C#
                   4
 
+            [C# - C# major] (3/5)
+
             A  -|---|---|-3-|---|- F
             E  -|---|---|---|-4-|- C#
             C  -|---|---|-2-|---|- G#
             G  -|-1-|---|---|---|- C#
                   6
 
-            A  -|-1-|---|---|---|- F
-            E  -|---|-2-|---|---|- C#
-            C  -|-1-|---|---|---|- G#
+            [C# - C# major] (4/5)
+
+            A  -|=1=|---|---|---|- F
+            E  -|===|-2-|---|---|- C#
+            C  -|=1=|---|---|---|- G#
             G  -|---|---|-3-|---|- F
                   8
 
+            [C# - C# major] (5/5)
+
             A  -|---|---|---|-4-|- G#
             E  -|---|-2-|---|---|- C#
             C  -|-1-|---|---|---|- G#
@@ -394,7 +530,7 @@ fn test_transpose(
         Some("5"),
         None,
         indoc!("
-            [C# - C# major]
+            [C# - C# major] (1/3)
 
             A  -|---|---|-3-|---|- F
             E  -|---|---|---|-4-|- C#
@@ -402,12 +538,16 @@ fn test_transpose(
             G  -|-1-|---|---|---|- C#
                   6
 
-            A  -|-1-|---|---|---|- F
-            E  -|---|-2-|---|---|- C#
-            C  -|-1-|---|---|---|- G#
+            [C# - C# major] (2/3)
+
+            A  -|=1=|---|---|---|- F
+            E  -|===|-2-|---|---|- C#
+            C  -|=1=|---|---|---|- G#
             G  -|---|---|-3-|---|- F
                   8
 
+            [C# - C# major] (3/3)
+
             A  -|---|---|---|-4-|- G#
             E  -|---|-2-|---|---|- C#
             C  -|-1-|---|---|---|- G#
@@ -420,28 +560,34 @@ fn test_transpose(
         None,
         Some("10"),
         indoc!("
-            [C# - C# major]
+            [C# - C# major] (1/4)
 
             A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
 
-            A  -|-1-|---|---|---|- C#
-            E  -|-1-|---|---|---|- G#
+            [C# - C# major] (2/4)
+
+            A  -|=1=|---|---|---|- C#
+            E  -|=1=|---|---|---|- G#
             C  -|---|-2-|---|---|- F
             G  -|---|---|-3-|---|- C#
                   4
 
+            [C# - C# major] (3/4)
+
             A  -|---|---|-3-|---|- F
             E  -|---|---|---|-4-|- C#
             C  -|---|---|-2-|---|- G#
             G  -|-1-|---|---|---|- C#
                   6
 
-            A  -|-1-|---|---|---|- F
-            E  -|---|-2-|---|---|- C#
-            C  -|-1-|---|---|---|- G#
+            [C# - C# major] (4/4)
+
+            A  -|=1=|---|---|---|- F
+            E  -|===|-2-|---|---|- C#
+            C  -|=1=|---|---|---|- G#
             G  -|---|---|-3-|---|- F
                   8
         ")
@@ -451,7 +597,7 @@ fn test_transpose(
         Some("5"),
         Some("10"),
         indoc!("
-            [C# - C# major]
+            [C# - C# major] (1/2)
 
             A  -|---|---|-3-|---|- F
             E  -|---|---|---|-4-|- C#
@@ -459,9 +605,11 @@ fn test_transpose(
             G  -|-1-|---|---|---|- C#
                   6
 
-            A  -|-1-|---|---|---|- F
-            E  -|---|-2-|---|---|- C#
-            C  -|-1-|---|---|---|- G#
+            [C# - C# major] (2/2)
+
+            A  -|=1=|---|---|---|- F
+            E  -|===|-2-|---|---|- C#
+            C  -|=1=|---|---|---|- G#
             G  -|---|---|-3-|---|- F
                   8
         ")
@@ -490,176 +638,2309 @@ fn test_all(
     Ok(())
 }
 
-#[rstest(
-    chart,
-    names,
-    case("0000", "C6 - C major 6th\nAm7 - A minor 7th"),
-    case("0003", "C - C major"),
-    case("0013", "Csus4 - C suspended 4th\nFsus2 - F suspended 2nd"),
-    case("10 10 10 10", "Gm7 - G minor 7th\nA#6 - A# major 6th"),
-    case("7604", "Dmaj9 - D major 9th"),
-    case("1304", "Emaj13 - E major 13th")
-)]
-fn test_name(chart: &str, names: &'static str) -> Result<(), Box<dyn Error + 'static>> {
+#[test]
+fn test_all_headers() -> Result<(), Box<dyn Error>> {
     let mut cmd = Command::cargo_bin("ukebox")?;
-    cmd.arg("name").arg(chart);
-    cmd.assert().success().stdout(format!("{names}\n"));
+    cmd.arg("chart").arg("--all").arg("G7");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+
+    // Each chart (represented by the line for its A string) has its own header.
+    let headers: Vec<_> = output
+        .lines()
+        .filter(|l| l.starts_with("[G7 - G dominant 7th]"))
+        .collect();
+    let charts = output.lines().filter(|l| l.starts_with("A ")).count();
+
+    assert!(charts > 1);
+    assert_eq!(headers.len(), charts);
+    assert_eq!(headers[0], format!("[G7 - G dominant 7th] (1/{charts})"));
 
     Ok(())
 }
 
 #[rstest(
-    chart,
-    tuning,
-    names,
-    case("0003", "C", "C - C major"),
-    case("0003", "D", "D - D major"),
-    case("0003", "G", "G - G major"),
-    case("10 10 10 10", "C", "Gm7 - G minor 7th\nA#6 - A# major 6th"),
-    case("10 10 10 10", "D", "C6 - C major 6th\nAm7 - A minor 7th"),
-    case("10 10 10 10", "G", "Dm7 - D minor 7th\nF6 - F major 6th")
+    chord,
+    max_fret,
+    json,
+    case(
+        "C",
+        "12",
+        r#"{"chord":"C","voicings":[{"tuning":"C","uke_strings":[["G",0,"G"],["C",0,"C"],["E",0,"E"],["A",3,"C"]],"span":1,"position":0}]}"#,
+    ),
+    case(
+        "Cm",
+        "0",
+        r#"{"chord":"Cm","voicings":[]}"#,
+    ),
 )]
-fn test_name_with_tuning(
-    chart: &str,
-    tuning: &str,
-    names: &'static str,
-) -> Result<(), Box<dyn Error + 'static>> {
+fn test_chart_json(chord: &str, max_fret: &str, json: &str) -> Result<(), Box<dyn Error>> {
     let mut cmd = Command::cargo_bin("ukebox")?;
-    cmd.arg("name");
-    cmd.arg("--tuning").arg(tuning);
-    cmd.arg(chart);
-    cmd.assert().success().stdout(format!("{names}\n"));
+    cmd.arg("--output")
+        .arg("json")
+        .arg("chart")
+        .arg("--max-fret")
+        .arg(max_fret)
+        .arg(chord);
+    cmd.assert().success().stdout(format!("{json}\n"));
 
     Ok(())
 }
 
 #[rstest(
-    chord_seq,
-    option,
-    chart,
+    fret_pattern,
+    output,
     case(
-        "C",
-        None,
+        "0003",
         indoc!("
-            [C - C major]
-
-            A  ||---|---|-3-|---|- C
-            E o||---|---|---|---|- E
-            C o||---|---|---|---|- C
-            G o||---|---|---|---|- G
+            C - C major
+              A   3  C  R
+              E   0  E  3
+              C   0  C  R
+              G   0  G  5
         ")
     ),
     case(
-        "C Am",
-        None,
+        "x232",
         indoc!("
-            [C - C major]
-
-            A  ||---|---|-3-|---|- C
-            E o||---|---|---|---|- E
-            C o||---|---|---|---|- C
-            G o||---|---|---|---|- G
-
-            [Am - A minor]
-
-            A  ||---|---|-3-|---|- C
-            E o||---|---|---|---|- E
-            C o||---|---|---|---|- C
-            G  ||---|-2-|---|---|- A
+            G/D - G major
+              A   2  B  3
+              E   3  G  R
+              C   2  D  5
+              G   x
         ")
     ),
     case(
-        "C F G",
-        None,
+        "0000",
         indoc!("
-            [C - C major]
+            C6 - C major 6th
+              A   0  A  6
+              E   0  E  3
+              C   0  C  R
+              G   0  G  5
+
+            Am7/C - A minor 7th
+              A   0  A  R
+              E   0  E  5
+              C   0  C  b3
+              G   0  G  b7
+        ")
+    )
+)]
+fn test_name_verbose(fret_pattern: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("name").arg("--verbose").arg(fret_pattern);
+    cmd.assert().success().stdout(output.to_string());
 
-            A  ||---|---|-3-|---|- C
-            E o||---|---|---|---|- E
-            C o||---|---|---|---|- C
-            G o||---|---|---|---|- G
+    Ok(())
+}
 
-            [F - F major]
+#[rstest(fret_pattern, json, case("0003", r#"["C"]"#), case("1234", "[]"))]
+fn test_name_json(fret_pattern: &str, json: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("name")
+        .arg(fret_pattern)
+        .arg("--output")
+        .arg("json");
+    cmd.assert().success().stdout(format!("{json}\n"));
 
-            A  ||---|---|-3-|---|- C
-            E  ||-1-|---|---|---|- F
-            C o||---|---|---|---|- C
-            G  ||---|-2-|---|---|- A
+    Ok(())
+}
 
-            [G - G major]
+#[test]
+fn test_left_handed() -> Result<(), Box<dyn Error>> {
+    let chart = indoc!(
+        "
+        [C - C major]
 
-            A  ||---|-2-|---|---|- B
-            E  ||---|---|-3-|---|- G
-            C  ||---|-1-|---|---|- D
-            G o||---|---|---|---|- G
-        ")
-    ),
-    case(
-        "C F G",
-        Some(("--tuning", "D")),
-        indoc!("
-            [C - C major]
+        G o||---|---|---|---|- G
+        C o||---|---|---|---|- C
+        E o||---|---|---|---|- E
+        A  ||---|---|-3-|---|- C
 
-            B   ||-1-|---|---|---|- C
-            F#  ||-1-|---|---|---|- G
-            D   ||---|-2-|---|---|- E
-            A   ||---|---|-3-|---|- C
+    "
+    );
 
-            [F - F major]
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--left-handed").arg("C");
+    cmd.assert().success().stdout(chart);
 
-            B   ||-1-|---|---|---|- C
-            F#  ||---|---|-4-|---|- A
-            D   ||---|---|-3-|---|- F
-            A   ||---|---|-2-|---|- C
+    Ok(())
+}
 
-            [G - G major]
+#[test]
+fn test_capo() -> Result<(), Box<dyn Error>> {
+    // With a capo at the second fret, D major is played using the C major shape.
+    let chart = indoc!(
+        "
+        [D - D major]
 
-            B  o||---|---|---|---|- B
-            F#  ||-1-|---|---|---|- G
-            D  o||---|---|---|---|- D
-            A   ||---|-2-|---|---|- B
-        ")
-    ),
-    case(
-        "C F G",
-        Some(("--transpose", "1")),
-        indoc!("
-            [C# - C# major]
+        B   ||---|---|-3-|---|- D
+        F# o||---|---|---|---|- F#
+        D  o||---|---|---|---|- D
+        A  o||---|---|---|---|- A
 
-            A  ||---|---|---|-4-|- C#
-            E  ||-1-|---|---|---|- F
-            C  ||-1-|---|---|---|- C#
-            G  ||-1-|---|---|---|- G#
+    "
+    );
 
-            [F# - F# major]
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--capo").arg("2").arg("D");
+    cmd.assert().success().stdout(chart);
 
-            A  ||---|---|---|-4-|- C#
-            E  ||---|-2-|---|---|- F#
-            C  ||-1-|---|---|---|- C#
-            G  ||---|---|-3-|---|- A#
+    Ok(())
+}
 
-            [G# - G# major]
+#[rstest(
+    spelling,
+    chart,
+    case(
+        "flats",
+        indoc!(
+            "
+            [D - D major]
 
-            A  -|-1-|---|---|---|- C
-            E  -|---|-2-|---|---|- G#
-            C  -|-1-|---|---|---|- D#
-            G  -|---|---|-3-|---|- C
-                  3
-        ")
+            A o||---|---|---|---|- A
+            E  ||---|-3-|---|---|- Gb
+            C  ||---|-2-|---|---|- D
+            G  ||---|-1-|---|---|- A
+
+        "
+        )
     ),
-)]
-fn test_voice_lead(
-    chord_seq: &str,
-    option: Option<(&str, &str)>,
-    chart: &'static str,
+    case(
+        "sharps",
+        indoc!(
+            "
+            [D - D major]
+
+            A o||---|---|---|---|- A
+            E  ||---|-3-|---|---|- F#
+            C  ||---|-2-|---|---|- D
+            G  ||---|-1-|---|---|- A
+
+        "
+        )
+    ),
+)]
+fn test_spell(spelling: &str, chart: &'static str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--spell").arg(spelling).arg("D");
+    cmd.assert().success().stdout(chart);
+
+    Ok(())
+}
+
+#[test]
+fn test_spell_sharps() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--spell").arg("sharps").arg("Eb");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("[D# - D# major]"))
+        .stdout(predicate::str::contains("Bb").not());
+
+    Ok(())
+}
+
+#[rstest(chord, case("C"), case("D"), case("Bbm7"))]
+fn test_capo_zero(chord: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg(chord);
+    let expected = cmd.assert().success().get_output().stdout.clone();
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--capo").arg("0").arg(chord);
+    cmd.assert().success().stdout(expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_capo_beyond_max_fret() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart")
+        .arg("--capo")
+        .arg("6")
+        .arg("--max-fret")
+        .arg("5")
+        .arg("C");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the capo (fret 6) cannot be placed beyond the maximal fret (5)",
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    output,
+    case(vec!["C"], "67 60 64 72\n"),
+    case(vec!["--tuning", "c-low-g", "C"], "55 60 64 72\n"),
+    case(vec!["--all", "--max-fret", "3", "C"], "67 60 64 72\n"),
+    case(vec!["--max-fret", "0", "Cm"], ""),
+)]
+fn test_chart_midi(args: Vec<&str>, output: &'static str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--midi").args(args);
+    cmd.assert().success().stdout(output);
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    output,
+    case(vec!["C"], "0 0 0 3\n"),
+    case(vec!["Bb"], "3 2 1 1\n"),
+    case(vec!["--min-fret", "2", "D"], "1 1 1 4\n"),
+    case(vec!["--max-fret", "0", "Cm"], ""),
+)]
+fn test_chart_fingers(args: Vec<&str>, output: &'static str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--fingers").args(args);
+    cmd.assert().success().stdout(output);
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    chart,
+    case(
+        vec!["--max-span", "5", "--min-fret", "3", "C"],
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|=1=|---|---|- C
+            E  ||---|---|=1=|---|---|- G
+            C  ||---|---|---|-2-|---|- E
+            G  ||---|---|---|---|-3-|- C
+        ")
+    ),
+    case(
+        vec!["--max-span", "5", "--min-fret", "3", "--window", "C"],
+        indoc!("
+            [C - C major]
+
+            A  -|=1=|---|---|---|- C
+            E  -|=1=|---|---|---|- G
+            C  -|---|-2-|---|---|- E
+            G  -|---|---|-3-|---|- C
+                  3
+        ")
+    ),
+    case(
+        vec!["--max-span", "5", "--min-fret", "7", "--window", "C"],
+        indoc!("
+            [C - C major]
+
+            A  -|=1=|---|---|---|- E
+            E  -|===|-2-|---|---|- C
+            C  -|=1=|---|---|---|- G
+            G  -|---|---|-3-|---|- E
+                  7
+        ")
+    ),
+)]
+fn test_chart_window(args: Vec<&str>, chart: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").args(args);
+    cmd.assert().success().stdout(format!("{chart}\n"));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    chart,
+    case(
+        vec!["--show-tuning", "C"],
+        indoc!("
+            [C - C major]
+
+            Tuning: G C E A
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+        ")
+    ),
+    case(
+        vec!["--no-notes", "C"],
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|-
+            E o||---|---|---|---|-
+            C o||---|---|---|---|-
+            G o||---|---|---|---|-
+        ")
+    ),
+    case(
+        vec!["--show-tuning", "--no-notes", "--tuning", "D", "C"],
+        indoc!("
+            [C - C major]
+
+            Tuning: A D F# B
+
+            B   ||=1=|---|---|---|-
+            F#  ||=1=|---|---|---|-
+            D   ||---|-2-|---|---|-
+            A   ||---|---|-3-|---|-
+        ")
+    ),
+)]
+fn test_chart_tuning_and_notes(args: Vec<&str>, chart: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").args(args);
+    cmd.assert().success().stdout(format!("{chart}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_intervals() -> Result<(), Box<dyn Error>> {
+    let chart = indoc!(
+        "
+        [C7 - C dominant 7th]
+
+        A  ||-1-|---|---|---|- Bb b7
+        E o||---|---|---|---|- E  3
+        C o||---|---|---|---|- C  R
+        G o||---|---|---|---|- G  5
+
+        R: root, 3: major third, 5: perfect fifth, b7: minor seventh
+    "
+    );
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--intervals").arg("C7");
+    cmd.assert().success().stdout(format!("{chart}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_no_duplicate_shapes() -> Result<(), Box<dyn Error>> {
+    let get_voicings = |args: &[&str]| -> Result<Vec<Value>, Box<dyn Error>> {
+        let mut cmd = Command::cargo_bin("ukebox")?;
+        cmd.args(["--output", "json", "chart", "--all", "--max-fret", "21"])
+            .args(args)
+            .arg("C");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let chart: Value = serde_json::from_slice(&output)?;
+        Ok(chart["voicings"].as_array().unwrap().clone())
+    };
+
+    let all_voicings = get_voicings(&[])?;
+    let voicings = get_voicings(&["--no-duplicate-shapes"])?;
+    assert!(voicings.len() < all_voicings.len());
+
+    // Frets relative to the lowest fret, reduced to a single octave.
+    let shapes: Vec<Vec<u64>> = voicings
+        .iter()
+        .map(|voicing| {
+            let frets: Vec<u64> = voicing["uke_strings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|uke_string| uke_string[1].as_u64().unwrap())
+                .collect();
+            let min_fret = frets.iter().min().unwrap();
+            frets.iter().map(|fret| (fret - min_fret) % 12).collect()
+        })
+        .collect();
+
+    for (i, shape) in shapes.iter().enumerate() {
+        assert!(!shapes[i + 1..].contains(shape));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_svg() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--format").arg("svg").arg("C");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 120 156">"#,
+        ))
+        .stdout(predicate::str::ends_with("</svg>\n"))
+        .stdout(predicate::str::contains("[C - C major]").not());
+
+    Ok(())
+}
+
+#[rstest(
+    max_results,
+    charts,
+    case("3", 3),
+    case("1", 1),
+    case("0", 0),
+    case("1000", 6)
+)]
+fn test_all_max_results(max_results: &str, charts: usize) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart")
+        .arg("--all")
+        .arg("--max-results")
+        .arg(max_results)
+        .arg("G7");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+
+    assert_eq!(
+        output.lines().filter(|l| l.starts_with("A ")).count(),
+        charts
+    );
+
+    // The charts are numbered up to the number of charts shown.
+    let labels: Vec<String> = (1..=charts)
+        .map(|i| format!("[G7 - G dominant 7th] ({i}/{charts})"))
+        .collect();
+    assert_eq!(
+        output
+            .lines()
+            .filter(|l| l.starts_with("[G7 - G dominant 7th] ("))
+            .collect::<Vec<_>>(),
+        labels
+    );
+
+    // The header is printed even if no chart is shown.
+    if charts == 0 {
+        assert_eq!(output, "[G7 - G dominant 7th]\n\n");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_max_results_without_all() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("G7");
+    let expected = cmd.assert().success().get_output().stdout.clone();
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--max-results").arg("3").arg("G7");
+    cmd.assert().success().stdout(expected);
+
+    Ok(())
+}
+
+#[rstest(
+    line,
+    case("C - C major: 0003"),
+    case("Am - A minor: 2000"),
+    case("F - F major: 2010"),
+    case("G - G major: 0232")
+)]
+fn test_suggest(line: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("suggest")
+        .arg("--min-fret")
+        .arg("0")
+        .arg("--max-fret")
+        .arg("3");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!("\n{line}\n")));
+
+    Ok(())
+}
+
+#[test]
+fn test_suggest_open_strings() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("suggest")
+        .arg("--open")
+        .arg("G")
+        .arg("--open")
+        .arg("A");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+
+    for line in output.lines() {
+        let frets = line.rsplit_once(": ").unwrap().1;
+        assert!(frets.starts_with('0') && frets.ends_with('0'));
+    }
+
+    Ok(())
+}
+
+#[rstest(
+    tuning,
+    chords,
+    case("C", "C6 - C major 6th\nAm7 - A minor 7th\n"),
+    case("G", "Em7 - E minor 7th\nG6 - G major 6th\n")
+)]
+fn test_open_chords(tuning: &str, chords: &'static str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("open-chords").arg("--tuning").arg(tuning);
+    cmd.assert().success().stdout(chords);
+
+    Ok(())
+}
+
+#[rstest(
+    chart,
+    names,
+    case("0000", "C6 - C major 6th\nAm7/C - A minor 7th"),
+    case("0003", "C - C major"),
+    case("0013", "Csus4 - C suspended 4th\nFsus2/C - F suspended 2nd"),
+    case("10 10 10 10", "Gm7/A# - G minor 7th\nA#6 - A# major 6th"),
+    case("7604", "Dmaj9/E - D major 9th"),
+    case("1304", "Emaj13/D# - E major 13th"),
+    case(
+        "2075",
+        "Cmaj13 - C major 13th\nD13/C - D dominant 13th\nAm11/C - A minor 11th\nB7alt/C - B altered dominant 7th"
+    ),
+    case("0403", "C/E - C major"),
+    case("x232", "G/D - G major"),
+    case("-232", "G/D - G major")
+)]
+fn test_name(chart: &str, names: &'static str) -> Result<(), Box<dyn Error + 'static>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("name").arg(chart);
+    cmd.assert().success().stdout(format!("{names}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_name_wrong_string_count() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("name").arg("--tuning").arg("guitalele").arg("0003");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the fret pattern has 4 frets but tuning guitalele has 6 strings",
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    from,
+    to,
+    output,
+    case(
+        "0003",
+        "0003",
+        indoc!("
+              A   3 ->  3  0   finger 3 -> 3
+              E   0 ->  0  0
+              C   0 ->  0  0
+              G   0 ->  0  0
+            semitone distance: 0, fingering distance: 0, total cost: 0
+        ")
+    ),
+    case(
+        "0003",
+        "0002",
+        indoc!("
+              A   3 ->  2  -1  finger 3 -> 2
+              E   0 ->  0  0
+              C   0 ->  0  0
+              G   0 ->  0  0
+            semitone distance: 1, fingering distance: 2, total cost: 1
+        ")
+    ),
+    case(
+        "0003",
+        "0232",
+        indoc!("
+              A   3 ->  2  -1  finger 3 -> 2
+              E   0 ->  3  +3  finger - -> 3
+              C   0 ->  2  +2  finger - -> 1
+              G   0 ->  0  0
+            semitone distance: 6, fingering distance: 3, total cost: 6
+        ")
+    ),
+    case(
+        "x232",
+        "0000",
+        indoc!("
+              A   2 ->  0  -2  finger 2 -> -
+              E   3 ->  0  -3  finger 3 -> -
+              C   2 ->  0  -2  finger 1 -> -
+              G   x ->  0  0
+            semitone distance: 7, fingering distance: 3, total cost: 7
+        ")
+    ),
+)]
+fn test_diff(from: &str, to: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("diff").arg(from).arg(to);
+    cmd.assert().success().stdout(output.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_wrong_string_count() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("diff").arg("0003").arg("00003");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the fret pattern has 5 frets but tuning C has 4 strings",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_guitalele() -> Result<(), Box<dyn Error>> {
+    let chart = indoc!(
+        "
+        [G - G major]
+
+        A  ||---|-3-|---|---|- B
+        E  ||---|---|-4-|---|- G
+        C  ||---|-2-|---|---|- D
+        G o||---|---|---|---|- G
+        D o||---|---|---|---|- D
+        A  ||---|-1-|---|---|- B
+
+    "
+    );
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--tuning").arg("guitalele").arg("G");
+    cmd.assert().success().stdout(chart);
+
+    Ok(())
+}
+
+#[rstest(
+    chart,
+    tuning,
+    names,
+    case("0003", "C", "C - C major"),
+    case("0003", "D", "D - D major"),
+    case("0003", "G", "G - G major"),
+    case("10 10 10 10", "C", "Gm7/A# - G minor 7th\nA#6 - A# major 6th"),
+    case("10 10 10 10", "D", "C6 - C major 6th\nAm7/C - A minor 7th"),
+    case("10 10 10 10", "G", "Dm7/F - D minor 7th\nF6 - F major 6th"),
+    case("320003", "guitalele", "C - C major"),
+    case("x 0 2 0 1 0", "guitalele", "Dm7 - D minor 7th\nF6/D - F major 6th")
+)]
+fn test_name_with_tuning(
+    chart: &str,
+    tuning: &str,
+    names: &'static str,
 ) -> Result<(), Box<dyn Error + 'static>> {
     let mut cmd = Command::cargo_bin("ukebox")?;
-    cmd.arg("voice-lead");
-    if let Some((arg, value)) = option {
-        cmd.arg(arg).arg(value);
-    }
-    cmd.arg(chord_seq);
+    cmd.arg("name");
+    cmd.arg("--tuning").arg(tuning);
+    cmd.arg(chart);
+    cmd.assert().success().stdout(format!("{names}\n"));
+
+    Ok(())
+}
+
+#[rstest(
+    chord_seq,
+    option,
+    chart,
+    case(
+        "C",
+        None,
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+        ")
+    ),
+    case(
+        "C Am",
+        None,
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+
+            [Am - A minor]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G  ||---|-2-|---|---|- A
+        ")
+    ),
+    case(
+        "C F G",
+        None,
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+
+            [F - F major]
+
+            A  ||---|---|-3-|---|- C
+            E  ||-1-|---|---|---|- F
+            C o||---|---|---|---|- C
+            G  ||---|-2-|---|---|- A
+
+            [G - G major]
+
+            A  ||---|-2-|---|---|- B
+            E  ||---|---|-3-|---|- G
+            C  ||---|-1-|---|---|- D
+            G o||---|---|---|---|- G
+        ")
+    ),
+    case(
+        "C F G",
+        Some(("--tuning", "D")),
+        indoc!("
+            [C - C major]
+
+            B   ||=1=|---|---|---|- C
+            F#  ||=1=|---|---|---|- G
+            D   ||---|-2-|---|---|- E
+            A   ||---|---|-3-|---|- C
+
+            [F - F major]
+
+            B   ||-1-|---|---|---|- C
+            F#  ||---|---|-4-|---|- A
+            D   ||---|---|-3-|---|- F
+            A   ||---|---|-2-|---|- C
+
+            [G - G major]
+
+            B  o||---|---|---|---|- B
+            F#  ||-1-|---|---|---|- G
+            D  o||---|---|---|---|- D
+            A   ||---|-2-|---|---|- B
+        ")
+    ),
+    case(
+        "C F G",
+        Some(("--transpose", "1")),
+        indoc!("
+            [C# - C# major]
+
+            A  ||---|---|---|-4-|- C#
+            E  ||=1=|---|---|---|- F
+            C  ||=1=|---|---|---|- C#
+            G  ||=1=|---|---|---|- G#
+
+            [F# - F# major]
+
+            A  ||---|---|---|-4-|- C#
+            E  ||---|-2-|---|---|- F#
+            C  ||-1-|---|---|---|- C#
+            G  ||---|---|-3-|---|- A#
+
+            [G# - G# major]
+
+            A  -|=1=|---|---|---|- C
+            E  -|===|-2-|---|---|- G#
+            C  -|=1=|---|---|---|- D#
+            G  -|---|---|-3-|---|- C
+                  3
+        ")
+    ),
+)]
+fn test_voice_lead(
+    chord_seq: &str,
+    option: Option<(&str, &str)>,
+    chart: &'static str,
+) -> Result<(), Box<dyn Error + 'static>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead");
+    if let Some((arg, value)) = option {
+        cmd.arg(arg).arg(value);
+    }
+    cmd.arg(chord_seq);
+    cmd.assert().success().stdout(format!("{chart}\n"));
+
+    Ok(())
+}
+
+#[rstest(
+    tuning,
+    fretboard,
+    case(
+        "C",
+        indoc!("
+              0     1    2    3    4    5
+            A A  || A# | B  | C  | C# | D  |
+            E E  || F  | F# | G  | G# | A  |
+            C C  || C# | D  | D# | E  | F  |
+            G G  || G# | A  | A# | B  | C  |
+        ")
+    ),
+    case(
+        "G",
+        indoc!("
+              0     1    2    3    4    5
+            E E  || F  | F# | G  | G# | A  |
+            B B  || C  | C# | D  | D# | E  |
+            G G  || G# | A  | A# | B  | C  |
+            D D  || D# | E  | F  | F# | G  |
+        ")
+    ),
+)]
+fn test_fretboard(tuning: &str, fretboard: &'static str) -> Result<(), Box<dyn Error + 'static>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("fretboard");
+    cmd.arg("--tuning").arg(tuning);
+    cmd.arg("--max-fret").arg("5");
+    cmd.assert().success().stdout(fretboard);
+
+    Ok(())
+}
+
+#[rstest(
+    note,
+    min_fret,
+    fretboard,
+    case(
+        "C",
+        "0",
+        indoc!("
+              0     1    2    3    4    5    6    7    8    9    10   11   12
+            A    ||    |    | C  |    |    |    |    |    |    |    |    |    |
+            E    ||    |    |    |    |    |    |    | C  |    |    |    |    |
+            C C  ||    |    |    |    |    |    |    |    |    |    |    | C  |
+            G    ||    |    |    |    | C  |    |    |    |    |    |    |    |
+        ")
+    ),
+    case(
+        "Db",
+        "5",
+        indoc!("
+              0     1    2    3    4    5    6    7    8    9    10   11   12
+            A    ||    |    |    |    |    |    |    |    |    |    |    |    |
+            E    ||    |    |    |    |    |    |    |    | Db |    |    |    |
+            C    ||    |    |    |    |    |    |    |    |    |    |    |    |
+            G    ||    |    |    |    |    | Db |    |    |    |    |    |    |
+        ")
+    ),
+)]
+fn test_find(
+    note: &str,
+    min_fret: &str,
+    fretboard: &'static str,
+) -> Result<(), Box<dyn Error + 'static>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("find").arg("--min-fret").arg(min_fret).arg(note);
+    cmd.assert().success().stdout(fretboard);
+
+    Ok(())
+}
+
+#[rstest(
+    merge_repeats,
+    chart,
+    case(
+        false,
+        indoc!("
+            [C - C major]
+
+            A  -|---|---|---|-4-|- G
+            E  -|---|-2-|---|---|- C
+            C  -|-1-|---|---|---|- G
+            G  -|---|---|-3-|---|- E
+                  7
+
+            [C - C major]
+
+            A  -|---|---|---|-4-|- G
+            E  -|---|-2-|---|---|- C
+            C  -|-1-|---|---|---|- G
+            G  -|---|---|-3-|---|- E
+                  7
+
+            [G - G major]
+
+            A  -|---|---|---|-4-|- G
+            E  -|=1=|---|---|---|- B
+            C  -|=1=|---|---|---|- G
+            G  -|=1=|---|---|---|- D
+                  7
+        ")
+    ),
+    case(
+        true,
+        indoc!("
+            [C - C major]
+
+            A  -|---|---|---|-4-|- G
+            E  -|---|-2-|---|---|- C
+            C  -|-1-|---|---|---|- G
+            G  -|---|---|-3-|---|- E
+                  7
+
+            [G - G major]
+
+            A  -|---|---|---|-4-|- G
+            E  -|=1=|---|---|---|- B
+            C  -|=1=|---|---|---|- G
+            G  -|=1=|---|---|---|- D
+                  7
+        ")
+    ),
+)]
+fn test_voice_lead_merge_repeats(
+    merge_repeats: bool,
+    chart: &'static str,
+) -> Result<(), Box<dyn Error + 'static>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead");
+    if merge_repeats {
+        cmd.arg("--merge-repeats");
+    }
+    cmd.arg("C C G");
+    cmd.assert().success().stdout(format!("{chart}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_num_paths() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--num-paths")
+        .arg("2")
+        .arg("C F G");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+
+    let paths: Vec<&str> = output.split("---------------------------\n\n").collect();
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].starts_with("Path 1 (semitone distance: "));
+    assert!(paths[1].starts_with("Path 2 (semitone distance: "));
+    // The charts differ, not just the labels.
+    let charts: Vec<Vec<&str>> = paths.iter().map(|p| p.lines().skip(1).collect()).collect();
+    assert_ne!(charts[0], charts[1]);
+
+    // Only the best path is printed by default.
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("C F G");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Path").not());
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_show_cost() -> Result<(), Box<dyn Error>> {
+    let output = indoc!(
+        "
+        [C - C major]
+
+        A  ||---|---|-3-|---|- C
+        E o||---|---|---|---|- E
+        C o||---|---|---|---|- C
+        G o||---|---|---|---|- G
+
+        -> (semitone distance: 3, fingering distance: 2)
+
+        [F - F major]
+
+        A  ||---|---|-3-|---|- C
+        E  ||-1-|---|---|---|- F
+        C o||---|---|---|---|- C
+        G  ||---|-2-|---|---|- A
+
+        -> (semitone distance: 7, fingering distance: 6)
+
+        [G - G major]
+
+        A  ||---|-2-|---|---|- B
+        E  ||---|---|-3-|---|- G
+        C  ||---|-1-|---|---|- D
+        G o||---|---|---|---|- G
+
+        Total (semitone distance: 10, fingering distance: 8)
+
+    "
+    );
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("--show-cost").arg("C F G");
+    cmd.assert().success().stdout(output);
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_position_weight() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--position-weight")
+        .arg("10")
+        .arg("--output")
+        .arg("json")
+        .arg("C Em Am F G");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let steps: Value = serde_json::from_slice(&output)?;
+
+    for step in steps.as_array().unwrap() {
+        for uke_string in step["voicing"]["uke_strings"].as_array().unwrap() {
+            assert!(uke_string[1].as_u64().unwrap() <= 4);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_json() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--format")
+        .arg("json")
+        .arg("C Am F");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let steps: Value = serde_json::from_slice(&output)?;
+    let steps = steps.as_array().unwrap();
+    assert_eq!(steps.len(), 3);
+
+    let numerals: Vec<_> = steps.iter().map(|s| s["roman_numeral"].clone()).collect();
+    assert_eq!(numerals, vec!["I", "vi", "IV"]);
+
+    let chords: Vec<_> = steps.iter().map(|s| s["chord"].clone()).collect();
+    assert_eq!(chords, vec!["C", "Am", "F"]);
+
+    // The first chord has no predecessor.
+    assert!(steps[0]["distance_from_previous"].is_null());
+
+    for step in &steps[1..] {
+        let dist = &step["distance_from_previous"];
+        assert!(dist["semitone_distance"].is_u64());
+        assert!(dist["fingering_distance"].is_u64());
+    }
+
+    for step in steps {
+        let voicing = &step["voicing"];
+        assert_eq!(voicing["tuning"], "C");
+        assert_eq!(voicing["uke_strings"].as_array().unwrap().len(), 4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_musicxml() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--format")
+        .arg("musicxml")
+        .arg("C F G");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let xml = String::from_utf8(output)?;
+
+    let doc = roxmltree::Document::parse_with_options(
+        &xml,
+        roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(doc.root_element().tag_name().name(), "score-partwise");
+
+    let measures: Vec<_> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("measure"))
+        .collect();
+    assert_eq!(measures.len(), 3);
+
+    for measure in measures {
+        let notes = measure.children().filter(|n| n.has_tag_name("note"));
+        assert_eq!(notes.count(), 4);
+    }
+
+    Ok(())
+}
+
+#[rstest(
+    chord_seq,
+    tuning,
+    chordpro,
+    case(
+        "C",
+        "C",
+        "{define: C base-fret 1 frets 0 0 0 3 fingers 0 0 0 3}\n"
+    ),
+    case(
+        "C",
+        "D",
+        "{define: C base-fret 1 frets 3 2 1 1 fingers 3 2 1 1}\n"
+    ),
+    case(
+        "C F C",
+        "C",
+        indoc!(
+            "
+            {define: C base-fret 1 frets 0 0 0 3 fingers 0 0 0 3}
+            {define: F base-fret 1 frets 2 0 1 3 fingers 2 0 1 3}
+        "
+        )
+    ),
+)]
+fn test_voice_lead_chordpro(
+    chord_seq: &str,
+    tuning: &str,
+    chordpro: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--format")
+        .arg("chordpro")
+        .arg("--tuning")
+        .arg(tuning)
+        .arg(chord_seq);
+    cmd.assert().success().stdout(chordpro.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    chord_seq,
+    tuning,
+    tab,
+    case(
+        "C Am F G",
+        "C",
+        indoc!(
+            "
+               C  Am  F  G
+            A|-3--3---3--2-|
+            E|-0--0---1--3-|
+            C|-0--0---0--2-|
+            G|-0--2---2--0-|
+        "
+        )
+    ),
+    case(
+        "C G",
+        "D",
+        indoc!(
+            "
+                C  G
+            B |-8--8-|
+            F#|-6--5-|
+            D |-5--5-|
+            A |-7--5-|
+        "
+        )
+    ),
+    case(
+        "C(DUDU) G7 Am(DU) F",
+        "C",
+        indoc!(
+            "
+               C(DUDU)  G7  Am(DU)  F
+            A|-3--------2---3-------3-|
+            E|-0--------1---0-------1-|
+            C|-0--------2---0-------0-|
+            G|-0--------0---2-------2-|
+        "
+        )
+    ),
+)]
+fn test_voice_lead_tab(chord_seq: &str, tuning: &str, tab: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--format")
+        .arg("tab")
+        .arg("--tuning")
+        .arg(tuning)
+        .arg(chord_seq);
+    cmd.assert().success().stdout(tab.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    output,
+    case(
+        vec!["C", "major", "--max-fret", "5"],
+        indoc!(
+            "
+            C major: C D E F G A B
+
+            A  0:A  2:B  3:C  5:D
+            E  0:E  1:F  3:G  5:A
+            C  0:C  2:D  4:E  5:F
+            G  0:G  2:A  4:B  5:C
+        "
+        )
+    ),
+    case(
+        vec!["A", "minor-pentatonic", "--min-fret", "5", "--max-fret", "8"],
+        indoc!(
+            "
+            A minor pentatonic: A C D E G
+
+            A  5:D  7:E
+            E  5:A  8:C
+            C  7:G
+            G  5:C  7:D
+        "
+        )
+    ),
+)]
+fn test_scale(args: Vec<&str>, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("scale").args(args);
+    cmd.assert().success().stdout(output.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    output,
+    case(vec!["--by", "3", "C Am F G"], "D# Cm G# A#\n"),
+    case(vec!["--by", "+3", "C Am F G"], "D# Cm G# A#\n"),
+    case(vec!["--by", "-2", "C Am F G"], "Bb Gm Eb F\n"),
+    case(vec!["--to", "D", "C Am F G"], "D Bm G A\n"),
+    case(vec!["--to", "Eb", "C Am F G"], "Eb Cm Ab Bb\n"),
+    case(vec!["--to", "D", "G/B"], "D/F#\n"),
+    case(vec!["--by", "2", "C | Am | F G |"], "D Bm G A\n"),
+    case(vec!["--by", "2", "| C | Am |\n| F | G |\n"], "D Bm G A\n"),
+    case(vec!["--by", "2", "| C x2 | Am % | F G |"], "D D Bm Bm G A\n"),
+    case(vec!["--by", "2", "C(DUDU) Am(DU)"], "D(DUDU) Bm(DU)\n"),
+)]
+fn test_transpose_subcommand(args: Vec<&str>, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("transpose").args(args);
+    cmd.assert().success().stdout(output.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_transpose_subcommand_invalid_chord() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("transpose").arg("--by").arg("1").arg("C | H");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "could not parse chord sequence (invalid chord 'H')",
+    ));
+
+    Ok(())
+}
+
+#[rstest(args, case(vec!["C"]), case(vec!["--by", "1", "--to", "D", "C"]))]
+fn test_transpose_subcommand_args(args: Vec<&str>) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("transpose").args(args);
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[rstest(
+    key,
+    numbers,
+    chord_seq,
+    case("C", "1 6m 4 5", "C Am F G"),
+    case("Bb", "1 4 57", "Bb Eb F7"),
+    case("A", "1 b7 4/6", "A G D/F#"),
+    // The numbers of minor keys are degrees of the major scale on the tonic.
+    case("Am", "1m 4m 5", "Am Dm E")
+)]
+fn test_voice_lead_nashville(
+    key: &str,
+    numbers: &str,
+    chord_seq: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg(chord_seq);
+    let expected = cmd.assert().success().get_output().stdout.clone();
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--key")
+        .arg(key)
+        .arg("--nashville")
+        .arg(numbers);
+    cmd.assert().success().stdout(expected);
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    error,
+    case(vec!["--nashville", "1 4 5"], "--key <KEY>"),
+    case(vec!["--key", "C", "--nashville", "1 8"], "could not parse chord sequence"),
+    case(vec!["--key", "C", "1 4 5"], "could not parse chord sequence")
+)]
+fn test_voice_lead_nashville_error(args: Vec<&str>, error: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").args(args);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_json_key() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--output")
+        .arg("json")
+        .arg("--key")
+        .arg("G")
+        .arg("C D G");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let steps: Value = serde_json::from_slice(&output)?;
+
+    let numerals: Vec<&str> = steps
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["roman_numeral"].as_str().unwrap())
+        .collect();
+    assert_eq!(numerals, ["IV", "V", "I"]);
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    output,
+    case(vec!["--key", "C", "C Am F G"], "I vi IV V\n"),
+    case(vec!["C Am F G"], "I vi IV V\n"),
+    case(vec!["--key", "C", "C A7 Dm G7 C"], "I V7/ii ii V7 I\n"),
+    case(vec!["--key", "C", "C Bb F C"], "I bVII IV I\n"),
+    case(vec!["--key", "G", "Am7 D7 Gmaj7"], "ii7 V7 Imaj7\n"),
+    case(vec![""], "\n"),
+)]
+fn test_analyze(args: Vec<&str>, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("analyze").args(args);
+    cmd.assert().success().stdout(output.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    chord,
+    chart,
+    case(
+        "C(no5)",
+        indoc!(
+            "
+            [C(no5) - C major]
+
+            A  -|-1-|---|---|---|- C
+            E o-|---|---|---|---|- E
+            C o-|---|---|---|---|- C
+            G  -|---|---|-3-|---|- C
+                  3
+
+        "
+        )
+    ),
+    case(
+        "Cno3",
+        indoc!(
+            "
+            [C(no3) - C major]
+
+            A  ||---|---|-4-|---|- C
+            E  ||---|---|-3-|---|- G
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+
+        "
+        )
+    )
+)]
+fn test_omitted_tones(chord: &str, chart: &'static str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg(chord);
+    cmd.assert().success().stdout(chart);
+
+    Ok(())
+}
+
+#[rstest(
+    line,
+    case("C added 11th - Cadd11"),
+    case("C added 13th - Cadd13"),
+    case("C 5th - C5")
+)]
+fn test_chords_added_tones(line: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chords");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(line));
+
+    Ok(())
+}
+
+#[test]
+fn test_chords_filter() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chords")
+        .arg("--filter")
+        .arg("minor")
+        .arg("--root")
+        .arg("D");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The root note D is used as an example.",
+        ))
+        .stdout(predicate::str::contains("D minor 7th - Dm7, Dmin7"))
+        .stdout(predicate::str::contains("D major").not())
+        .stdout(predicate::str::contains("D diminished").not());
+
+    Ok(())
+}
+
+#[rstest(
+    filter,
+    chord_types,
+    case("diminished", vec!["Diminished", "DiminishedSeventh", "HalfDiminishedSeventh"]),
+    case(
+        "dominant",
+        vec![
+            "DominantSeventh",
+            "DominantNinth",
+            "DominantEleventh",
+            "DominantThirteenth",
+            "DominantSeventhFlatNinth",
+            "DominantSeventhSharpNinth",
+            "DominantSeventhFlatFifth",
+            "DominantSeventhSharpEleventh",
+            "DominantSeventhFlatThirteenth",
+            "AlteredDominantSeventh",
+            "AugmentedSeventh",
+        ]
+    )
+)]
+fn test_chords_filter_json(filter: &str, chord_types: Vec<&str>) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chords").arg("--filter").arg(filter);
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let found: Vec<_> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["chord_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(found.len(), chord_types.len());
+    for chord_type in chord_types {
+        assert!(found.contains(&chord_type));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_chords_difficulty() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output")
+        .arg("json")
+        .arg("chords")
+        .arg("--difficulty");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let difficulty = |chord_type: &str| {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["chord_type"] == chord_type)
+            .and_then(|c| c["difficulty"].as_f64())
+            .unwrap()
+    };
+    assert!(difficulty("Major") < difficulty("DominantThirteenth"));
+    assert!(difficulty("MajorSixth") < difficulty("Diminished"));
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chords").arg("--difficulty");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "C major - C, Cmaj, CM (difficulty: 9.6)",
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    chord,
+    relative,
+    case("C", "Am"),
+    case("Am", "C"),
+    case("Eb", "Cm"),
+    case("F#m7", "Amaj7")
+)]
+fn test_relative(chord: &str, relative: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("relative").arg(chord);
+    cmd.assert().success().stdout(format!("{relative}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_relative_none() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("relative").arg("G7");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "chord G7 has neither a relative minor nor a relative major chord",
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    custom_tuning,
+    chart,
+    case(
+        "A D F# B",
+        indoc!("
+            [C - C major]
+
+            B   ||=1=|---|---|---|- C
+            F#  ||=1=|---|---|---|- G
+            D   ||---|-2-|---|---|- E
+            A   ||---|---|-3-|---|- C
+
+        ")
+    ),
+    case(
+        "G C E G",
+        indoc!("
+            [C - C major]
+
+            G o||---|---|---|---|- G
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+
+        ")
+    )
+)]
+fn test_custom_tuning(custom_tuning: &str, chart: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--custom-tuning")
+        .arg(custom_tuning)
+        .arg("chart")
+        .arg("C");
+    cmd.assert().success().stdout(chart.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    error,
+    case(
+        vec!["--custom-tuning", "A D F#"],
+        "the custom tuning has 3 strings but tuning C has 4 strings"
+    ),
+    case(
+        vec!["--custom-tuning", "A D F# B", "--tuning", "guitalele"],
+        "the custom tuning has 4 strings but tuning guitalele has 6 strings"
+    ),
+    case(vec!["--custom-tuning", "A D H B"], "could not parse tuning 'A D H B'")
+)]
+fn test_custom_tuning_invalid(args: Vec<&str>, error: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.args(args).arg("chart").arg("C");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_per_row() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--all").arg("--max-results").arg("3");
+    cmd.arg("--per-row").arg("2").arg("C");
+    cmd.assert().success().stdout(indoc!(
+        "
+        [C - C major] (1/3)         [C - C major] (2/3)
+
+        A  ||---|---|-3-|---|- C    A  ||---|---|-1-|---|- C
+        E o||---|---|---|---|- E    E o||---|---|---|---|- E
+        C o||---|---|---|---|- C    C  ||---|---|---|-2-|- E
+        G o||---|---|---|---|- G    G o||---|---|---|---|- G
+
+        [C - C major] (3/3)
+
+        A  ||---|---|=1=|---|- C
+        E  ||---|---|=1=|---|- G
+        C  ||---|---|---|-2-|- E
+        G o||---|---|---|---|- G
+
+        "
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    style,
+    chart,
+    case(
+        "ascii",
+        indoc!("
+            [C - C major]
+
+            A  ||---|---|-3-|---|- C
+            E o||---|---|---|---|- E
+            C o||---|---|---|---|- C
+            G o||---|---|---|---|- G
+        ")
+    ),
+    case(
+        "unicode",
+        indoc!("
+            [C - C major]
+
+            A  ║║───┼───┼─3─┼───┼─ C
+            E ○║║───┼───┼───┼───┼─ E
+            C ○║║───┼───┼───┼───┼─ C
+            G ○║║───┼───┼───┼───┼─ G
+        ")
+    )
+)]
+fn test_chart_style(style: &str, chart: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--style").arg(style).arg("C");
     cmd.assert().success().stdout(format!("{chart}\n"));
 
     Ok(())
 }
+
+#[rstest(
+    chord_seq,
+    output,
+    case("Bb Eb F", "Capo on fret 3: G C D"),
+    case("Bb Gm Eb F", "Capo on fret 3: G Em C D"),
+    case("E A B7", "Capo on fret 2: D G A7"),
+    case("C F G", "No capo needed: C F G")
+)]
+fn test_capo_subcommand(chord_seq: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("capo").arg(chord_seq);
+    cmd.assert().success().stdout(format!("{output}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_capo_subcommand_not_found() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("capo").arg("--max-fret").arg("0").arg("Cm C6");
+    cmd.assert()
+        .success()
+        .stdout("No matching capo position was found\n");
+
+    Ok(())
+}
+
+#[rstest(chord_seq, case(""), case(" | | "))]
+fn test_capo_subcommand_empty(chord_seq: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("capo").arg(chord_seq);
+    cmd.assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "the chord sequence is empty (it should contain at least one chord)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_sort_by_span() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart")
+        .arg("--all")
+        .arg("--sort-by")
+        .arg("span")
+        .arg("G7");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let spans: Vec<u64> = json["voicings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|voicing| voicing["span"].as_u64().unwrap())
+        .collect();
+    assert!(spans.len() > 1);
+    assert!(spans.windows(2).all(|w| w[0] <= w[1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_sort_by_span_text() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--all").arg("--sort-by").arg("span");
+    cmd.arg("--max-results").arg("2").arg("G7");
+    cmd.assert().success().stdout(indoc!(
+        "
+        [G7 - G dominant 7th] (1/2)
+
+        A  ||---|-3-|---|---|- B
+        E  ||-1-|---|---|---|- F
+        C  ||---|-2-|---|---|- D
+        G o||---|---|---|---|- G
+
+        [G7 - G dominant 7th] (2/2)
+
+        A  -|---|-2-|---|---|- F
+        E  -|=1=|---|---|---|- B
+        C  -|=1=|---|---|---|- G
+        G  -|=1=|---|---|---|- D
+              7
+
+        "
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_range() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart")
+        .arg("--all")
+        .arg("--range")
+        .arg("G4-")
+        .arg("C");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    // The C string has to be pressed down at least on fret 7 to sound G4 or higher.
+    let json: Value = serde_json::from_slice(&output)?;
+    let voicings = json["voicings"].as_array().unwrap();
+    assert_eq!(voicings.len(), 7);
+    assert!(voicings
+        .iter()
+        .all(|voicing| voicing["uke_strings"][1][1].as_u64().unwrap() >= 7));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_range_text() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--all").arg("--range").arg("C4-C5");
+    cmd.arg("--max-results").arg("1").arg("C");
+    cmd.assert().success().stdout(indoc!(
+        "
+        [C - C major] (1/1)
+
+        A  ||---|---|-3-|---|- C
+        E o||---|---|---|---|- E
+        C o||---|---|---|---|- C
+        G o||---|---|---|---|- G
+
+        "
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    range,
+    case("C4"),
+    case("-"),
+    case("H3-C5"),
+    case("A5-C4"),
+    case("C30-"),
+    case("C4-C30")
+)]
+fn test_chart_range_invalid(range: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--range").arg(range).arg("C");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--range <LOWEST-HIGHEST>"));
+
+    Ok(())
+}
+
+#[rstest(tuning, case("DGBE"), case("D G B E"), case("D4-G4-B4-E4"))]
+fn test_tuning_notes(tuning: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--tuning").arg(tuning).arg("chart").arg("C");
+    cmd.assert().success().stdout(indoc!(
+        "
+        [C - C major]
+
+        E o||---|---|---|---|- E
+        B  ||-1-|---|---|---|- C
+        G o||---|---|---|---|- G
+        D  ||---|-2-|---|---|- E
+
+        "
+    ));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    error,
+    case(vec!["--tuning", "DGXE"], "could not parse tuning 'DGXE'"),
+    case(
+        vec!["--tuning", "DGBEA", "--custom-tuning", "A D F# B"],
+        "the custom tuning has 4 strings but tuning D-G-B-E-A has 5 strings"
+    ),
+    case(
+        vec!["--tuning", "GCE"],
+        "tuning 'GCE' has 3 strings but at least 4 strings are needed"
+    )
+)]
+fn test_tuning_notes_invalid(args: Vec<&str>, error: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.args(args).arg("chart").arg("C");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    count,
+    case(vec!["C"], 24),
+    case(vec!["C7"], 7),
+    case(vec!["--max-fret", "3", "C"], 1),
+    case(vec!["--no-duplicate-shapes", "C"], 16),
+    case(vec!["--tuning", "D", "--max-span", "2", "G"], 2)
+)]
+fn test_chart_count(args: Vec<&str>, count: usize) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--count").args(args);
+    cmd.assert().success().stdout(format!("{count}\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_count_matches_all() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart").arg("--all").arg("Bbm7");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let json: Value = serde_json::from_slice(&output)?;
+
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--count").arg("Bbm7");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let count: usize = String::from_utf8(output)?.trim().parse()?;
+
+    assert_eq!(json["voicings"].as_array().unwrap().len(), count);
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_count_conflict() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").arg("--count").arg("--midi").arg("C");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    frets,
+    case(vec!["--drone", "4", "C"], vec![[0, 0, 0, 0], [5, 4, 3, 0], [5, 7, 0, 0]]),
+    case(vec!["--drone", "1", "--drone-chord-tone", "C"], vec![[0, 0, 0, 3], [0, 4, 0, 3], [0, 4, 3, 3]]),
+    // With a capo, the drone string is played at the capo.
+    case(vec!["--capo", "2", "--drone", "4", "D"], vec![[0, 0, 0, 0], [5, 4, 3, 0], [5, 7, 0, 0]]),
+    case(vec!["--capo", "2", "--min-fret", "4", "--drone", "4", "D"], vec![[5, 4, 3, 0], [9, 7, 8, 0]])
+)]
+fn test_chart_drone(args: Vec<&str>, frets: Vec<[u64; 4]>) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart")
+        .arg("--all")
+        .arg("--max-results")
+        .arg("3")
+        .args(args);
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let voicing_frets: Vec<[u64; 4]> = json["voicings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|voicing| {
+            let mut frets = [0; 4];
+            for (i, uke_string) in voicing["uke_strings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+            {
+                frets[i] = uke_string[1].as_u64().unwrap();
+            }
+            frets
+        })
+        .collect();
+    assert_eq!(voicing_frets, frets);
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    error,
+    case(vec!["--drone", "5", "C"], "the drone string 5 does not exist in tuning C with 4 strings"),
+    case(vec!["--drone", "0", "C"], "invalid value '0' for '--drone <STRING>'"),
+    case(vec!["--drone-chord-tone", "C"], "--drone <STRING>")
+)]
+fn test_chart_drone_invalid(args: Vec<&str>, error: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart").args(args);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    frets,
+    case(vec!["C"], vec![vec![0, 0, 0, 3], vec![0, 0, 0, 7], vec![0, 0, 8, 7]]),
+    case(
+        vec!["--tuning", "c-low-g", "C"],
+        vec![vec![5, 4, 3, 3], vec![5, 7, 0, 7], vec![5, 7, 8, 7]]
+    )
+)]
+fn test_chart_root_in_bass(args: Vec<&str>, frets: Vec<Vec<u64>>) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart").arg("--all").arg("--max-results").arg("3");
+    cmd.arg("--root-in-bass").args(args);
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let voicing_frets: Vec<Vec<u64>> = json["voicings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|voicing| {
+            voicing["uke_strings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|uke_string| uke_string[1].as_u64().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(voicing_frets, frets);
+
+    Ok(())
+}
+
+#[rstest(
+    chord,
+    max_open_strings,
+    chart,
+    case(
+        "C",
+        "0",
+        indoc!("
+            [C - C major]
+
+            A  -|=1=|---|---|---|- C
+            E  -|=1=|---|---|---|- G
+            C  -|---|-2-|---|---|- E
+            G  -|---|---|-3-|---|- C
+                  3
+
+        ")
+    ),
+    case(
+        "Am",
+        "1",
+        indoc!("
+            [Am - A minor]
+
+            A  ||---|---|-2-|---|- C
+            E o||---|---|---|---|- E
+            C  ||---|---|---|-3-|- E
+            G  ||---|-1-|---|---|- A
+
+        ")
+    )
+)]
+fn test_chart_max_open_strings(
+    chord: &str,
+    max_open_strings: &str,
+    chart: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart")
+        .arg("--max-open-strings")
+        .arg(max_open_strings)
+        .arg(chord);
+    cmd.assert().success().stdout(chart.to_string());
+
+    Ok(())
+}
+
+#[rstest(
+    line,
+    case("C major - C, Cmaj, CM - 1 3 5"),
+    case("C dominant 7th - C7, Cdom - 1 3 5 b7"),
+    case("C half-diminished 7th - Cm7b5, Cø, Cø7 - 1 b3 b5 b7"),
+    case("C diminished 7th - Cdim7, Co7 - 1 b3 b5 bb7"),
+    case("C dominant 7th sharp 9th - C7#9 - 1 3 5 b7 #9")
+)]
+fn test_chords_print_intervals(line: &str) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chords").arg("--print-intervals");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+
+    assert!(output.lines().any(|l| l == line));
+
+    // Apart from the header, there is one line per chord type.
+    let chord_type_lines = output.lines().filter(|l| l.contains(" - ")).count();
+    assert_eq!(chord_type_lines, ukebox::ChordType::values().count());
+
+    Ok(())
+}
+
+#[test]
+fn test_chords_print_intervals_json() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output")
+        .arg("json")
+        .arg("chords")
+        .arg("--print-intervals");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output)?;
+    let dominant_seventh = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["chord_type"] == "DominantSeventh")
+        .unwrap();
+    assert_eq!(
+        dominant_seventh["intervals"],
+        serde_json::json!(["1", "3", "5", "b7"])
+    );
+
+    Ok(())
+}
+
+#[rstest(
+    key,
+    chord_seq,
+    warnings,
+    case("C", "C Am F G", ""),
+    case("C", "C E7 Am", "warning: chord E7 is not diatonic to C major\n"),
+    case("Am", "Am Dm E7 Am", ""),
+    case(
+        "Am",
+        "Am D F# E",
+        "warning: chord D is not diatonic to A minor\nwarning: chord F# is not diatonic to A minor\n"
+    )
+)]
+fn test_voice_lead_check_key(
+    key: &str,
+    chord_seq: &str,
+    warnings: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg(chord_seq);
+    let expected = cmd.assert().success().get_output().stdout.clone();
+
+    // The voice leading itself is not affected by the check.
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--key")
+        .arg(key)
+        .arg("--check-key")
+        .arg(chord_seq);
+    cmd.assert()
+        .success()
+        .stdout(expected)
+        .stderr(warnings.to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_check_key_invalid() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead")
+        .arg("--key")
+        .arg("H")
+        .arg("--check-key")
+        .arg("C F G");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("could not parse key 'H'"));
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_lead_check_key_without_key() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("voice-lead").arg("--check-key").arg("C F G");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--key <KEY>"));
+
+    Ok(())
+}
+
+#[test]
+fn test_chords_grouped_by_stack_height() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chords");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+    let lines: Vec<&str> = output.lines().collect();
+    let position = |line: &str| lines.iter().position(|&l| l == line).unwrap();
+
+    // Triads precede sixths, sevenths and ninths, each group under its header.
+    let headers = [
+        "Triads, power chords and added 4th chords",
+        "Sixth chords",
+        "Seventh chords",
+        "Ninth chords",
+        "Eleventh chords",
+        "Thirteenth chords",
+    ];
+    for (h1, h2) in headers.iter().zip(headers.iter().skip(1)) {
+        assert!(position(h1) < position(h2));
+    }
+
+    assert!(position("C minor - Cm, Cmin") > position("Triads, power chords and added 4th chords"));
+    assert!(position("C 5th - C5") < position("Sixth chords"));
+    assert!(position("C added 4th - Cadd4") < position("Sixth chords"));
+    assert!(position("C minor - Cm, Cmin") < position("C major 6th - C6, Cmaj6, CM6"));
+    assert!(position("C major 6th - C6, Cmaj6, CM6") < position("C dominant 7th - C7, Cdom"));
+    assert!(position("C dominant 7th - C7, Cdom") < position("Ninth chords"));
+    assert!(position("C dominant 9th - C9") > position("Ninth chords"));
+    assert!(position("C dominant 9th - C9") < position("Eleventh chords"));
+
+    Ok(())
+}
+
+#[rstest(
+    args,
+    frets,
+    case(vec!["--max-span", "2"], vec![vec![0, 0, 0, 3]]),
+    case(vec!["--max-open-strings", "0"], vec![vec![5, 4, 3, 3]]),
+    case(vec!["--all", "--max-fret", "5"], vec![vec![0, 0, 0, 3]]),
+    case(vec!["--all", "--min-fret", "5", "--max-fret", "7"], vec![vec![5, 4, 3, 3]])
+)]
+fn test_chart_capo_relative(args: Vec<&str>, frets: Vec<Vec<u64>>) -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart").arg("--capo").arg("2").args(args).arg("D");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    // The frets are counted from the capo, so strings at the capo are open.
+    let json: Value = serde_json::from_slice(&output)?;
+    let voicing_frets: Vec<Vec<u64>> = json["voicings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|voicing| {
+            voicing["uke_strings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|uke_string| uke_string[1].as_u64().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(voicing_frets, frets);
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_count_json() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("--output").arg("json");
+    cmd.arg("chart")
+        .arg("--count")
+        .arg("--no-duplicate-shapes")
+        .arg("C");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let json: Value = serde_json::from_slice(&output)?;
+
+    assert_eq!(json["chord"], "C");
+    assert_eq!(json["count"], 16);
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_svg_all() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart")
+        .arg("--all")
+        .arg("--format")
+        .arg("svg")
+        .arg("C");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "'--all' cannot be used with '--format svg'",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_svg_no_match() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("ukebox")?;
+    cmd.arg("chart")
+        .arg("--format")
+        .arg("svg")
+        .arg("--max-fret")
+        .arg("0")
+        .arg("Cm");
+    cmd.assert()
+        .success()
+        .stdout("")
+        .stderr("No matching chord voicing was found\n");
+
+    Ok(())
+}